@@ -43,11 +43,12 @@ public partial class LightWalletClient : IDisposable
 	/// This must match the network that the server at <paramref name="serverUrl"/> operates on.
 	/// </param>
 	/// <param name="dataFile">The path to the sqlite database to load (or create) that stores the decrypted transactions.</param>
-	public LightWalletClient(Uri serverUrl, ZcashNetwork network, string dataFile)
+	/// <param name="passphrase">The passphrase that <paramref name="dataFile"/> is encrypted with, or <see langword="null" /> to store it unencrypted.</param>
+	public LightWalletClient(Uri serverUrl, ZcashNetwork network, string dataFile, string? passphrase = null)
 	{
 		Requires.NotNull(serverUrl);
 
-		this.dbinit = new(dataFile, ToChainType(network), MinimumConfirmations);
+		this.dbinit = new(dataFile, ToChainType(network), MinimumConfirmations, passphrase);
 		this.serverUrl = serverUrl;
 		this.Network = network;
 
@@ -55,6 +56,21 @@ public partial class LightWalletClient : IDisposable
 		this.ReadAccountsFromDatabase();
 	}
 
+	/// <summary>
+	/// Occurs when <see cref="DownloadTransactionsAsync"/> finds that the blockchain was reorganized
+	/// and rewinds the wallet to before the reorganization.
+	/// </summary>
+	/// <remarks>
+	/// Confirmations and transactions from <see cref="ReorgDetectedEventArgs.ToHeight"/> onward may have changed.
+	/// </remarks>
+	public event EventHandler<ReorgDetectedEventArgs>? ReorgDetected;
+
+	/// <summary>
+	/// Occurs when a request that <see cref="DownloadTransactionsAsync"/> makes to the lightwallet server fails
+	/// and will be retried after a delay.
+	/// </summary>
+	public event EventHandler<RequestRetryingEventArgs>? RequestRetrying;
+
 	/// <summary>
 	/// Gets the Zcash network that this client operates on.
 	/// </summary>
@@ -206,7 +222,7 @@ public partial class LightWalletClient : IDisposable
 			throw new InvalidOperationException(Strings.UnrecognizedAccount);
 		}
 
-		return (UnifiedAddress)ZcashAddress.Decode(LightWalletMethods.AddDiversifier(this.dbinit, accountId, diversifierIndex[..].ToArray()));
+		return (UnifiedAddress)ZcashAddress.Decode(LightWalletMethods.AddDiversifier(this.dbinit, accountId, diversifierIndex[..].ToArray(), receiverTypes: null));
 	}
 
 	/// <summary>
@@ -325,7 +341,8 @@ public partial class LightWalletClient : IDisposable
 				this.dbinit,
 				this.serverUrl.AbsoluteUri,
 				this.GetUnifiedSpendingKeyBytes(account),
-				details);
+				details,
+				coinSelection: null);
 			return result.Select(r => new TxId(r.txid)).ToArray();
 		}
 		catch (uniffi.LightWallet.LightWalletException ex)
@@ -351,7 +368,8 @@ public partial class LightWalletClient : IDisposable
 			SendDetails sendDetails = LightWalletMethods.SimulateSend(
 				this.dbinit,
 				account.FullViewing.UnifiedKey.TextEncoding,
-				LineItemsToSendDetails(payments));
+				LineItemsToSendDetails(payments),
+				coinSelection: null);
 
 			return new SpendDetails(sendDetails);
 		}
@@ -686,6 +704,48 @@ public partial class LightWalletClient : IDisposable
 		public double PercentComplete => this.TotalSteps == 0 ? 0 : (double)this.CurrentStep * 100 / this.TotalSteps;
 	}
 
+	/// <summary>
+	/// Describes a blockchain reorganization that was detected during a <see cref="DownloadTransactionsAsync"/> operation.
+	/// </summary>
+	/// <param name="fromHeight">The height at which the reorganization was detected.</param>
+	/// <param name="toHeight">The height that the wallet was rewound to.</param>
+	public class ReorgDetectedEventArgs(uint fromHeight, uint toHeight) : EventArgs
+	{
+		/// <summary>
+		/// Gets the height at which the reorganization was detected.
+		/// </summary>
+		public uint FromHeight => fromHeight;
+
+		/// <summary>
+		/// Gets the height that the wallet was rewound to.
+		/// </summary>
+		public uint ToHeight => toHeight;
+	}
+
+	/// <summary>
+	/// Describes a failed request to the lightwallet server that will be retried.
+	/// </summary>
+	/// <param name="retry">The number of the upcoming retry, starting from 1.</param>
+	/// <param name="delay">How long until the request is retried.</param>
+	/// <param name="error">The error that the request failed with.</param>
+	public class RequestRetryingEventArgs(uint retry, TimeSpan delay, string error) : EventArgs
+	{
+		/// <summary>
+		/// Gets the number of the upcoming retry, starting from 1.
+		/// </summary>
+		public uint Retry => retry;
+
+		/// <summary>
+		/// Gets how long until the request is retried.
+		/// </summary>
+		public TimeSpan Delay => delay;
+
+		/// <summary>
+		/// Gets the error that the request failed with.
+		/// </summary>
+		public string Error => error;
+	}
+
 	private class SyncUpdateSink(
 		LightWalletClient client,
 		IProgress<SyncProgress>? statusUpdates,
@@ -710,6 +770,10 @@ public partial class LightWalletClient : IDisposable
 					g.Select(CreateTransaction).ToArray()));
 			discoveredTransactions.Report(dictionary);
 		}
+
+		public void ReorgDetected(uint fromHeight, uint toHeight) => client.ReorgDetected?.Invoke(client, new(fromHeight, toHeight));
+
+		public void OnRetry(uint retry, ulong delayMs, string error) => client.RequestRetrying?.Invoke(client, new(retry, TimeSpan.FromMilliseconds(delayMs), error));
 	}
 
 	private class Cancellation : CancellationSource, IDisposable
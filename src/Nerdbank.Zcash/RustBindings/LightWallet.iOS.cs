@@ -527,6 +527,8 @@ static class _UniFFILib
 		_UniFFILib.uniffiCheckApiChecksums();
 
 		FfiConverterTypeCancellationSource.INSTANCE.Register();
+		FfiConverterTypeLogSink.INSTANCE.Register();
+		FfiConverterTypeParameterDownloadProgress.INSTANCE.Register();
 		FfiConverterTypeSyncUpdate.INSTANCE.Register();
 	}
 
@@ -536,6 +538,18 @@ static class _UniFFILib
 		ref RustCallStatus _uniffi_out_err
 	);
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_init_callback_logsink(
+		ForeignCallback @callbackStub,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_init_callback_parameterdownloadprogress(
+		ForeignCallback @callbackStub,
+		ref RustCallStatus _uniffi_out_err
+	);
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern void uniffi_nerdbank_zcash_rust_fn_init_callback_syncupdate(
 		ForeignCallback @callbackStub,
@@ -558,18 +572,62 @@ static class _UniFFILib
 		RustBuffer @config,
 		uint @accountId,
 		RustBuffer @diversifierIndex,
+		RustBuffer @receiverTypes,
 		ref RustCallStatus _uniffi_out_err
 	);
 
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern void uniffi_nerdbank_zcash_rust_fn_func_cancel(uint @id, ref RustCallStatus _uniffi_out_err);
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_check_wallet(RustBuffer @config, ref RustCallStatus _uniffi_out_err);
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern sbyte uniffi_nerdbank_zcash_rust_fn_func_disconnect_server(RustBuffer @uri, ref RustCallStatus _uniffi_out_err);
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_discover_accounts(
+		RustBuffer @config,
+		RustBuffer @uri,
+		RustBuffer @seed,
+		uint @maxGap,
+		RustBuffer @birthdayHeight,
+		RustBuffer @cancellation,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_export_backup(
+		RustBuffer @config,
+		RustBuffer @path,
+		RustBuffer @passphrase,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_find_account_by_seed(
+		RustBuffer @config,
+		RustBuffer @seed,
+		uint @accountIndex,
+		ref RustCallStatus _uniffi_out_err
+	);
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_get_accounts(RustBuffer @config, ref RustCallStatus _uniffi_out_err);
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_get_address_book(
+		RustBuffer @config,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_get_batch(
+		RustBuffer @config,
+		RustBuffer @batchId,
+		ref RustCallStatus _uniffi_out_err
+	);
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_get_birthday_height(
 		RustBuffer @config,
@@ -632,12 +690,177 @@ static class _UniFFILib
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern void uniffi_nerdbank_zcash_rust_fn_func_init(RustBuffer @config, ref RustCallStatus _uniffi_out_err);
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_list_addresses(
+		RustBuffer @config,
+		uint @accountId,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_list_notes(
+		RustBuffer @config,
+		uint @accountId,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_maintain_database(
+		RustBuffer @config,
+		RustBuffer @options,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_next_unused_address(
+		RustBuffer @config,
+		uint @accountId,
+		RustBuffer @receiverTypes,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_prefetch_parameters(
+		RustBuffer @progress,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_rank_servers(RustBuffer @uris, ref RustCallStatus _uniffi_out_err);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_rekey_database(
+		RustBuffer @config,
+		RustBuffer @newPassphrase,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_release_prover(ref RustCallStatus _uniffi_out_err);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern sbyte uniffi_nerdbank_zcash_rust_fn_func_remove_account(
+		RustBuffer @config,
+		uint @accountId,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern sbyte uniffi_nerdbank_zcash_rust_fn_func_remove_address_book_entry(
+		RustBuffer @config,
+		uint @id,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_repair_wallet(
+		RustBuffer @config,
+		RustBuffer @repairs,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_rescan_account(
+		RustBuffer @config,
+		uint @accountId,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_rescan_from(
+		RustBuffer @config,
+		uint @height,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_restore_backup(
+		RustBuffer @path,
+		RustBuffer @passphrase,
+		RustBuffer @newDataFile,
+		RustBuffer @newPassphrase,
+		ref RustCallStatus _uniffi_out_err
+	);
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_send(
 		RustBuffer @config,
 		RustBuffer @uri,
 		RustBuffer @usk,
 		RustBuffer @sendDetails,
+		RustBuffer @coinSelection,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern RustBuffer uniffi_nerdbank_zcash_rust_fn_func_send_batch(
+		RustBuffer @config,
+		RustBuffer @uri,
+		RustBuffer @usk,
+		RustBuffer @batchId,
+		RustBuffer @sendDetails,
+		RustBuffer @options,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_account_metadata(
+		RustBuffer @config,
+		uint @accountId,
+		RustBuffer @key,
+		RustBuffer @value,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_account_name(
+		RustBuffer @config,
+		uint @accountId,
+		RustBuffer @name,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern uint uniffi_nerdbank_zcash_rust_fn_func_set_address_book_entry(
+		RustBuffer @config,
+		RustBuffer @entry,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_address_label(
+		RustBuffer @config,
+		uint @accountId,
+		RustBuffer @diversifierIndex,
+		RustBuffer @label,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_connection_options(
+		RustBuffer @uri,
+		RustBuffer @options,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_log_sink(
+		RustBuffer @sink,
+		RustBuffer @level,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_parameter_paths(RustBuffer @paths, ref RustCallStatus _uniffi_out_err);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_tor(RustBuffer @options, ref RustCallStatus _uniffi_out_err);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern void uniffi_nerdbank_zcash_rust_fn_func_set_transaction_label(
+		RustBuffer @config,
+		RustBuffer @txid,
+		RustBuffer @label,
 		ref RustCallStatus _uniffi_out_err
 	);
 
@@ -655,6 +878,7 @@ static class _UniFFILib
 		RustBuffer @config,
 		RustBuffer @ufvk,
 		RustBuffer @sendDetails,
+		RustBuffer @coinSelection,
 		ref RustCallStatus _uniffi_out_err
 	);
 
@@ -665,6 +889,15 @@ static class _UniFFILib
 		RustBuffer @progress,
 		sbyte @continually,
 		RustBuffer @cancellation,
+		RustBuffer @options,
+		ref RustCallStatus _uniffi_out_err
+	);
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern sbyte uniffi_nerdbank_zcash_rust_fn_func_verify_spending_key(
+		RustBuffer @config,
+		uint @accountId,
+		RustBuffer @usk,
 		ref RustCallStatus _uniffi_out_err
 	);
 
@@ -858,12 +1091,30 @@ static class _UniFFILib
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_cancel();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_check_wallet();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_disconnect_server();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_discover_accounts();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_export_backup();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_find_account_by_seed();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_get_accounts();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_get_address_book();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_get_batch();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_height();
 
@@ -891,9 +1142,81 @@ static class _UniFFILib
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_init();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_list_addresses();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_list_notes();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_maintain_database();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_next_unused_address();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_prefetch_parameters();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_rank_servers();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_rekey_database();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_release_prover();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_remove_account();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_remove_address_book_entry();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_repair_wallet();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_rescan_account();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_rescan_from();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_restore_backup();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_send();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_send_batch();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_account_metadata();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_account_name();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_address_book_entry();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_address_label();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_connection_options();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_log_sink();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_parameter_paths();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_tor();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_set_transaction_label();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_shield();
 
@@ -903,15 +1226,30 @@ static class _UniFFILib
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_sync();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_func_verify_spending_key();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_cancellationsource_set_cancellation_id();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_logsink_log();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_parameterdownloadprogress_report();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_update_status();
 
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_report_transactions();
 
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_reorg_detected();
+
+	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
+	public static extern ushort uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_on_retry();
+
 	[DllImport("@rpath/nerdbank_zcash_rust.framework/nerdbank_zcash_rust")]
 	public static extern uint ffi_nerdbank_zcash_rust_uniffi_contract_version();
 
@@ -939,10 +1277,10 @@ static class _UniFFILib
 		}
 		{
 			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_add_diversifier();
-			if (checksum != 60533)
+			if (checksum != 9480)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_add_diversifier` checksum `60533`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_add_diversifier` checksum `9480`, library returned `{checksum}`"
 				);
 			}
 		}
@@ -956,47 +1294,101 @@ static class _UniFFILib
 			}
 		}
 		{
-			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_disconnect_server();
-			if (checksum != 21432)
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_check_wallet();
+			if (checksum != 8793)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_disconnect_server` checksum `21432`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_check_wallet` checksum `8793`, library returned `{checksum}`"
 				);
 			}
 		}
 		{
-			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_accounts();
-			if (checksum != 25864)
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_disconnect_server();
+			if (checksum != 21432)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_accounts` checksum `25864`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_disconnect_server` checksum `21432`, library returned `{checksum}`"
 				);
 			}
 		}
 		{
-			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_height();
-			if (checksum != 24763)
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_discover_accounts();
+			if (checksum != 41553)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_height` checksum `24763`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_discover_accounts` checksum `41553`, library returned `{checksum}`"
 				);
 			}
 		}
 		{
-			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_heights();
-			if (checksum != 22457)
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_export_backup();
+			if (checksum != 50293)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_heights` checksum `22457`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_export_backup` checksum `50293`, library returned `{checksum}`"
 				);
 			}
 		}
 		{
-			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_block_height();
-			if (checksum != 44608)
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_find_account_by_seed();
+			if (checksum != 64088)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_block_height` checksum `44608`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_find_account_by_seed` checksum `64088`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_accounts();
+			if (checksum != 25864)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_accounts` checksum `25864`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_address_book();
+			if (checksum != 64120)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_address_book` checksum `64120`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_batch();
+			if (checksum != 8415)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_batch` checksum `8415`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_height();
+			if (checksum != 24763)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_height` checksum `24763`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_heights();
+			if (checksum != 22457)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_birthday_heights` checksum `22457`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_get_block_height();
+			if (checksum != 44608)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_get_block_height` checksum `44608`, library returned `{checksum}`"
 				);
 			}
 		}
@@ -1054,12 +1446,228 @@ static class _UniFFILib
 				);
 			}
 		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_list_addresses();
+			if (checksum != 7729)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_list_addresses` checksum `7729`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_list_notes();
+			if (checksum != 10606)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_list_notes` checksum `10606`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_maintain_database();
+			if (checksum != 62332)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_maintain_database` checksum `62332`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_next_unused_address();
+			if (checksum != 36330)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_next_unused_address` checksum `36330`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_prefetch_parameters();
+			if (checksum != 56690)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_prefetch_parameters` checksum `56690`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_rank_servers();
+			if (checksum != 53688)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_rank_servers` checksum `53688`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_rekey_database();
+			if (checksum != 59995)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_rekey_database` checksum `59995`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_release_prover();
+			if (checksum != 7997)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_release_prover` checksum `7997`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_remove_account();
+			if (checksum != 60897)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_remove_account` checksum `60897`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_remove_address_book_entry();
+			if (checksum != 55525)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_remove_address_book_entry` checksum `55525`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_repair_wallet();
+			if (checksum != 58585)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_repair_wallet` checksum `58585`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_rescan_account();
+			if (checksum != 43037)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_rescan_account` checksum `43037`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_rescan_from();
+			if (checksum != 7592)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_rescan_from` checksum `7592`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_restore_backup();
+			if (checksum != 53234)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_restore_backup` checksum `53234`, library returned `{checksum}`"
+				);
+			}
+		}
 		{
 			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_send();
-			if (checksum != 5940)
+			if (checksum != 47662)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_send` checksum `47662`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_send_batch();
+			if (checksum != 57172)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_send_batch` checksum `57172`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_account_metadata();
+			if (checksum != 12104)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_account_metadata` checksum `12104`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_account_name();
+			if (checksum != 45215)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_account_name` checksum `45215`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_address_book_entry();
+			if (checksum != 31281)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_address_book_entry` checksum `31281`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_address_label();
+			if (checksum != 50911)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_address_label` checksum `50911`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_connection_options();
+			if (checksum != 16541)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_connection_options` checksum `16541`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_log_sink();
+			if (checksum != 5303)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_log_sink` checksum `5303`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_parameter_paths();
+			if (checksum != 51236)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_parameter_paths` checksum `51236`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_tor();
+			if (checksum != 41407)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_tor` checksum `41407`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_set_transaction_label();
+			if (checksum != 15061)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_send` checksum `5940`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_set_transaction_label` checksum `15061`, library returned `{checksum}`"
 				);
 			}
 		}
@@ -1074,19 +1682,28 @@ static class _UniFFILib
 		}
 		{
 			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_simulate_send();
-			if (checksum != 63526)
+			if (checksum != 29341)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_simulate_send` checksum `63526`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_simulate_send` checksum `29341`, library returned `{checksum}`"
 				);
 			}
 		}
 		{
 			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_sync();
-			if (checksum != 57553)
+			if (checksum != 55200)
 			{
 				throw new UniffiContractChecksumException(
-					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_sync` checksum `57553`, library returned `{checksum}`"
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_sync` checksum `55200`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_func_verify_spending_key();
+			if (checksum != 17187)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_func_verify_spending_key` checksum `17187`, library returned `{checksum}`"
 				);
 			}
 		}
@@ -1099,6 +1716,24 @@ static class _UniFFILib
 				);
 			}
 		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_method_logsink_log();
+			if (checksum != 24816)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_method_logsink_log` checksum `24816`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_method_parameterdownloadprogress_report();
+			if (checksum != 9546)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_method_parameterdownloadprogress_report` checksum `9546`, library returned `{checksum}`"
+				);
+			}
+		}
 		{
 			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_update_status();
 			if (checksum != 36073)
@@ -1117,6 +1752,24 @@ static class _UniFFILib
 				);
 			}
 		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_reorg_detected();
+			if (checksum != 4870)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_reorg_detected` checksum `4870`, library returned `{checksum}`"
+				);
+			}
+		}
+		{
+			var checksum = _UniFFILib.uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_on_retry();
+			if (checksum != 2587)
+			{
+				throw new UniffiContractChecksumException(
+					$"uniffi.LightWallet: uniffi bindings expected function `uniffi_nerdbank_zcash_rust_checksum_method_syncupdate_on_retry` checksum `2587`, library returned `{checksum}`"
+				);
+			}
+		}
 	}
 }
 
@@ -1217,6 +1870,36 @@ class FfiConverterInt64 : FfiConverter<long, long>
 	}
 }
 
+class FfiConverterDouble : FfiConverter<double, double>
+{
+	public static FfiConverterDouble INSTANCE = new FfiConverterDouble();
+
+	public override double Lift(double value)
+	{
+		return value;
+	}
+
+	public override double Read(BigEndianStream stream)
+	{
+		return stream.ReadDouble();
+	}
+
+	public override double Lower(double value)
+	{
+		return value;
+	}
+
+	public override int AllocationSize(double value)
+	{
+		return 8;
+	}
+
+	public override void Write(double value, BigEndianStream stream)
+	{
+		stream.WriteDouble(value);
+	}
+}
+
 class FfiConverterBoolean : FfiConverter<bool, sbyte>
 {
 	public static FfiConverterBoolean INSTANCE = new FfiConverterBoolean();
@@ -1369,33 +2052,241 @@ class FfiConverterTimestamp : FfiConverterRustBuffer<DateTime>
 	}
 }
 
-internal record AccountInfo(uint @id, String? @uvk, BirthdayHeights @birthdayHeights) { }
+internal record AccountAddress(
+	byte[] @diversifierIndex,
+	String @address,
+	String? @orchardReceiver,
+	String? @saplingReceiver,
+	String? @transparentReceiver,
+	DateTime? @created,
+	String? @label,
+	ulong @totalReceived
+) { }
 
-class FfiConverterTypeAccountInfo : FfiConverterRustBuffer<AccountInfo>
+class FfiConverterTypeAccountAddress : FfiConverterRustBuffer<AccountAddress>
 {
-	public static FfiConverterTypeAccountInfo INSTANCE = new FfiConverterTypeAccountInfo();
-
-	public override AccountInfo Read(BigEndianStream stream)
-	{
-		return new AccountInfo(
-			@id: FfiConverterUInt32.INSTANCE.Read(stream),
-			@uvk: FfiConverterOptionalString.INSTANCE.Read(stream),
-			@birthdayHeights: FfiConverterTypeBirthdayHeights.INSTANCE.Read(stream)
+	public static FfiConverterTypeAccountAddress INSTANCE = new FfiConverterTypeAccountAddress();
+
+	public override AccountAddress Read(BigEndianStream stream)
+	{
+		return new AccountAddress(
+			@diversifierIndex: FfiConverterByteArray.INSTANCE.Read(stream),
+			@address: FfiConverterString.INSTANCE.Read(stream),
+			@orchardReceiver: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@saplingReceiver: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@transparentReceiver: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@created: FfiConverterOptionalTimestamp.INSTANCE.Read(stream),
+			@label: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@totalReceived: FfiConverterUInt64.INSTANCE.Read(stream)
 		);
 	}
 
-	public override int AllocationSize(AccountInfo value)
+	public override int AllocationSize(AccountAddress value)
 	{
-		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@id)
-			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@uvk)
-			+ FfiConverterTypeBirthdayHeights.INSTANCE.AllocationSize(value.@birthdayHeights);
+		return FfiConverterByteArray.INSTANCE.AllocationSize(value.@diversifierIndex)
+			+ FfiConverterString.INSTANCE.AllocationSize(value.@address)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@orchardReceiver)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@saplingReceiver)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@transparentReceiver)
+			+ FfiConverterOptionalTimestamp.INSTANCE.AllocationSize(value.@created)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@label)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@totalReceived);
 	}
 
-	public override void Write(AccountInfo value, BigEndianStream stream)
+	public override void Write(AccountAddress value, BigEndianStream stream)
+	{
+		FfiConverterByteArray.INSTANCE.Write(value.@diversifierIndex, stream);
+		FfiConverterString.INSTANCE.Write(value.@address, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@orchardReceiver, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@saplingReceiver, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@transparentReceiver, stream);
+		FfiConverterOptionalTimestamp.INSTANCE.Write(value.@created, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@label, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@totalReceived, stream);
+	}
+}
+
+internal record AccountInfo(
+	uint @id,
+	String? @uvk,
+	BirthdayHeights @birthdayHeights,
+	uint? @zip32AccountIndex,
+	byte[]? @seedFingerprint,
+	bool @spendingKeyAvailable,
+	List<Pool> @pools,
+	String? @name,
+	Dictionary<String, String> @metadata
+) { }
+
+class FfiConverterTypeAccountInfo : FfiConverterRustBuffer<AccountInfo>
+{
+	public static FfiConverterTypeAccountInfo INSTANCE = new FfiConverterTypeAccountInfo();
+
+	public override AccountInfo Read(BigEndianStream stream)
+	{
+		return new AccountInfo(
+			@id: FfiConverterUInt32.INSTANCE.Read(stream),
+			@uvk: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@birthdayHeights: FfiConverterTypeBirthdayHeights.INSTANCE.Read(stream),
+			@zip32AccountIndex: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@seedFingerprint: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
+			@spendingKeyAvailable: FfiConverterBoolean.INSTANCE.Read(stream),
+			@pools: FfiConverterSequenceTypePool.INSTANCE.Read(stream),
+			@name: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@metadata: FfiConverterDictionaryStringString.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(AccountInfo value)
+	{
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@id)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@uvk)
+			+ FfiConverterTypeBirthdayHeights.INSTANCE.AllocationSize(value.@birthdayHeights)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@zip32AccountIndex)
+			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@seedFingerprint)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@spendingKeyAvailable)
+			+ FfiConverterSequenceTypePool.INSTANCE.AllocationSize(value.@pools)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@name)
+			+ FfiConverterDictionaryStringString.INSTANCE.AllocationSize(value.@metadata);
+	}
+
+	public override void Write(AccountInfo value, BigEndianStream stream)
 	{
 		FfiConverterUInt32.INSTANCE.Write(value.@id, stream);
 		FfiConverterOptionalString.INSTANCE.Write(value.@uvk, stream);
 		FfiConverterTypeBirthdayHeights.INSTANCE.Write(value.@birthdayHeights, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@zip32AccountIndex, stream);
+		FfiConverterOptionalByteArray.INSTANCE.Write(value.@seedFingerprint, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@spendingKeyAvailable, stream);
+		FfiConverterSequenceTypePool.INSTANCE.Write(value.@pools, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@name, stream);
+		FfiConverterDictionaryStringString.INSTANCE.Write(value.@metadata, stream);
+	}
+}
+
+internal record AddressBookEntry(uint? @id, String @address, String @label) { }
+
+class FfiConverterTypeAddressBookEntry : FfiConverterRustBuffer<AddressBookEntry>
+{
+	public static FfiConverterTypeAddressBookEntry INSTANCE = new FfiConverterTypeAddressBookEntry();
+
+	public override AddressBookEntry Read(BigEndianStream stream)
+	{
+		return new AddressBookEntry(
+			@id: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@address: FfiConverterString.INSTANCE.Read(stream),
+			@label: FfiConverterString.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(AddressBookEntry value)
+	{
+		return FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@id)
+			+ FfiConverterString.INSTANCE.AllocationSize(value.@address)
+			+ FfiConverterString.INSTANCE.AllocationSize(value.@label);
+	}
+
+	public override void Write(AddressBookEntry value, BigEndianStream stream)
+	{
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@id, stream);
+		FfiConverterString.INSTANCE.Write(value.@address, stream);
+		FfiConverterString.INSTANCE.Write(value.@label, stream);
+	}
+}
+
+/// <summary>
+/// Controls how `send_batch` splits payments into transactions.
+/// </summary>
+internal record BatchOptions(uint @maxPaymentsPerTransaction = 50u, ulong? @maxFeePerTransaction = null) { }
+
+class FfiConverterTypeBatchOptions : FfiConverterRustBuffer<BatchOptions>
+{
+	public static FfiConverterTypeBatchOptions INSTANCE = new FfiConverterTypeBatchOptions();
+
+	public override BatchOptions Read(BigEndianStream stream)
+	{
+		return new BatchOptions(
+			@maxPaymentsPerTransaction: FfiConverterUInt32.INSTANCE.Read(stream),
+			@maxFeePerTransaction: FfiConverterOptionalUInt64.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(BatchOptions value)
+	{
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@maxPaymentsPerTransaction)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@maxFeePerTransaction);
+	}
+
+	public override void Write(BatchOptions value, BigEndianStream stream)
+	{
+		FfiConverterUInt32.INSTANCE.Write(value.@maxPaymentsPerTransaction, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@maxFeePerTransaction, stream);
+	}
+}
+
+internal record BatchPayment(uint @index, String @recipient, ulong @value, BatchPaymentStatus @status, byte[]? @txid, String? @error) { }
+
+class FfiConverterTypeBatchPayment : FfiConverterRustBuffer<BatchPayment>
+{
+	public static FfiConverterTypeBatchPayment INSTANCE = new FfiConverterTypeBatchPayment();
+
+	public override BatchPayment Read(BigEndianStream stream)
+	{
+		return new BatchPayment(
+			@index: FfiConverterUInt32.INSTANCE.Read(stream),
+			@recipient: FfiConverterString.INSTANCE.Read(stream),
+			@value: FfiConverterUInt64.INSTANCE.Read(stream),
+			@status: FfiConverterTypeBatchPaymentStatus.INSTANCE.Read(stream),
+			@txid: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
+			@error: FfiConverterOptionalString.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(BatchPayment value)
+	{
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@index)
+			+ FfiConverterString.INSTANCE.AllocationSize(value.@recipient)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@value)
+			+ FfiConverterTypeBatchPaymentStatus.INSTANCE.AllocationSize(value.@status)
+			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@txid)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@error);
+	}
+
+	public override void Write(BatchPayment value, BigEndianStream stream)
+	{
+		FfiConverterUInt32.INSTANCE.Write(value.@index, stream);
+		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
+		FfiConverterTypeBatchPaymentStatus.INSTANCE.Write(value.@status, stream);
+		FfiConverterOptionalByteArray.INSTANCE.Write(value.@txid, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@error, stream);
+	}
+}
+
+internal record BatchResult(String @batchId, List<BatchPayment> @payments) { }
+
+class FfiConverterTypeBatchResult : FfiConverterRustBuffer<BatchResult>
+{
+	public static FfiConverterTypeBatchResult INSTANCE = new FfiConverterTypeBatchResult();
+
+	public override BatchResult Read(BigEndianStream stream)
+	{
+		return new BatchResult(
+			@batchId: FfiConverterString.INSTANCE.Read(stream),
+			@payments: FfiConverterSequenceTypeBatchPayment.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(BatchResult value)
+	{
+		return FfiConverterString.INSTANCE.AllocationSize(value.@batchId)
+			+ FfiConverterSequenceTypeBatchPayment.INSTANCE.AllocationSize(value.@payments);
+	}
+
+	public override void Write(BatchResult value, BigEndianStream stream)
+	{
+		FfiConverterString.INSTANCE.Write(value.@batchId, stream);
+		FfiConverterSequenceTypeBatchPayment.INSTANCE.Write(value.@payments, stream);
 	}
 }
 
@@ -1429,7 +2320,92 @@ class FfiConverterTypeBirthdayHeights : FfiConverterRustBuffer<BirthdayHeights>
 	}
 }
 
-internal record DbInit(String @dataFile, ChainType @network, uint @minConfirmations) { }
+internal record CoinSelection(List<NoteId>? @spendOnly, List<NoteId> @exclude) { }
+
+class FfiConverterTypeCoinSelection : FfiConverterRustBuffer<CoinSelection>
+{
+	public static FfiConverterTypeCoinSelection INSTANCE = new FfiConverterTypeCoinSelection();
+
+	public override CoinSelection Read(BigEndianStream stream)
+	{
+		return new CoinSelection(
+			@spendOnly: FfiConverterOptionalSequenceTypeNoteId.INSTANCE.Read(stream),
+			@exclude: FfiConverterSequenceTypeNoteId.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(CoinSelection value)
+	{
+		return FfiConverterOptionalSequenceTypeNoteId.INSTANCE.AllocationSize(value.@spendOnly)
+			+ FfiConverterSequenceTypeNoteId.INSTANCE.AllocationSize(value.@exclude);
+	}
+
+	public override void Write(CoinSelection value, BigEndianStream stream)
+	{
+		FfiConverterOptionalSequenceTypeNoteId.INSTANCE.Write(value.@spendOnly, stream);
+		FfiConverterSequenceTypeNoteId.INSTANCE.Write(value.@exclude, stream);
+	}
+}
+
+/// <summary>
+/// Options for how to connect to a light wallet server.
+/// Whether TLS is used follows from the server URI's scheme (http or https).
+/// </summary>
+internal record ConnectionOptions(
+	List<String> @caCertificates = null,
+	bool @trustOnlyCustomCertificates = false,
+	String? @tlsDomainName = null,
+	ulong? @connectTimeoutMs = null,
+	ulong? @requestTimeoutMs = null,
+	ulong? @keepaliveIntervalMs = null,
+	ulong? @keepaliveTimeoutMs = null,
+	String? @socks5Proxy = null
+) { }
+
+class FfiConverterTypeConnectionOptions : FfiConverterRustBuffer<ConnectionOptions>
+{
+	public static FfiConverterTypeConnectionOptions INSTANCE = new FfiConverterTypeConnectionOptions();
+
+	public override ConnectionOptions Read(BigEndianStream stream)
+	{
+		return new ConnectionOptions(
+			@caCertificates: FfiConverterSequenceString.INSTANCE.Read(stream),
+			@trustOnlyCustomCertificates: FfiConverterBoolean.INSTANCE.Read(stream),
+			@tlsDomainName: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@connectTimeoutMs: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@requestTimeoutMs: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@keepaliveIntervalMs: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@keepaliveTimeoutMs: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@socks5Proxy: FfiConverterOptionalString.INSTANCE.Read(stream)
+		);
+	}
+
+	public override int AllocationSize(ConnectionOptions value)
+	{
+		return FfiConverterSequenceString.INSTANCE.AllocationSize(value.@caCertificates)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@trustOnlyCustomCertificates)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@tlsDomainName)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@connectTimeoutMs)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@requestTimeoutMs)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@keepaliveIntervalMs)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@keepaliveTimeoutMs)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@socks5Proxy);
+	}
+
+	public override void Write(ConnectionOptions value, BigEndianStream stream)
+	{
+		FfiConverterSequenceString.INSTANCE.Write(value.@caCertificates, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@trustOnlyCustomCertificates, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@tlsDomainName, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@connectTimeoutMs, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@requestTimeoutMs, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@keepaliveIntervalMs, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@keepaliveTimeoutMs, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@socks5Proxy, stream);
+	}
+}
+
+internal record DbInit(String @dataFile, ChainType @network, uint @minConfirmations, String? @passphrase) { }
 
 class FfiConverterTypeDbInit : FfiConverterRustBuffer<DbInit>
 {
@@ -1440,7 +2416,8 @@ class FfiConverterTypeDbInit : FfiConverterRustBuffer<DbInit>
 		return new DbInit(
 			@dataFile: FfiConverterString.INSTANCE.Read(stream),
 			@network: FfiConverterTypeChainType.INSTANCE.Read(stream),
-			@minConfirmations: FfiConverterUInt32.INSTANCE.Read(stream)
+			@minConfirmations: FfiConverterUInt32.INSTANCE.Read(stream),
+			@passphrase: FfiConverterOptionalString.INSTANCE.Read(stream)
 		);
 	}
 
@@ -1448,7 +2425,8 @@ class FfiConverterTypeDbInit : FfiConverterRustBuffer<DbInit>
 	{
 		return FfiConverterString.INSTANCE.AllocationSize(value.@dataFile)
 			+ FfiConverterTypeChainType.INSTANCE.AllocationSize(value.@network)
-			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@minConfirmations);
+			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@minConfirmations)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@passphrase);
 	}
 
 	public override void Write(DbInit value, BigEndianStream stream)
@@ -1456,1333 +2434,3357 @@ class FfiConverterTypeDbInit : FfiConverterRustBuffer<DbInit>
 		FfiConverterString.INSTANCE.Write(value.@dataFile, stream);
 		FfiConverterTypeChainType.INSTANCE.Write(value.@network, stream);
 		FfiConverterUInt32.INSTANCE.Write(value.@minConfirmations, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@passphrase, stream);
 	}
 }
 
-internal record SendDetails(ulong @fee) { }
+internal record DiscoveredAccount(uint @accountId, uint @accountIndex, uint @birthdayHeight) { }
 
-class FfiConverterTypeSendDetails : FfiConverterRustBuffer<SendDetails>
+class FfiConverterTypeDiscoveredAccount : FfiConverterRustBuffer<DiscoveredAccount>
 {
-	public static FfiConverterTypeSendDetails INSTANCE = new FfiConverterTypeSendDetails();
+	public static FfiConverterTypeDiscoveredAccount INSTANCE = new FfiConverterTypeDiscoveredAccount();
 
-	public override SendDetails Read(BigEndianStream stream)
+	public override DiscoveredAccount Read(BigEndianStream stream)
 	{
-		return new SendDetails(@fee: FfiConverterUInt64.INSTANCE.Read(stream));
+		return new DiscoveredAccount(
+			@accountId: FfiConverterUInt32.INSTANCE.Read(stream),
+			@accountIndex: FfiConverterUInt32.INSTANCE.Read(stream),
+			@birthdayHeight: FfiConverterUInt32.INSTANCE.Read(stream)
+		);
 	}
 
-	public override int AllocationSize(SendDetails value)
+	public override int AllocationSize(DiscoveredAccount value)
 	{
-		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@fee);
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@accountId)
+			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@accountIndex)
+			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@birthdayHeight);
 	}
 
-	public override void Write(SendDetails value, BigEndianStream stream)
+	public override void Write(DiscoveredAccount value, BigEndianStream stream)
 	{
-		FfiConverterUInt64.INSTANCE.Write(value.@fee, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@accountId, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@accountIndex, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@birthdayHeight, stream);
 	}
 }
 
-internal record SendTransactionResult(byte[] @txid) { }
+internal record HeightRange(uint @start, uint @end) { }
 
-class FfiConverterTypeSendTransactionResult : FfiConverterRustBuffer<SendTransactionResult>
+class FfiConverterTypeHeightRange : FfiConverterRustBuffer<HeightRange>
 {
-	public static FfiConverterTypeSendTransactionResult INSTANCE = new FfiConverterTypeSendTransactionResult();
+	public static FfiConverterTypeHeightRange INSTANCE = new FfiConverterTypeHeightRange();
 
-	public override SendTransactionResult Read(BigEndianStream stream)
+	public override HeightRange Read(BigEndianStream stream)
 	{
-		return new SendTransactionResult(@txid: FfiConverterByteArray.INSTANCE.Read(stream));
+		return new HeightRange(@start: FfiConverterUInt32.INSTANCE.Read(stream), @end: FfiConverterUInt32.INSTANCE.Read(stream));
 	}
 
-	public override int AllocationSize(SendTransactionResult value)
+	public override int AllocationSize(HeightRange value)
 	{
-		return FfiConverterByteArray.INSTANCE.AllocationSize(value.@txid);
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@start) + FfiConverterUInt32.INSTANCE.AllocationSize(value.@end);
 	}
 
-	public override void Write(SendTransactionResult value, BigEndianStream stream)
+	public override void Write(HeightRange value, BigEndianStream stream)
 	{
-		FfiConverterByteArray.INSTANCE.Write(value.@txid, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@start, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@end, stream);
 	}
 }
 
-internal record SyncUpdateData(uint? @lastFullyScannedBlock, uint @tipHeight, ulong @currentStep, ulong @totalSteps, String? @lastError) { }
+internal record MaintenanceOptions(bool @pruneNullifierMap, bool @compactScanQueue, bool @reindex, bool @vacuum) { }
 
-class FfiConverterTypeSyncUpdateData : FfiConverterRustBuffer<SyncUpdateData>
+class FfiConverterTypeMaintenanceOptions : FfiConverterRustBuffer<MaintenanceOptions>
 {
-	public static FfiConverterTypeSyncUpdateData INSTANCE = new FfiConverterTypeSyncUpdateData();
+	public static FfiConverterTypeMaintenanceOptions INSTANCE = new FfiConverterTypeMaintenanceOptions();
 
-	public override SyncUpdateData Read(BigEndianStream stream)
+	public override MaintenanceOptions Read(BigEndianStream stream)
 	{
-		return new SyncUpdateData(
-			@lastFullyScannedBlock: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
-			@tipHeight: FfiConverterUInt32.INSTANCE.Read(stream),
-			@currentStep: FfiConverterUInt64.INSTANCE.Read(stream),
-			@totalSteps: FfiConverterUInt64.INSTANCE.Read(stream),
-			@lastError: FfiConverterOptionalString.INSTANCE.Read(stream)
+		return new MaintenanceOptions(
+			@pruneNullifierMap: FfiConverterBoolean.INSTANCE.Read(stream),
+			@compactScanQueue: FfiConverterBoolean.INSTANCE.Read(stream),
+			@reindex: FfiConverterBoolean.INSTANCE.Read(stream),
+			@vacuum: FfiConverterBoolean.INSTANCE.Read(stream)
 		);
 	}
 
-	public override int AllocationSize(SyncUpdateData value)
+	public override int AllocationSize(MaintenanceOptions value)
 	{
-		return FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@lastFullyScannedBlock)
-			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@tipHeight)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@currentStep)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@totalSteps)
-			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@lastError);
+		return FfiConverterBoolean.INSTANCE.AllocationSize(value.@pruneNullifierMap)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@compactScanQueue)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@reindex)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@vacuum);
 	}
 
-	public override void Write(SyncUpdateData value, BigEndianStream stream)
+	public override void Write(MaintenanceOptions value, BigEndianStream stream)
 	{
-		FfiConverterOptionalUInt32.INSTANCE.Write(value.@lastFullyScannedBlock, stream);
-		FfiConverterUInt32.INSTANCE.Write(value.@tipHeight, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@currentStep, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@totalSteps, stream);
-		FfiConverterOptionalString.INSTANCE.Write(value.@lastError, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@pruneNullifierMap, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@compactScanQueue, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@reindex, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@vacuum, stream);
 	}
 }
 
-internal record Transaction(
-	uint @accountId,
-	byte[] @txid,
-	DateTime? @blockTime,
-	uint? @minedHeight,
-	bool @expiredUnmined,
-	long @accountBalanceDelta,
-	ulong? @fee,
-	List<TransactionNote> @outgoing,
-	List<TransactionNote> @incoming,
-	List<TransactionNote> @change
+internal record MaintenanceReport(
+	ulong @sizeBefore,
+	ulong @sizeAfter,
+	ulong @bytesReclaimed,
+	ulong @nullifiersPruned,
+	ulong @scanRangesMerged
 ) { }
 
-class FfiConverterTypeTransaction : FfiConverterRustBuffer<Transaction>
+class FfiConverterTypeMaintenanceReport : FfiConverterRustBuffer<MaintenanceReport>
 {
-	public static FfiConverterTypeTransaction INSTANCE = new FfiConverterTypeTransaction();
+	public static FfiConverterTypeMaintenanceReport INSTANCE = new FfiConverterTypeMaintenanceReport();
 
-	public override Transaction Read(BigEndianStream stream)
+	public override MaintenanceReport Read(BigEndianStream stream)
 	{
-		return new Transaction(
-			@accountId: FfiConverterUInt32.INSTANCE.Read(stream),
-			@txid: FfiConverterByteArray.INSTANCE.Read(stream),
-			@blockTime: FfiConverterOptionalTimestamp.INSTANCE.Read(stream),
-			@minedHeight: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
-			@expiredUnmined: FfiConverterBoolean.INSTANCE.Read(stream),
-			@accountBalanceDelta: FfiConverterInt64.INSTANCE.Read(stream),
-			@fee: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
-			@outgoing: FfiConverterSequenceTypeTransactionNote.INSTANCE.Read(stream),
-			@incoming: FfiConverterSequenceTypeTransactionNote.INSTANCE.Read(stream),
-			@change: FfiConverterSequenceTypeTransactionNote.INSTANCE.Read(stream)
+		return new MaintenanceReport(
+			@sizeBefore: FfiConverterUInt64.INSTANCE.Read(stream),
+			@sizeAfter: FfiConverterUInt64.INSTANCE.Read(stream),
+			@bytesReclaimed: FfiConverterUInt64.INSTANCE.Read(stream),
+			@nullifiersPruned: FfiConverterUInt64.INSTANCE.Read(stream),
+			@scanRangesMerged: FfiConverterUInt64.INSTANCE.Read(stream)
 		);
 	}
 
-	public override int AllocationSize(Transaction value)
+	public override int AllocationSize(MaintenanceReport value)
 	{
-		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@accountId)
-			+ FfiConverterByteArray.INSTANCE.AllocationSize(value.@txid)
-			+ FfiConverterOptionalTimestamp.INSTANCE.AllocationSize(value.@blockTime)
-			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@minedHeight)
-			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@expiredUnmined)
-			+ FfiConverterInt64.INSTANCE.AllocationSize(value.@accountBalanceDelta)
-			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@fee)
-			+ FfiConverterSequenceTypeTransactionNote.INSTANCE.AllocationSize(value.@outgoing)
-			+ FfiConverterSequenceTypeTransactionNote.INSTANCE.AllocationSize(value.@incoming)
-			+ FfiConverterSequenceTypeTransactionNote.INSTANCE.AllocationSize(value.@change);
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@sizeBefore)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@sizeAfter)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@bytesReclaimed)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@nullifiersPruned)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@scanRangesMerged);
 	}
 
-	public override void Write(Transaction value, BigEndianStream stream)
+	public override void Write(MaintenanceReport value, BigEndianStream stream)
 	{
-		FfiConverterUInt32.INSTANCE.Write(value.@accountId, stream);
-		FfiConverterByteArray.INSTANCE.Write(value.@txid, stream);
-		FfiConverterOptionalTimestamp.INSTANCE.Write(value.@blockTime, stream);
-		FfiConverterOptionalUInt32.INSTANCE.Write(value.@minedHeight, stream);
-		FfiConverterBoolean.INSTANCE.Write(value.@expiredUnmined, stream);
-		FfiConverterInt64.INSTANCE.Write(value.@accountBalanceDelta, stream);
-		FfiConverterOptionalUInt64.INSTANCE.Write(value.@fee, stream);
-		FfiConverterSequenceTypeTransactionNote.INSTANCE.Write(value.@outgoing, stream);
-		FfiConverterSequenceTypeTransactionNote.INSTANCE.Write(value.@incoming, stream);
-		FfiConverterSequenceTypeTransactionNote.INSTANCE.Write(value.@change, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@sizeBefore, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@sizeAfter, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@bytesReclaimed, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@nullifiersPruned, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@scanRangesMerged, stream);
 	}
 }
 
-internal record TransactionNote(ulong @value, byte[]? @memo, String @recipient, Pool @pool) { }
+internal record NoteId(byte[] @txid, Pool @pool, uint @outputIndex) { }
 
-class FfiConverterTypeTransactionNote : FfiConverterRustBuffer<TransactionNote>
+class FfiConverterTypeNoteId : FfiConverterRustBuffer<NoteId>
 {
-	public static FfiConverterTypeTransactionNote INSTANCE = new FfiConverterTypeTransactionNote();
+	public static FfiConverterTypeNoteId INSTANCE = new FfiConverterTypeNoteId();
 
-	public override TransactionNote Read(BigEndianStream stream)
+	public override NoteId Read(BigEndianStream stream)
 	{
-		return new TransactionNote(
-			@value: FfiConverterUInt64.INSTANCE.Read(stream),
-			@memo: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
-			@recipient: FfiConverterString.INSTANCE.Read(stream),
-			@pool: FfiConverterTypePool.INSTANCE.Read(stream)
+		return new NoteId(
+			@txid: FfiConverterByteArray.INSTANCE.Read(stream),
+			@pool: FfiConverterTypePool.INSTANCE.Read(stream),
+			@outputIndex: FfiConverterUInt32.INSTANCE.Read(stream)
 		);
 	}
 
-	public override int AllocationSize(TransactionNote value)
+	public override int AllocationSize(NoteId value)
 	{
-		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@value)
-			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@memo)
-			+ FfiConverterString.INSTANCE.AllocationSize(value.@recipient)
-			+ FfiConverterTypePool.INSTANCE.AllocationSize(value.@pool);
+		return FfiConverterByteArray.INSTANCE.AllocationSize(value.@txid)
+			+ FfiConverterTypePool.INSTANCE.AllocationSize(value.@pool)
+			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@outputIndex);
 	}
 
-	public override void Write(TransactionNote value, BigEndianStream stream)
+	public override void Write(NoteId value, BigEndianStream stream)
 	{
-		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
-		FfiConverterOptionalByteArray.INSTANCE.Write(value.@memo, stream);
-		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
+		FfiConverterByteArray.INSTANCE.Write(value.@txid, stream);
 		FfiConverterTypePool.INSTANCE.Write(value.@pool, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@outputIndex, stream);
 	}
 }
 
-internal record TransactionSendDetail(ulong @value, byte[]? @memo, String @recipient) { }
+/// <summary>
+/// Explicit paths to the Sapling parameter files.
+/// </summary>
+internal record ParameterPaths(String @spend, String @output) { }
 
-class FfiConverterTypeTransactionSendDetail : FfiConverterRustBuffer<TransactionSendDetail>
+class FfiConverterTypeParameterPaths : FfiConverterRustBuffer<ParameterPaths>
 {
-	public static FfiConverterTypeTransactionSendDetail INSTANCE = new FfiConverterTypeTransactionSendDetail();
+	public static FfiConverterTypeParameterPaths INSTANCE = new FfiConverterTypeParameterPaths();
 
-	public override TransactionSendDetail Read(BigEndianStream stream)
+	public override ParameterPaths Read(BigEndianStream stream)
 	{
-		return new TransactionSendDetail(
-			@value: FfiConverterUInt64.INSTANCE.Read(stream),
-			@memo: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
-			@recipient: FfiConverterString.INSTANCE.Read(stream)
-		);
+		return new ParameterPaths(@spend: FfiConverterString.INSTANCE.Read(stream), @output: FfiConverterString.INSTANCE.Read(stream));
 	}
 
-	public override int AllocationSize(TransactionSendDetail value)
+	public override int AllocationSize(ParameterPaths value)
 	{
-		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@value)
-			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@memo)
-			+ FfiConverterString.INSTANCE.AllocationSize(value.@recipient);
+		return FfiConverterString.INSTANCE.AllocationSize(value.@spend) + FfiConverterString.INSTANCE.AllocationSize(value.@output);
 	}
 
-	public override void Write(TransactionSendDetail value, BigEndianStream stream)
+	public override void Write(ParameterPaths value, BigEndianStream stream)
 	{
-		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
-		FfiConverterOptionalByteArray.INSTANCE.Write(value.@memo, stream);
-		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
+		FfiConverterString.INSTANCE.Write(value.@spend, stream);
+		FfiConverterString.INSTANCE.Write(value.@output, stream);
 	}
 }
 
-internal record TransparentNote(ulong @value, String @recipient) { }
+/// <summary>
+/// Controls how failed requests to the server are retried.
+/// Requests that fail because of the request itself (e.g. an invalid argument) are not retried.
+/// </summary>
+internal record RetryPolicy(
+	uint @maxRetries = 3u,
+	ulong @initialDelayMs = 2000uL,
+	ulong @maxDelayMs = 30000uL,
+	double @backoffMultiplier = 2.0,
+	double @jitter = 0.2,
+	ulong @maxElapsedMs = 120000uL
+) { }
 
-class FfiConverterTypeTransparentNote : FfiConverterRustBuffer<TransparentNote>
+class FfiConverterTypeRetryPolicy : FfiConverterRustBuffer<RetryPolicy>
 {
-	public static FfiConverterTypeTransparentNote INSTANCE = new FfiConverterTypeTransparentNote();
+	public static FfiConverterTypeRetryPolicy INSTANCE = new FfiConverterTypeRetryPolicy();
 
-	public override TransparentNote Read(BigEndianStream stream)
+	public override RetryPolicy Read(BigEndianStream stream)
 	{
-		return new TransparentNote(@value: FfiConverterUInt64.INSTANCE.Read(stream), @recipient: FfiConverterString.INSTANCE.Read(stream));
+		return new RetryPolicy(
+			@maxRetries: FfiConverterUInt32.INSTANCE.Read(stream),
+			@initialDelayMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@maxDelayMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@backoffMultiplier: FfiConverterDouble.INSTANCE.Read(stream),
+			@jitter: FfiConverterDouble.INSTANCE.Read(stream),
+			@maxElapsedMs: FfiConverterUInt64.INSTANCE.Read(stream)
+		);
 	}
 
-	public override int AllocationSize(TransparentNote value)
+	public override int AllocationSize(RetryPolicy value)
 	{
-		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@value) + FfiConverterString.INSTANCE.AllocationSize(value.@recipient);
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@maxRetries)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@initialDelayMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@maxDelayMs)
+			+ FfiConverterDouble.INSTANCE.AllocationSize(value.@backoffMultiplier)
+			+ FfiConverterDouble.INSTANCE.AllocationSize(value.@jitter)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@maxElapsedMs);
 	}
 
-	public override void Write(TransparentNote value, BigEndianStream stream)
+	public override void Write(RetryPolicy value, BigEndianStream stream)
 	{
-		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
-		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@maxRetries, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@initialDelayMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@maxDelayMs, stream);
+		FfiConverterDouble.INSTANCE.Write(value.@backoffMultiplier, stream);
+		FfiConverterDouble.INSTANCE.Write(value.@jitter, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@maxElapsedMs, stream);
 	}
 }
 
-internal record UserBalances(
-	ulong @spendable,
-	ulong @immatureChange,
-	ulong @minimumFees,
-	ulong @immatureIncome,
-	ulong @dust,
-	ulong @incoming,
-	ulong @incomingDust
-) { }
+internal record SendDetails(ulong @fee) { }
 
-class FfiConverterTypeUserBalances : FfiConverterRustBuffer<UserBalances>
+class FfiConverterTypeSendDetails : FfiConverterRustBuffer<SendDetails>
 {
-	public static FfiConverterTypeUserBalances INSTANCE = new FfiConverterTypeUserBalances();
+	public static FfiConverterTypeSendDetails INSTANCE = new FfiConverterTypeSendDetails();
 
-	public override UserBalances Read(BigEndianStream stream)
+	public override SendDetails Read(BigEndianStream stream)
 	{
-		return new UserBalances(
-			@spendable: FfiConverterUInt64.INSTANCE.Read(stream),
-			@immatureChange: FfiConverterUInt64.INSTANCE.Read(stream),
-			@minimumFees: FfiConverterUInt64.INSTANCE.Read(stream),
-			@immatureIncome: FfiConverterUInt64.INSTANCE.Read(stream),
-			@dust: FfiConverterUInt64.INSTANCE.Read(stream),
-			@incoming: FfiConverterUInt64.INSTANCE.Read(stream),
-			@incomingDust: FfiConverterUInt64.INSTANCE.Read(stream)
-		);
+		return new SendDetails(@fee: FfiConverterUInt64.INSTANCE.Read(stream));
 	}
 
-	public override int AllocationSize(UserBalances value)
+	public override int AllocationSize(SendDetails value)
 	{
-		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@spendable)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@immatureChange)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@minimumFees)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@immatureIncome)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@dust)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@incoming)
-			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@incomingDust);
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@fee);
 	}
 
-	public override void Write(UserBalances value, BigEndianStream stream)
+	public override void Write(SendDetails value, BigEndianStream stream)
 	{
-		FfiConverterUInt64.INSTANCE.Write(value.@spendable, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@immatureChange, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@minimumFees, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@immatureIncome, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@dust, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@incoming, stream);
-		FfiConverterUInt64.INSTANCE.Write(value.@incomingDust, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@fee, stream);
 	}
 }
 
-internal enum ChainType : int
-{
-	Testnet,
-	Mainnet,
-}
+internal record SendTransactionResult(byte[] @txid) { }
 
-class FfiConverterTypeChainType : FfiConverterRustBuffer<ChainType>
+class FfiConverterTypeSendTransactionResult : FfiConverterRustBuffer<SendTransactionResult>
 {
-	public static FfiConverterTypeChainType INSTANCE = new FfiConverterTypeChainType();
+	public static FfiConverterTypeSendTransactionResult INSTANCE = new FfiConverterTypeSendTransactionResult();
 
-	public override ChainType Read(BigEndianStream stream)
+	public override SendTransactionResult Read(BigEndianStream stream)
 	{
-		var value = stream.ReadInt() - 1;
-		if (Enum.IsDefined(typeof(ChainType), value))
-		{
-			return (ChainType)value;
-		}
-		else
-		{
-			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeChainType.Read()", value));
-		}
+		return new SendTransactionResult(@txid: FfiConverterByteArray.INSTANCE.Read(stream));
 	}
 
-	public override int AllocationSize(ChainType value)
+	public override int AllocationSize(SendTransactionResult value)
 	{
-		return 4;
+		return FfiConverterByteArray.INSTANCE.AllocationSize(value.@txid);
 	}
 
-	public override void Write(ChainType value, BigEndianStream stream)
+	public override void Write(SendTransactionResult value, BigEndianStream stream)
 	{
-		stream.WriteInt((int)value + 1);
+		FfiConverterByteArray.INSTANCE.Write(value.@txid, stream);
 	}
 }
 
-internal class LightWalletException : UniffiException
-{
-	// Each variant is a nested class
+/// <summary>
+/// The health of a light wallet server.
+/// </summary>
+internal record ServerStatus(String @uri, bool @healthy, ulong? @latencyMs, uint? @tipHeight, String? @error) { }
 
+class FfiConverterTypeServerStatus : FfiConverterRustBuffer<ServerStatus>
+{
+	public static FfiConverterTypeServerStatus INSTANCE = new FfiConverterTypeServerStatus();
 
-	public class InvalidArgument : LightWalletException
+	public override ServerStatus Read(BigEndianStream stream)
 	{
-		// Members
-		public String @message;
-
-		// Constructor
-		public InvalidArgument(String @message)
-		{
-			this.@message = @message;
-		}
+		return new ServerStatus(
+			@uri: FfiConverterString.INSTANCE.Read(stream),
+			@healthy: FfiConverterBoolean.INSTANCE.Read(stream),
+			@latencyMs: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@tipHeight: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@error: FfiConverterOptionalString.INSTANCE.Read(stream)
+		);
 	}
 
-	public class InvalidUri : LightWalletException { }
-
-	public class SqliteClientException : LightWalletException
+	public override int AllocationSize(ServerStatus value)
 	{
-		// Members
-		public String @message;
+		return FfiConverterString.INSTANCE.AllocationSize(value.@uri)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@healthy)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@latencyMs)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@tipHeight)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@error);
+	}
 
-		// Constructor
-		public SqliteClientException(String @message)
-		{
-			this.@message = @message;
-		}
+	public override void Write(ServerStatus value, BigEndianStream stream)
+	{
+		FfiConverterString.INSTANCE.Write(value.@uri, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@healthy, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@latencyMs, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@tipHeight, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@error, stream);
 	}
+}
 
-	public class Canceled : LightWalletException { }
+internal record SyncOptions(
+	uint? @reorgRewindDepth = null,
+	uint? @maxReorgRewindDepth = null,
+	RetryPolicy? @retryPolicy = null,
+	List<String> @fallbackServers = null
+) { }
 
-	public class SyncFirst : LightWalletException { }
+class FfiConverterTypeSyncOptions : FfiConverterRustBuffer<SyncOptions>
+{
+	public static FfiConverterTypeSyncOptions INSTANCE = new FfiConverterTypeSyncOptions();
 
-	public class InsufficientFunds : LightWalletException
+	public override SyncOptions Read(BigEndianStream stream)
 	{
-		// Members
-		public ulong @required;
-		public ulong @available;
-
-		// Constructor
-		public InsufficientFunds(ulong @required, ulong @available)
-		{
-			this.@required = @required;
-			this.@available = @available;
-		}
+		return new SyncOptions(
+			@reorgRewindDepth: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@maxReorgRewindDepth: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@retryPolicy: FfiConverterOptionalTypeRetryPolicy.INSTANCE.Read(stream),
+			@fallbackServers: FfiConverterSequenceString.INSTANCE.Read(stream)
+		);
 	}
 
-	public class Other : LightWalletException
+	public override int AllocationSize(SyncOptions value)
 	{
-		// Members
-		public String @message;
+		return FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@reorgRewindDepth)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@maxReorgRewindDepth)
+			+ FfiConverterOptionalTypeRetryPolicy.INSTANCE.AllocationSize(value.@retryPolicy)
+			+ FfiConverterSequenceString.INSTANCE.AllocationSize(value.@fallbackServers);
+	}
 
-		// Constructor
-		public Other(String @message)
-		{
-			this.@message = @message;
-		}
+	public override void Write(SyncOptions value, BigEndianStream stream)
+	{
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@reorgRewindDepth, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@maxReorgRewindDepth, stream);
+		FfiConverterOptionalTypeRetryPolicy.INSTANCE.Write(value.@retryPolicy, stream);
+		FfiConverterSequenceString.INSTANCE.Write(value.@fallbackServers, stream);
 	}
 }
 
-class FfiConverterTypeLightWalletException : FfiConverterRustBuffer<LightWalletException>, CallStatusErrorHandler<LightWalletException>
-{
-	public static FfiConverterTypeLightWalletException INSTANCE = new FfiConverterTypeLightWalletException();
+/// <summary>
+/// Timings and counts for the work done so far in a sync.
+/// Downloading and scanning blocks overlap, so their timings may add up to more than the elapsed time.
+/// </summary>
+internal record SyncSummary(
+	ulong @elapsedMs,
+	ulong @subtreeRootsMs,
+	ulong @transparentMs,
+	ulong @downloadMs,
+	ulong @scanMs,
+	ulong @enhancementMs,
+	ulong @transactionsEnhanced,
+	ulong @dataRequests,
+	ulong @reorgs
+) { }
 
-	public override LightWalletException Read(BigEndianStream stream)
-	{
-		var value = stream.ReadInt();
-		switch (value)
-		{
-			case 1:
-				return new LightWalletException.InvalidArgument(FfiConverterString.INSTANCE.Read(stream));
-			case 2:
-				return new LightWalletException.InvalidUri();
-			case 3:
-				return new LightWalletException.SqliteClientException(FfiConverterString.INSTANCE.Read(stream));
-			case 4:
-				return new LightWalletException.Canceled();
-			case 5:
-				return new LightWalletException.SyncFirst();
-			case 6:
-				return new LightWalletException.InsufficientFunds(
-					FfiConverterUInt64.INSTANCE.Read(stream),
-					FfiConverterUInt64.INSTANCE.Read(stream)
-				);
-			case 7:
-				return new LightWalletException.Other(FfiConverterString.INSTANCE.Read(stream));
-			default:
-				throw new InternalException(
-					String.Format("invalid error value '{0}' in FfiConverterTypeLightWalletException.Read()", value)
-				);
-		}
+class FfiConverterTypeSyncSummary : FfiConverterRustBuffer<SyncSummary>
+{
+	public static FfiConverterTypeSyncSummary INSTANCE = new FfiConverterTypeSyncSummary();
+
+	public override SyncSummary Read(BigEndianStream stream)
+	{
+		return new SyncSummary(
+			@elapsedMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@subtreeRootsMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@transparentMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@downloadMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@scanMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@enhancementMs: FfiConverterUInt64.INSTANCE.Read(stream),
+			@transactionsEnhanced: FfiConverterUInt64.INSTANCE.Read(stream),
+			@dataRequests: FfiConverterUInt64.INSTANCE.Read(stream),
+			@reorgs: FfiConverterUInt64.INSTANCE.Read(stream)
+		);
 	}
 
-	public override int AllocationSize(LightWalletException value)
+	public override int AllocationSize(SyncSummary value)
 	{
-		switch (value)
-		{
-			case LightWalletException.InvalidArgument variant_value:
-				return 4 + FfiConverterString.INSTANCE.AllocationSize(variant_value.@message);
-			case LightWalletException.InvalidUri variant_value:
-				return 4;
-			case LightWalletException.SqliteClientException variant_value:
-				return 4 + FfiConverterString.INSTANCE.AllocationSize(variant_value.@message);
-			case LightWalletException.Canceled variant_value:
-				return 4;
-			case LightWalletException.SyncFirst variant_value:
-				return 4;
-			case LightWalletException.InsufficientFunds variant_value:
-				return 4
-					+ FfiConverterUInt64.INSTANCE.AllocationSize(variant_value.@required)
-					+ FfiConverterUInt64.INSTANCE.AllocationSize(variant_value.@available);
-			case LightWalletException.Other variant_value:
-				return 4 + FfiConverterString.INSTANCE.AllocationSize(variant_value.@message);
-			default:
-				throw new InternalException(
-					String.Format("invalid error value '{0}' in FfiConverterTypeLightWalletException.AllocationSize()", value)
-				);
-		}
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@elapsedMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@subtreeRootsMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@transparentMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@downloadMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@scanMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@enhancementMs)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@transactionsEnhanced)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@dataRequests)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@reorgs);
 	}
 
-	public override void Write(LightWalletException value, BigEndianStream stream)
+	public override void Write(SyncSummary value, BigEndianStream stream)
 	{
-		switch (value)
-		{
-			case LightWalletException.InvalidArgument variant_value:
-				stream.WriteInt(1);
-				FfiConverterString.INSTANCE.Write(variant_value.@message, stream);
-				break;
-			case LightWalletException.InvalidUri variant_value:
-				stream.WriteInt(2);
-				break;
-			case LightWalletException.SqliteClientException variant_value:
-				stream.WriteInt(3);
-				FfiConverterString.INSTANCE.Write(variant_value.@message, stream);
-				break;
-			case LightWalletException.Canceled variant_value:
-				stream.WriteInt(4);
-				break;
-			case LightWalletException.SyncFirst variant_value:
-				stream.WriteInt(5);
-				break;
-			case LightWalletException.InsufficientFunds variant_value:
-				stream.WriteInt(6);
-				FfiConverterUInt64.INSTANCE.Write(variant_value.@required, stream);
-				FfiConverterUInt64.INSTANCE.Write(variant_value.@available, stream);
-				break;
-			case LightWalletException.Other variant_value:
-				stream.WriteInt(7);
-				FfiConverterString.INSTANCE.Write(variant_value.@message, stream);
-				break;
-			default:
-				throw new InternalException(
-					String.Format("invalid error value '{0}' in FfiConverterTypeLightWalletException.Write()", value)
-				);
-		}
+		FfiConverterUInt64.INSTANCE.Write(value.@elapsedMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@subtreeRootsMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@transparentMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@downloadMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@scanMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@enhancementMs, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@transactionsEnhanced, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@dataRequests, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@reorgs, stream);
 	}
 }
 
-internal enum Pool : int
-{
-	Transparent,
-	Sapling,
-	Orchard,
-}
+internal record SyncUpdateData(
+	uint? @lastFullyScannedBlock,
+	uint @tipHeight,
+	ulong @currentStep,
+	ulong @totalSteps,
+	String? @lastError,
+	SyncPhase @phase,
+	double @progress,
+	ulong @blocksDownloaded,
+	ulong @bytesDownloaded,
+	ulong @blocksScanned,
+	double @blocksPerSecond,
+	ulong? @etaSeconds,
+	HeightRange? @currentRange,
+	ScanRangePriority? @currentRangePriority,
+	SyncSummary @summary,
+	String? @server
+) { }
 
-class FfiConverterTypePool : FfiConverterRustBuffer<Pool>
+class FfiConverterTypeSyncUpdateData : FfiConverterRustBuffer<SyncUpdateData>
 {
-	public static FfiConverterTypePool INSTANCE = new FfiConverterTypePool();
+	public static FfiConverterTypeSyncUpdateData INSTANCE = new FfiConverterTypeSyncUpdateData();
 
-	public override Pool Read(BigEndianStream stream)
+	public override SyncUpdateData Read(BigEndianStream stream)
 	{
-		var value = stream.ReadInt() - 1;
-		if (Enum.IsDefined(typeof(Pool), value))
-		{
-			return (Pool)value;
-		}
-		else
-		{
-			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypePool.Read()", value));
-		}
+		return new SyncUpdateData(
+			@lastFullyScannedBlock: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@tipHeight: FfiConverterUInt32.INSTANCE.Read(stream),
+			@currentStep: FfiConverterUInt64.INSTANCE.Read(stream),
+			@totalSteps: FfiConverterUInt64.INSTANCE.Read(stream),
+			@lastError: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@phase: FfiConverterTypeSyncPhase.INSTANCE.Read(stream),
+			@progress: FfiConverterDouble.INSTANCE.Read(stream),
+			@blocksDownloaded: FfiConverterUInt64.INSTANCE.Read(stream),
+			@bytesDownloaded: FfiConverterUInt64.INSTANCE.Read(stream),
+			@blocksScanned: FfiConverterUInt64.INSTANCE.Read(stream),
+			@blocksPerSecond: FfiConverterDouble.INSTANCE.Read(stream),
+			@etaSeconds: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@currentRange: FfiConverterOptionalTypeHeightRange.INSTANCE.Read(stream),
+			@currentRangePriority: FfiConverterOptionalTypeScanRangePriority.INSTANCE.Read(stream),
+			@summary: FfiConverterTypeSyncSummary.INSTANCE.Read(stream),
+			@server: FfiConverterOptionalString.INSTANCE.Read(stream)
+		);
 	}
 
-	public override int AllocationSize(Pool value)
+	public override int AllocationSize(SyncUpdateData value)
 	{
-		return 4;
+		return FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@lastFullyScannedBlock)
+			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@tipHeight)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@currentStep)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@totalSteps)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@lastError)
+			+ FfiConverterTypeSyncPhase.INSTANCE.AllocationSize(value.@phase)
+			+ FfiConverterDouble.INSTANCE.AllocationSize(value.@progress)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@blocksDownloaded)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@bytesDownloaded)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@blocksScanned)
+			+ FfiConverterDouble.INSTANCE.AllocationSize(value.@blocksPerSecond)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@etaSeconds)
+			+ FfiConverterOptionalTypeHeightRange.INSTANCE.AllocationSize(value.@currentRange)
+			+ FfiConverterOptionalTypeScanRangePriority.INSTANCE.AllocationSize(value.@currentRangePriority)
+			+ FfiConverterTypeSyncSummary.INSTANCE.AllocationSize(value.@summary)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@server);
 	}
 
-	public override void Write(Pool value, BigEndianStream stream)
+	public override void Write(SyncUpdateData value, BigEndianStream stream)
 	{
-		stream.WriteInt((int)value + 1);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@lastFullyScannedBlock, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@tipHeight, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@currentStep, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@totalSteps, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@lastError, stream);
+		FfiConverterTypeSyncPhase.INSTANCE.Write(value.@phase, stream);
+		FfiConverterDouble.INSTANCE.Write(value.@progress, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@blocksDownloaded, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@bytesDownloaded, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@blocksScanned, stream);
+		FfiConverterDouble.INSTANCE.Write(value.@blocksPerSecond, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@etaSeconds, stream);
+		FfiConverterOptionalTypeHeightRange.INSTANCE.Write(value.@currentRange, stream);
+		FfiConverterOptionalTypeScanRangePriority.INSTANCE.Write(value.@currentRangePriority, stream);
+		FfiConverterTypeSyncSummary.INSTANCE.Write(value.@summary, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@server, stream);
 	}
 }
 
-static class UniffiCallbackResponseCode
-{
-	public static int SUCCESS = 0;
-	public static int ERROR = 1;
-	public static int UNEXPECTED_ERROR = 2;
-}
+/// <summary>
+/// Routes the traffic to light wallet servers and parameter downloads through Tor.
+/// </summary>
+internal record TorOptions(String @socksAddress, String? @httpTunnelAddress = null, bool @isolateAccounts = true) { }
 
-class ConcurrentHandleMap<T>
-	where T : notnull
+class FfiConverterTypeTorOptions : FfiConverterRustBuffer<TorOptions>
 {
-	Dictionary<ulong, T> leftMap = new Dictionary<ulong, T>();
-	Dictionary<T, ulong> rightMap = new Dictionary<T, ulong>();
+	public static FfiConverterTypeTorOptions INSTANCE = new FfiConverterTypeTorOptions();
 
-	Object lock_ = new Object();
-	ulong currentHandle = 0;
+	public override TorOptions Read(BigEndianStream stream)
+	{
+		return new TorOptions(
+			@socksAddress: FfiConverterString.INSTANCE.Read(stream),
+			@httpTunnelAddress: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@isolateAccounts: FfiConverterBoolean.INSTANCE.Read(stream)
+		);
+	}
 
-	public ulong Insert(T obj)
+	public override int AllocationSize(TorOptions value)
 	{
-		lock (lock_)
-		{
-			ulong existingHandle = 0;
-			if (rightMap.TryGetValue(obj, out existingHandle))
-			{
-				return existingHandle;
-			}
-			currentHandle += 1;
-			leftMap[currentHandle] = obj;
-			rightMap[obj] = currentHandle;
-			return currentHandle;
-		}
+		return FfiConverterString.INSTANCE.AllocationSize(value.@socksAddress)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@httpTunnelAddress)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@isolateAccounts);
 	}
 
-	public bool TryGet(ulong handle, out T result)
+	public override void Write(TorOptions value, BigEndianStream stream)
 	{
-		// Possible null reference assignment
-#pragma warning disable 8601
-		return leftMap.TryGetValue(handle, out result);
-#pragma warning restore 8601
+		FfiConverterString.INSTANCE.Write(value.@socksAddress, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@httpTunnelAddress, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@isolateAccounts, stream);
 	}
+}
 
-	public bool Remove(ulong handle)
+internal record Transaction(
+	uint @accountId,
+	byte[] @txid,
+	DateTime? @blockTime,
+	uint? @minedHeight,
+	bool @expiredUnmined,
+	long @accountBalanceDelta,
+	ulong? @fee,
+	String? @label,
+	List<TransactionNote> @outgoing,
+	List<TransactionNote> @incoming,
+	List<TransactionNote> @change
+) { }
+
+class FfiConverterTypeTransaction : FfiConverterRustBuffer<Transaction>
+{
+	public static FfiConverterTypeTransaction INSTANCE = new FfiConverterTypeTransaction();
+
+	public override Transaction Read(BigEndianStream stream)
 	{
-		return Remove(handle, out T result);
+		return new Transaction(
+			@accountId: FfiConverterUInt32.INSTANCE.Read(stream),
+			@txid: FfiConverterByteArray.INSTANCE.Read(stream),
+			@blockTime: FfiConverterOptionalTimestamp.INSTANCE.Read(stream),
+			@minedHeight: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@expiredUnmined: FfiConverterBoolean.INSTANCE.Read(stream),
+			@accountBalanceDelta: FfiConverterInt64.INSTANCE.Read(stream),
+			@fee: FfiConverterOptionalUInt64.INSTANCE.Read(stream),
+			@label: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@outgoing: FfiConverterSequenceTypeTransactionNote.INSTANCE.Read(stream),
+			@incoming: FfiConverterSequenceTypeTransactionNote.INSTANCE.Read(stream),
+			@change: FfiConverterSequenceTypeTransactionNote.INSTANCE.Read(stream)
+		);
 	}
 
-	public bool Remove(ulong handle, out T result)
+	public override int AllocationSize(Transaction value)
 	{
-		lock (lock_)
-		{
-			// Possible null reference assignment
-#pragma warning disable 8601
-			if (leftMap.TryGetValue(handle, out result))
-			{
-#pragma warning restore 8601
-				leftMap.Remove(handle);
-				rightMap.Remove(result);
-				return true;
-			}
-			else
-			{
-				return false;
-			}
-		}
+		return FfiConverterUInt32.INSTANCE.AllocationSize(value.@accountId)
+			+ FfiConverterByteArray.INSTANCE.AllocationSize(value.@txid)
+			+ FfiConverterOptionalTimestamp.INSTANCE.AllocationSize(value.@blockTime)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@minedHeight)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@expiredUnmined)
+			+ FfiConverterInt64.INSTANCE.AllocationSize(value.@accountBalanceDelta)
+			+ FfiConverterOptionalUInt64.INSTANCE.AllocationSize(value.@fee)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@label)
+			+ FfiConverterSequenceTypeTransactionNote.INSTANCE.AllocationSize(value.@outgoing)
+			+ FfiConverterSequenceTypeTransactionNote.INSTANCE.AllocationSize(value.@incoming)
+			+ FfiConverterSequenceTypeTransactionNote.INSTANCE.AllocationSize(value.@change);
+	}
+
+	public override void Write(Transaction value, BigEndianStream stream)
+	{
+		FfiConverterUInt32.INSTANCE.Write(value.@accountId, stream);
+		FfiConverterByteArray.INSTANCE.Write(value.@txid, stream);
+		FfiConverterOptionalTimestamp.INSTANCE.Write(value.@blockTime, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@minedHeight, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@expiredUnmined, stream);
+		FfiConverterInt64.INSTANCE.Write(value.@accountBalanceDelta, stream);
+		FfiConverterOptionalUInt64.INSTANCE.Write(value.@fee, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@label, stream);
+		FfiConverterSequenceTypeTransactionNote.INSTANCE.Write(value.@outgoing, stream);
+		FfiConverterSequenceTypeTransactionNote.INSTANCE.Write(value.@incoming, stream);
+		FfiConverterSequenceTypeTransactionNote.INSTANCE.Write(value.@change, stream);
 	}
 }
 
-[UnmanagedFunctionPointer(CallingConvention.Cdecl)]
-internal delegate int ForeignCallback(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf);
+internal record TransactionNote(ulong @value, byte[]? @memo, String @recipient, String? @recipientLabel, Pool @pool) { }
 
-internal abstract class FfiConverterCallbackInterface<CallbackInterface> : FfiConverter<CallbackInterface, ulong>
-	where CallbackInterface : notnull
+class FfiConverterTypeTransactionNote : FfiConverterRustBuffer<TransactionNote>
 {
-	ConcurrentHandleMap<CallbackInterface> handleMap = new ConcurrentHandleMap<CallbackInterface>();
-
-	// Registers the foreign callback with the Rust side.
-	// This method is generated for each callback interface.
-	public abstract void Register();
+	public static FfiConverterTypeTransactionNote INSTANCE = new FfiConverterTypeTransactionNote();
 
-	public RustBuffer Drop(ulong handle)
+	public override TransactionNote Read(BigEndianStream stream)
 	{
-		handleMap.Remove(handle);
-		return new RustBuffer();
+		return new TransactionNote(
+			@value: FfiConverterUInt64.INSTANCE.Read(stream),
+			@memo: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
+			@recipient: FfiConverterString.INSTANCE.Read(stream),
+			@recipientLabel: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@pool: FfiConverterTypePool.INSTANCE.Read(stream)
+		);
 	}
 
-	public override CallbackInterface Lift(ulong handle)
+	public override int AllocationSize(TransactionNote value)
 	{
-		if (!handleMap.TryGet(handle, out CallbackInterface result))
-		{
-			throw new InternalException($"No callback in handlemap '{handle}'");
-		}
-		return result;
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@value)
+			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@memo)
+			+ FfiConverterString.INSTANCE.AllocationSize(value.@recipient)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@recipientLabel)
+			+ FfiConverterTypePool.INSTANCE.AllocationSize(value.@pool);
 	}
 
-	public override CallbackInterface Read(BigEndianStream stream)
+	public override void Write(TransactionNote value, BigEndianStream stream)
 	{
-		return Lift(stream.ReadULong());
+		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
+		FfiConverterOptionalByteArray.INSTANCE.Write(value.@memo, stream);
+		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@recipientLabel, stream);
+		FfiConverterTypePool.INSTANCE.Write(value.@pool, stream);
 	}
+}
 
-	public override ulong Lower(CallbackInterface value)
+internal record TransactionSendDetail(ulong @value, byte[]? @memo, String @recipient) { }
+
+class FfiConverterTypeTransactionSendDetail : FfiConverterRustBuffer<TransactionSendDetail>
+{
+	public static FfiConverterTypeTransactionSendDetail INSTANCE = new FfiConverterTypeTransactionSendDetail();
+
+	public override TransactionSendDetail Read(BigEndianStream stream)
 	{
-		return handleMap.Insert(value);
+		return new TransactionSendDetail(
+			@value: FfiConverterUInt64.INSTANCE.Read(stream),
+			@memo: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
+			@recipient: FfiConverterString.INSTANCE.Read(stream)
+		);
 	}
 
-	public override int AllocationSize(CallbackInterface value)
+	public override int AllocationSize(TransactionSendDetail value)
 	{
-		return 8;
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@value)
+			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@memo)
+			+ FfiConverterString.INSTANCE.AllocationSize(value.@recipient);
 	}
 
-	public override void Write(CallbackInterface value, BigEndianStream stream)
+	public override void Write(TransactionSendDetail value, BigEndianStream stream)
 	{
-		stream.WriteULong(Lower(value));
+		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
+		FfiConverterOptionalByteArray.INSTANCE.Write(value.@memo, stream);
+		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
 	}
 }
 
-internal interface CancellationSource
-{
-	void SetCancellationId(uint @id);
-}
+internal record TransparentNote(ulong @value, String @recipient) { }
 
-// The ForeignCallback that is passed to Rust.
-class ForeignCallbackTypeCancellationSource
+class FfiConverterTypeTransparentNote : FfiConverterRustBuffer<TransparentNote>
 {
-	public static readonly ForeignCallback INSTANCE = INSTANCE_FUNC;
+	public static FfiConverterTypeTransparentNote INSTANCE = new FfiConverterTypeTransparentNote();
 
-#if IOS
-	[ObjCRuntime.MonoPInvokeCallback(typeof(ForeignCallback))]
-#endif
-	private static int INSTANCE_FUNC(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf)
+	public override TransparentNote Read(BigEndianStream stream)
 	{
-		var cb = FfiConverterTypeCancellationSource.INSTANCE.Lift(handle);
-		switch (method)
-		{
-			case 0:
-			{
-				// 0 means Rust is done with the callback, and the callback
-				// can be dropped by the foreign language.
-				FfiConverterTypeCancellationSource.INSTANCE.Drop(handle);
-				// No return value.
-				// See docs of ForeignCallback in `uniffi/src/ffi/foreigncallbacks.rs`
-				return 0;
-			}
-
-			case 1:
-			{
-				try
-				{
-					outBuf = InvokeSetCancellationId(cb, RustBuffer.MemoryStream(argsData, argsLength));
-					return UniffiCallbackResponseCode.SUCCESS;
-				}
-				catch (Exception e)
-				{
-					// Unexpected error
-					try
-					{
-						// Try to serialize the error into a string
-						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
-					}
-					catch
-					{
-						// If that fails, then it's time to give up and just return
-					}
-					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
-				}
-			}
+		return new TransparentNote(@value: FfiConverterUInt64.INSTANCE.Read(stream), @recipient: FfiConverterString.INSTANCE.Read(stream));
+	}
 
-			default:
-			{
-				// This should never happen, because an out of bounds method index won't
-				// ever be used. Once we can catch errors, we should return an InternalException.
-				// https://github.com/mozilla/uniffi-rs/issues/351
-				return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
-			}
-		}
+	public override int AllocationSize(TransparentNote value)
+	{
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@value) + FfiConverterString.INSTANCE.AllocationSize(value.@recipient);
 	}
 
-	static RustBuffer InvokeSetCancellationId(CancellationSource callback, BigEndianStream stream)
+	public override void Write(TransparentNote value, BigEndianStream stream)
 	{
-		callback.SetCancellationId(FfiConverterUInt32.INSTANCE.Read(stream));
-		return new RustBuffer();
+		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
+		FfiConverterString.INSTANCE.Write(value.@recipient, stream);
 	}
 }
 
-// The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
-class FfiConverterTypeCancellationSource : FfiConverterCallbackInterface<CancellationSource>
+internal record UnspentNote(
+	Pool @pool,
+	ulong @value,
+	byte[] @txid,
+	uint @outputIndex,
+	uint? @minedHeight,
+	uint? @confirmations,
+	bool @isChange,
+	byte[]? @diversifier,
+	String? @recipient,
+	bool @hasMemo,
+	NoteSpendability @spendability
+) { }
+
+class FfiConverterTypeUnspentNote : FfiConverterRustBuffer<UnspentNote>
 {
-	public static FfiConverterTypeCancellationSource INSTANCE = new FfiConverterTypeCancellationSource();
+	public static FfiConverterTypeUnspentNote INSTANCE = new FfiConverterTypeUnspentNote();
 
-	public override void Register()
+	public override UnspentNote Read(BigEndianStream stream)
 	{
-		_UniffiHelpers.RustCall(
-			(ref RustCallStatus status) =>
-			{
-				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_init_callback_cancellationsource(
-					ForeignCallbackTypeCancellationSource.INSTANCE,
-					ref status
-				);
-			}
+		return new UnspentNote(
+			@pool: FfiConverterTypePool.INSTANCE.Read(stream),
+			@value: FfiConverterUInt64.INSTANCE.Read(stream),
+			@txid: FfiConverterByteArray.INSTANCE.Read(stream),
+			@outputIndex: FfiConverterUInt32.INSTANCE.Read(stream),
+			@minedHeight: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@confirmations: FfiConverterOptionalUInt32.INSTANCE.Read(stream),
+			@isChange: FfiConverterBoolean.INSTANCE.Read(stream),
+			@diversifier: FfiConverterOptionalByteArray.INSTANCE.Read(stream),
+			@recipient: FfiConverterOptionalString.INSTANCE.Read(stream),
+			@hasMemo: FfiConverterBoolean.INSTANCE.Read(stream),
+			@spendability: FfiConverterTypeNoteSpendability.INSTANCE.Read(stream)
 		);
 	}
-}
-
-internal interface SyncUpdate
-{
-	void UpdateStatus(SyncUpdateData @data);
-	void ReportTransactions(List<Transaction> @transactions);
-}
 
-// The ForeignCallback that is passed to Rust.
-class ForeignCallbackTypeSyncUpdate
-{
-	public static readonly ForeignCallback INSTANCE = INSTANCE_FUNC;
+	public override int AllocationSize(UnspentNote value)
+	{
+		return FfiConverterTypePool.INSTANCE.AllocationSize(value.@pool)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@value)
+			+ FfiConverterByteArray.INSTANCE.AllocationSize(value.@txid)
+			+ FfiConverterUInt32.INSTANCE.AllocationSize(value.@outputIndex)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@minedHeight)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@confirmations)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@isChange)
+			+ FfiConverterOptionalByteArray.INSTANCE.AllocationSize(value.@diversifier)
+			+ FfiConverterOptionalString.INSTANCE.AllocationSize(value.@recipient)
+			+ FfiConverterBoolean.INSTANCE.AllocationSize(value.@hasMemo)
+			+ FfiConverterTypeNoteSpendability.INSTANCE.AllocationSize(value.@spendability);
+	}
 
-#if IOS
-	[ObjCRuntime.MonoPInvokeCallback(typeof(ForeignCallback))]
-#endif
-	private static int INSTANCE_FUNC(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf)
+	public override void Write(UnspentNote value, BigEndianStream stream)
 	{
-		var cb = FfiConverterTypeSyncUpdate.INSTANCE.Lift(handle);
-		switch (method)
-		{
-			case 0:
-			{
-				// 0 means Rust is done with the callback, and the callback
-				// can be dropped by the foreign language.
-				FfiConverterTypeSyncUpdate.INSTANCE.Drop(handle);
-				// No return value.
-				// See docs of ForeignCallback in `uniffi/src/ffi/foreigncallbacks.rs`
-				return 0;
-			}
+		FfiConverterTypePool.INSTANCE.Write(value.@pool, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@value, stream);
+		FfiConverterByteArray.INSTANCE.Write(value.@txid, stream);
+		FfiConverterUInt32.INSTANCE.Write(value.@outputIndex, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@minedHeight, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@confirmations, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@isChange, stream);
+		FfiConverterOptionalByteArray.INSTANCE.Write(value.@diversifier, stream);
+		FfiConverterOptionalString.INSTANCE.Write(value.@recipient, stream);
+		FfiConverterBoolean.INSTANCE.Write(value.@hasMemo, stream);
+		FfiConverterTypeNoteSpendability.INSTANCE.Write(value.@spendability, stream);
+	}
+}
 
-			case 1:
-			{
-				try
-				{
-					outBuf = InvokeUpdateStatus(cb, RustBuffer.MemoryStream(argsData, argsLength));
-					return UniffiCallbackResponseCode.SUCCESS;
-				}
-				catch (Exception e)
-				{
-					// Unexpected error
-					try
-					{
-						// Try to serialize the error into a string
-						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
-					}
-					catch
-					{
-						// If that fails, then it's time to give up and just return
-					}
-					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
-				}
-			}
+internal record UserBalances(
+	ulong @spendable,
+	ulong @immatureChange,
+	ulong @minimumFees,
+	ulong @immatureIncome,
+	ulong @dust,
+	ulong @incoming,
+	ulong @incomingDust
+) { }
 
-			case 2:
-			{
-				try
-				{
-					outBuf = InvokeReportTransactions(cb, RustBuffer.MemoryStream(argsData, argsLength));
-					return UniffiCallbackResponseCode.SUCCESS;
-				}
-				catch (Exception e)
-				{
-					// Unexpected error
-					try
-					{
-						// Try to serialize the error into a string
-						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
-					}
-					catch
-					{
-						// If that fails, then it's time to give up and just return
-					}
-					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
-				}
-			}
+class FfiConverterTypeUserBalances : FfiConverterRustBuffer<UserBalances>
+{
+	public static FfiConverterTypeUserBalances INSTANCE = new FfiConverterTypeUserBalances();
 
-			default:
-			{
-				// This should never happen, because an out of bounds method index won't
-				// ever be used. Once we can catch errors, we should return an InternalException.
-				// https://github.com/mozilla/uniffi-rs/issues/351
-				return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
-			}
-		}
+	public override UserBalances Read(BigEndianStream stream)
+	{
+		return new UserBalances(
+			@spendable: FfiConverterUInt64.INSTANCE.Read(stream),
+			@immatureChange: FfiConverterUInt64.INSTANCE.Read(stream),
+			@minimumFees: FfiConverterUInt64.INSTANCE.Read(stream),
+			@immatureIncome: FfiConverterUInt64.INSTANCE.Read(stream),
+			@dust: FfiConverterUInt64.INSTANCE.Read(stream),
+			@incoming: FfiConverterUInt64.INSTANCE.Read(stream),
+			@incomingDust: FfiConverterUInt64.INSTANCE.Read(stream)
+		);
 	}
 
-	static RustBuffer InvokeUpdateStatus(SyncUpdate callback, BigEndianStream stream)
+	public override int AllocationSize(UserBalances value)
 	{
-		callback.UpdateStatus(FfiConverterTypeSyncUpdateData.INSTANCE.Read(stream));
-		return new RustBuffer();
+		return FfiConverterUInt64.INSTANCE.AllocationSize(value.@spendable)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@immatureChange)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@minimumFees)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@immatureIncome)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@dust)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@incoming)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@incomingDust);
 	}
 
-	static RustBuffer InvokeReportTransactions(SyncUpdate callback, BigEndianStream stream)
+	public override void Write(UserBalances value, BigEndianStream stream)
 	{
-		callback.ReportTransactions(FfiConverterSequenceTypeTransaction.INSTANCE.Read(stream));
-		return new RustBuffer();
+		FfiConverterUInt64.INSTANCE.Write(value.@spendable, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@immatureChange, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@minimumFees, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@immatureIncome, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@dust, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@incoming, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@incomingDust, stream);
 	}
 }
 
-// The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
-class FfiConverterTypeSyncUpdate : FfiConverterCallbackInterface<SyncUpdate>
+internal record WalletCheckReport(
+	List<uint> @treeInconsistencies,
+	List<HeightRange> @scanQueueGaps,
+	ulong @orphanedNotes,
+	List<byte[]> @missingRawTransactions,
+	List<byte[]> @missingFees,
+	ulong @unknownOutputPools,
+	uint? @safeTruncationHeight
+) { }
+
+class FfiConverterTypeWalletCheckReport : FfiConverterRustBuffer<WalletCheckReport>
 {
-	public static FfiConverterTypeSyncUpdate INSTANCE = new FfiConverterTypeSyncUpdate();
+	public static FfiConverterTypeWalletCheckReport INSTANCE = new FfiConverterTypeWalletCheckReport();
+
+	public override WalletCheckReport Read(BigEndianStream stream)
+	{
+		return new WalletCheckReport(
+			@treeInconsistencies: FfiConverterSequenceUInt32.INSTANCE.Read(stream),
+			@scanQueueGaps: FfiConverterSequenceTypeHeightRange.INSTANCE.Read(stream),
+			@orphanedNotes: FfiConverterUInt64.INSTANCE.Read(stream),
+			@missingRawTransactions: FfiConverterSequenceByteArray.INSTANCE.Read(stream),
+			@missingFees: FfiConverterSequenceByteArray.INSTANCE.Read(stream),
+			@unknownOutputPools: FfiConverterUInt64.INSTANCE.Read(stream),
+			@safeTruncationHeight: FfiConverterOptionalUInt32.INSTANCE.Read(stream)
+		);
+	}
 
-	public override void Register()
+	public override int AllocationSize(WalletCheckReport value)
 	{
-		_UniffiHelpers.RustCall(
-			(ref RustCallStatus status) =>
-			{
-				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_init_callback_syncupdate(ForeignCallbackTypeSyncUpdate.INSTANCE, ref status);
-			}
-		);
+		return FfiConverterSequenceUInt32.INSTANCE.AllocationSize(value.@treeInconsistencies)
+			+ FfiConverterSequenceTypeHeightRange.INSTANCE.AllocationSize(value.@scanQueueGaps)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@orphanedNotes)
+			+ FfiConverterSequenceByteArray.INSTANCE.AllocationSize(value.@missingRawTransactions)
+			+ FfiConverterSequenceByteArray.INSTANCE.AllocationSize(value.@missingFees)
+			+ FfiConverterUInt64.INSTANCE.AllocationSize(value.@unknownOutputPools)
+			+ FfiConverterOptionalUInt32.INSTANCE.AllocationSize(value.@safeTruncationHeight);
+	}
+
+	public override void Write(WalletCheckReport value, BigEndianStream stream)
+	{
+		FfiConverterSequenceUInt32.INSTANCE.Write(value.@treeInconsistencies, stream);
+		FfiConverterSequenceTypeHeightRange.INSTANCE.Write(value.@scanQueueGaps, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@orphanedNotes, stream);
+		FfiConverterSequenceByteArray.INSTANCE.Write(value.@missingRawTransactions, stream);
+		FfiConverterSequenceByteArray.INSTANCE.Write(value.@missingFees, stream);
+		FfiConverterUInt64.INSTANCE.Write(value.@unknownOutputPools, stream);
+		FfiConverterOptionalUInt32.INSTANCE.Write(value.@safeTruncationHeight, stream);
 	}
 }
 
-class FfiConverterOptionalUInt32 : FfiConverterRustBuffer<uint?>
+internal enum BatchPaymentStatus : int
 {
-	public static FfiConverterOptionalUInt32 INSTANCE = new FfiConverterOptionalUInt32();
+	Pending,
+	Created,
+	Sent,
+	Failed,
+}
 
-	public override uint? Read(BigEndianStream stream)
-	{
-		if (stream.ReadByte() == 0)
-		{
-			return null;
-		}
-		return FfiConverterUInt32.INSTANCE.Read(stream);
-	}
+class FfiConverterTypeBatchPaymentStatus : FfiConverterRustBuffer<BatchPaymentStatus>
+{
+	public static FfiConverterTypeBatchPaymentStatus INSTANCE = new FfiConverterTypeBatchPaymentStatus();
 
-	public override int AllocationSize(uint? value)
+	public override BatchPaymentStatus Read(BigEndianStream stream)
 	{
-		if (value == null)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(BatchPaymentStatus), value))
 		{
-			return 1;
+			return (BatchPaymentStatus)value;
 		}
 		else
 		{
-			return 1 + FfiConverterUInt32.INSTANCE.AllocationSize((uint)value);
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeBatchPaymentStatus.Read()", value));
 		}
 	}
 
-	public override void Write(uint? value, BigEndianStream stream)
+	public override int AllocationSize(BatchPaymentStatus value)
 	{
-		if (value == null)
-		{
-			stream.WriteByte(0);
-		}
-		else
-		{
-			stream.WriteByte(1);
-			FfiConverterUInt32.INSTANCE.Write((uint)value, stream);
-		}
+		return 4;
+	}
+
+	public override void Write(BatchPaymentStatus value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterOptionalUInt64 : FfiConverterRustBuffer<ulong?>
+internal enum ChainType : int
 {
-	public static FfiConverterOptionalUInt64 INSTANCE = new FfiConverterOptionalUInt64();
+	Testnet,
+	Mainnet,
+}
 
-	public override ulong? Read(BigEndianStream stream)
-	{
-		if (stream.ReadByte() == 0)
-		{
-			return null;
-		}
-		return FfiConverterUInt64.INSTANCE.Read(stream);
-	}
+class FfiConverterTypeChainType : FfiConverterRustBuffer<ChainType>
+{
+	public static FfiConverterTypeChainType INSTANCE = new FfiConverterTypeChainType();
 
-	public override int AllocationSize(ulong? value)
+	public override ChainType Read(BigEndianStream stream)
 	{
-		if (value == null)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(ChainType), value))
 		{
-			return 1;
+			return (ChainType)value;
 		}
 		else
 		{
-			return 1 + FfiConverterUInt64.INSTANCE.AllocationSize((ulong)value);
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeChainType.Read()", value));
 		}
 	}
 
-	public override void Write(ulong? value, BigEndianStream stream)
+	public override int AllocationSize(ChainType value)
 	{
-		if (value == null)
-		{
-			stream.WriteByte(0);
-		}
-		else
-		{
-			stream.WriteByte(1);
-			FfiConverterUInt64.INSTANCE.Write((ulong)value, stream);
-		}
+		return 4;
+	}
+
+	public override void Write(ChainType value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterOptionalString : FfiConverterRustBuffer<String?>
+internal class LightWalletException : UniffiException
 {
-	public static FfiConverterOptionalString INSTANCE = new FfiConverterOptionalString();
+	// Each variant is a nested class
 
-	public override String? Read(BigEndianStream stream)
+
+	public class InvalidArgument : LightWalletException
 	{
-		if (stream.ReadByte() == 0)
+		// Members
+		public String @message;
+
+		// Constructor
+		public InvalidArgument(String @message)
 		{
-			return null;
+			this.@message = @message;
 		}
-		return FfiConverterString.INSTANCE.Read(stream);
 	}
 
-	public override int AllocationSize(String? value)
+	public class InvalidUri : LightWalletException { }
+
+	public class SqliteClientException : LightWalletException
 	{
-		if (value == null)
-		{
-			return 1;
-		}
-		else
+		// Members
+		public String @message;
+
+		// Constructor
+		public SqliteClientException(String @message)
 		{
-			return 1 + FfiConverterString.INSTANCE.AllocationSize((String)value);
+			this.@message = @message;
 		}
 	}
 
-	public override void Write(String? value, BigEndianStream stream)
+	public class Canceled : LightWalletException { }
+
+	public class SyncFirst : LightWalletException { }
+
+	public class InsufficientFunds : LightWalletException
 	{
-		if (value == null)
+		// Members
+		public ulong @required;
+		public ulong @available;
+
+		// Constructor
+		public InsufficientFunds(ulong @required, ulong @available)
 		{
-			stream.WriteByte(0);
+			this.@required = @required;
+			this.@available = @available;
 		}
-		else
+	}
+
+	public class Other : LightWalletException
+	{
+		// Members
+		public String @message;
+
+		// Constructor
+		public Other(String @message)
 		{
-			stream.WriteByte(1);
-			FfiConverterString.INSTANCE.Write((String)value, stream);
+			this.@message = @message;
 		}
 	}
 }
 
-class FfiConverterOptionalByteArray : FfiConverterRustBuffer<byte[]?>
+class FfiConverterTypeLightWalletException : FfiConverterRustBuffer<LightWalletException>, CallStatusErrorHandler<LightWalletException>
 {
-	public static FfiConverterOptionalByteArray INSTANCE = new FfiConverterOptionalByteArray();
+	public static FfiConverterTypeLightWalletException INSTANCE = new FfiConverterTypeLightWalletException();
 
-	public override byte[]? Read(BigEndianStream stream)
+	public override LightWalletException Read(BigEndianStream stream)
 	{
-		if (stream.ReadByte() == 0)
+		var value = stream.ReadInt();
+		switch (value)
 		{
-			return null;
+			case 1:
+				return new LightWalletException.InvalidArgument(FfiConverterString.INSTANCE.Read(stream));
+			case 2:
+				return new LightWalletException.InvalidUri();
+			case 3:
+				return new LightWalletException.SqliteClientException(FfiConverterString.INSTANCE.Read(stream));
+			case 4:
+				return new LightWalletException.Canceled();
+			case 5:
+				return new LightWalletException.SyncFirst();
+			case 6:
+				return new LightWalletException.InsufficientFunds(
+					FfiConverterUInt64.INSTANCE.Read(stream),
+					FfiConverterUInt64.INSTANCE.Read(stream)
+				);
+			case 7:
+				return new LightWalletException.Other(FfiConverterString.INSTANCE.Read(stream));
+			default:
+				throw new InternalException(
+					String.Format("invalid error value '{0}' in FfiConverterTypeLightWalletException.Read()", value)
+				);
 		}
-		return FfiConverterByteArray.INSTANCE.Read(stream);
 	}
 
-	public override int AllocationSize(byte[]? value)
+	public override int AllocationSize(LightWalletException value)
 	{
-		if (value == null)
-		{
-			return 1;
-		}
-		else
+		switch (value)
 		{
-			return 1 + FfiConverterByteArray.INSTANCE.AllocationSize((byte[])value);
+			case LightWalletException.InvalidArgument variant_value:
+				return 4 + FfiConverterString.INSTANCE.AllocationSize(variant_value.@message);
+			case LightWalletException.InvalidUri variant_value:
+				return 4;
+			case LightWalletException.SqliteClientException variant_value:
+				return 4 + FfiConverterString.INSTANCE.AllocationSize(variant_value.@message);
+			case LightWalletException.Canceled variant_value:
+				return 4;
+			case LightWalletException.SyncFirst variant_value:
+				return 4;
+			case LightWalletException.InsufficientFunds variant_value:
+				return 4
+					+ FfiConverterUInt64.INSTANCE.AllocationSize(variant_value.@required)
+					+ FfiConverterUInt64.INSTANCE.AllocationSize(variant_value.@available);
+			case LightWalletException.Other variant_value:
+				return 4 + FfiConverterString.INSTANCE.AllocationSize(variant_value.@message);
+			default:
+				throw new InternalException(
+					String.Format("invalid error value '{0}' in FfiConverterTypeLightWalletException.AllocationSize()", value)
+				);
 		}
 	}
 
-	public override void Write(byte[]? value, BigEndianStream stream)
+	public override void Write(LightWalletException value, BigEndianStream stream)
 	{
-		if (value == null)
-		{
-			stream.WriteByte(0);
-		}
-		else
+		switch (value)
 		{
-			stream.WriteByte(1);
-			FfiConverterByteArray.INSTANCE.Write((byte[])value, stream);
+			case LightWalletException.InvalidArgument variant_value:
+				stream.WriteInt(1);
+				FfiConverterString.INSTANCE.Write(variant_value.@message, stream);
+				break;
+			case LightWalletException.InvalidUri variant_value:
+				stream.WriteInt(2);
+				break;
+			case LightWalletException.SqliteClientException variant_value:
+				stream.WriteInt(3);
+				FfiConverterString.INSTANCE.Write(variant_value.@message, stream);
+				break;
+			case LightWalletException.Canceled variant_value:
+				stream.WriteInt(4);
+				break;
+			case LightWalletException.SyncFirst variant_value:
+				stream.WriteInt(5);
+				break;
+			case LightWalletException.InsufficientFunds variant_value:
+				stream.WriteInt(6);
+				FfiConverterUInt64.INSTANCE.Write(variant_value.@required, stream);
+				FfiConverterUInt64.INSTANCE.Write(variant_value.@available, stream);
+				break;
+			case LightWalletException.Other variant_value:
+				stream.WriteInt(7);
+				FfiConverterString.INSTANCE.Write(variant_value.@message, stream);
+				break;
+			default:
+				throw new InternalException(
+					String.Format("invalid error value '{0}' in FfiConverterTypeLightWalletException.Write()", value)
+				);
 		}
 	}
 }
 
-class FfiConverterOptionalTimestamp : FfiConverterRustBuffer<DateTime?>
+internal enum LogLevel : int
 {
-	public static FfiConverterOptionalTimestamp INSTANCE = new FfiConverterOptionalTimestamp();
+	Error,
+	Warn,
+	Info,
+	Debug,
+	Trace,
+}
 
-	public override DateTime? Read(BigEndianStream stream)
-	{
-		if (stream.ReadByte() == 0)
-		{
-			return null;
-		}
-		return FfiConverterTimestamp.INSTANCE.Read(stream);
-	}
+class FfiConverterTypeLogLevel : FfiConverterRustBuffer<LogLevel>
+{
+	public static FfiConverterTypeLogLevel INSTANCE = new FfiConverterTypeLogLevel();
 
-	public override int AllocationSize(DateTime? value)
+	public override LogLevel Read(BigEndianStream stream)
 	{
-		if (value == null)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(LogLevel), value))
 		{
-			return 1;
+			return (LogLevel)value;
 		}
 		else
 		{
-			return 1 + FfiConverterTimestamp.INSTANCE.AllocationSize((DateTime)value);
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeLogLevel.Read()", value));
 		}
 	}
 
-	public override void Write(DateTime? value, BigEndianStream stream)
+	public override int AllocationSize(LogLevel value)
 	{
-		if (value == null)
-		{
-			stream.WriteByte(0);
-		}
-		else
-		{
-			stream.WriteByte(1);
-			FfiConverterTimestamp.INSTANCE.Write((DateTime)value, stream);
-		}
+		return 4;
+	}
+
+	public override void Write(LogLevel value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterOptionalTypeCancellationSource : FfiConverterRustBuffer<CancellationSource?>
+internal enum NoteSpendability : int
 {
-	public static FfiConverterOptionalTypeCancellationSource INSTANCE = new FfiConverterOptionalTypeCancellationSource();
+	Spendable,
+	Immature,
+	Unconfirmed,
+	Dust,
+}
 
-	public override CancellationSource? Read(BigEndianStream stream)
-	{
-		if (stream.ReadByte() == 0)
-		{
-			return null;
-		}
-		return FfiConverterTypeCancellationSource.INSTANCE.Read(stream);
-	}
+class FfiConverterTypeNoteSpendability : FfiConverterRustBuffer<NoteSpendability>
+{
+	public static FfiConverterTypeNoteSpendability INSTANCE = new FfiConverterTypeNoteSpendability();
 
-	public override int AllocationSize(CancellationSource? value)
+	public override NoteSpendability Read(BigEndianStream stream)
 	{
-		if (value == null)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(NoteSpendability), value))
 		{
-			return 1;
+			return (NoteSpendability)value;
 		}
 		else
 		{
-			return 1 + FfiConverterTypeCancellationSource.INSTANCE.AllocationSize((CancellationSource)value);
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeNoteSpendability.Read()", value));
 		}
 	}
 
-	public override void Write(CancellationSource? value, BigEndianStream stream)
+	public override int AllocationSize(NoteSpendability value)
 	{
-		if (value == null)
-		{
-			stream.WriteByte(0);
-		}
-		else
-		{
-			stream.WriteByte(1);
-			FfiConverterTypeCancellationSource.INSTANCE.Write((CancellationSource)value, stream);
-		}
+		return 4;
+	}
+
+	public override void Write(NoteSpendability value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterOptionalTypeSyncUpdate : FfiConverterRustBuffer<SyncUpdate?>
+internal enum Pool : int
 {
-	public static FfiConverterOptionalTypeSyncUpdate INSTANCE = new FfiConverterOptionalTypeSyncUpdate();
+	Transparent,
+	Sapling,
+	Orchard,
+}
 
-	public override SyncUpdate? Read(BigEndianStream stream)
-	{
-		if (stream.ReadByte() == 0)
-		{
-			return null;
-		}
-		return FfiConverterTypeSyncUpdate.INSTANCE.Read(stream);
-	}
+class FfiConverterTypePool : FfiConverterRustBuffer<Pool>
+{
+	public static FfiConverterTypePool INSTANCE = new FfiConverterTypePool();
 
-	public override int AllocationSize(SyncUpdate? value)
+	public override Pool Read(BigEndianStream stream)
 	{
-		if (value == null)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(Pool), value))
 		{
-			return 1;
+			return (Pool)value;
 		}
 		else
 		{
-			return 1 + FfiConverterTypeSyncUpdate.INSTANCE.AllocationSize((SyncUpdate)value);
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypePool.Read()", value));
 		}
 	}
 
-	public override void Write(SyncUpdate? value, BigEndianStream stream)
+	public override int AllocationSize(Pool value)
 	{
-		if (value == null)
-		{
-			stream.WriteByte(0);
-		}
-		else
-		{
-			stream.WriteByte(1);
-			FfiConverterTypeSyncUpdate.INSTANCE.Write((SyncUpdate)value, stream);
-		}
+		return 4;
+	}
+
+	public override void Write(Pool value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterSequenceTypeAccountInfo : FfiConverterRustBuffer<List<AccountInfo>>
+internal enum ScanRangePriority : int
 {
-	public static FfiConverterSequenceTypeAccountInfo INSTANCE = new FfiConverterSequenceTypeAccountInfo();
+	Ignored,
+	Scanned,
+	Historic,
+	OpenAdjacent,
+	FoundNote,
+	ChainTip,
+	Verify,
+}
 
-	public override List<AccountInfo> Read(BigEndianStream stream)
+class FfiConverterTypeScanRangePriority : FfiConverterRustBuffer<ScanRangePriority>
+{
+	public static FfiConverterTypeScanRangePriority INSTANCE = new FfiConverterTypeScanRangePriority();
+
+	public override ScanRangePriority Read(BigEndianStream stream)
 	{
-		var length = stream.ReadInt();
-		var result = new List<AccountInfo>(length);
-		for (int i = 0; i < length; i++)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(ScanRangePriority), value))
 		{
-			result.Add(FfiConverterTypeAccountInfo.INSTANCE.Read(stream));
+			return (ScanRangePriority)value;
 		}
-		return result;
-	}
-
-	public override int AllocationSize(List<AccountInfo> value)
-	{
-		var sizeForLength = 4;
-
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
+		else
 		{
-			return sizeForLength;
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeScanRangePriority.Read()", value));
 		}
-
-		var sizeForItems = value.Select(item => FfiConverterTypeAccountInfo.INSTANCE.AllocationSize(item)).Sum();
-		return sizeForLength + sizeForItems;
 	}
 
-	public override void Write(List<AccountInfo> value, BigEndianStream stream)
+	public override int AllocationSize(ScanRangePriority value)
 	{
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
-		{
-			stream.WriteInt(0);
-			return;
-		}
+		return 4;
+	}
 
-		stream.WriteInt(value.Count);
-		value.ForEach(item => FfiConverterTypeAccountInfo.INSTANCE.Write(item, stream));
+	public override void Write(ScanRangePriority value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterSequenceTypeSendTransactionResult : FfiConverterRustBuffer<List<SendTransactionResult>>
+internal enum SyncPhase : int
 {
-	public static FfiConverterSequenceTypeSendTransactionResult INSTANCE = new FfiConverterSequenceTypeSendTransactionResult();
+	SubtreeRoots,
+	Transparent,
+	Verify,
+	Scan,
+	Enhancement,
+	Mempool,
+}
 
-	public override List<SendTransactionResult> Read(BigEndianStream stream)
+class FfiConverterTypeSyncPhase : FfiConverterRustBuffer<SyncPhase>
+{
+	public static FfiConverterTypeSyncPhase INSTANCE = new FfiConverterTypeSyncPhase();
+
+	public override SyncPhase Read(BigEndianStream stream)
 	{
-		var length = stream.ReadInt();
-		var result = new List<SendTransactionResult>(length);
-		for (int i = 0; i < length; i++)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(SyncPhase), value))
 		{
-			result.Add(FfiConverterTypeSendTransactionResult.INSTANCE.Read(stream));
+			return (SyncPhase)value;
 		}
-		return result;
-	}
-
-	public override int AllocationSize(List<SendTransactionResult> value)
-	{
-		var sizeForLength = 4;
-
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
+		else
 		{
-			return sizeForLength;
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeSyncPhase.Read()", value));
 		}
-
-		var sizeForItems = value.Select(item => FfiConverterTypeSendTransactionResult.INSTANCE.AllocationSize(item)).Sum();
-		return sizeForLength + sizeForItems;
 	}
 
-	public override void Write(List<SendTransactionResult> value, BigEndianStream stream)
+	public override int AllocationSize(SyncPhase value)
 	{
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
-		{
-			stream.WriteInt(0);
-			return;
-		}
+		return 4;
+	}
 
-		stream.WriteInt(value.Count);
-		value.ForEach(item => FfiConverterTypeSendTransactionResult.INSTANCE.Write(item, stream));
+	public override void Write(SyncPhase value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterSequenceTypeTransaction : FfiConverterRustBuffer<List<Transaction>>
+internal enum WalletRepair : int
 {
-	public static FfiConverterSequenceTypeTransaction INSTANCE = new FfiConverterSequenceTypeTransaction();
+	RequeueEnhancements,
+	RemoveOrphanedNotes,
+	FillScanQueueGaps,
+	TruncateToSafeHeight,
+}
 
-	public override List<Transaction> Read(BigEndianStream stream)
+class FfiConverterTypeWalletRepair : FfiConverterRustBuffer<WalletRepair>
+{
+	public static FfiConverterTypeWalletRepair INSTANCE = new FfiConverterTypeWalletRepair();
+
+	public override WalletRepair Read(BigEndianStream stream)
 	{
-		var length = stream.ReadInt();
-		var result = new List<Transaction>(length);
-		for (int i = 0; i < length; i++)
+		var value = stream.ReadInt() - 1;
+		if (Enum.IsDefined(typeof(WalletRepair), value))
 		{
-			result.Add(FfiConverterTypeTransaction.INSTANCE.Read(stream));
+			return (WalletRepair)value;
 		}
-		return result;
-	}
-
-	public override int AllocationSize(List<Transaction> value)
-	{
-		var sizeForLength = 4;
-
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
+		else
 		{
-			return sizeForLength;
+			throw new InternalException(String.Format("invalid enum value '{0}' in FfiConverterTypeWalletRepair.Read()", value));
 		}
-
-		var sizeForItems = value.Select(item => FfiConverterTypeTransaction.INSTANCE.AllocationSize(item)).Sum();
-		return sizeForLength + sizeForItems;
 	}
 
-	public override void Write(List<Transaction> value, BigEndianStream stream)
+	public override int AllocationSize(WalletRepair value)
 	{
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
-		{
-			stream.WriteInt(0);
-			return;
-		}
+		return 4;
+	}
 
-		stream.WriteInt(value.Count);
-		value.ForEach(item => FfiConverterTypeTransaction.INSTANCE.Write(item, stream));
+	public override void Write(WalletRepair value, BigEndianStream stream)
+	{
+		stream.WriteInt((int)value + 1);
 	}
 }
 
-class FfiConverterSequenceTypeTransactionNote : FfiConverterRustBuffer<List<TransactionNote>>
+static class UniffiCallbackResponseCode
 {
-	public static FfiConverterSequenceTypeTransactionNote INSTANCE = new FfiConverterSequenceTypeTransactionNote();
+	public static int SUCCESS = 0;
+	public static int ERROR = 1;
+	public static int UNEXPECTED_ERROR = 2;
+}
 
-	public override List<TransactionNote> Read(BigEndianStream stream)
+class ConcurrentHandleMap<T>
+	where T : notnull
+{
+	Dictionary<ulong, T> leftMap = new Dictionary<ulong, T>();
+	Dictionary<T, ulong> rightMap = new Dictionary<T, ulong>();
+
+	Object lock_ = new Object();
+	ulong currentHandle = 0;
+
+	public ulong Insert(T obj)
 	{
-		var length = stream.ReadInt();
-		var result = new List<TransactionNote>(length);
-		for (int i = 0; i < length; i++)
+		lock (lock_)
 		{
-			result.Add(FfiConverterTypeTransactionNote.INSTANCE.Read(stream));
+			ulong existingHandle = 0;
+			if (rightMap.TryGetValue(obj, out existingHandle))
+			{
+				return existingHandle;
+			}
+			currentHandle += 1;
+			leftMap[currentHandle] = obj;
+			rightMap[obj] = currentHandle;
+			return currentHandle;
 		}
-		return result;
 	}
 
-	public override int AllocationSize(List<TransactionNote> value)
+	public bool TryGet(ulong handle, out T result)
 	{
-		var sizeForLength = 4;
-
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
-		{
-			return sizeForLength;
-		}
+		// Possible null reference assignment
+#pragma warning disable 8601
+		return leftMap.TryGetValue(handle, out result);
+#pragma warning restore 8601
+	}
 
-		var sizeForItems = value.Select(item => FfiConverterTypeTransactionNote.INSTANCE.AllocationSize(item)).Sum();
-		return sizeForLength + sizeForItems;
+	public bool Remove(ulong handle)
+	{
+		return Remove(handle, out T result);
 	}
 
-	public override void Write(List<TransactionNote> value, BigEndianStream stream)
+	public bool Remove(ulong handle, out T result)
 	{
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
+		lock (lock_)
 		{
-			stream.WriteInt(0);
-			return;
+			// Possible null reference assignment
+#pragma warning disable 8601
+			if (leftMap.TryGetValue(handle, out result))
+			{
+#pragma warning restore 8601
+				leftMap.Remove(handle);
+				rightMap.Remove(result);
+				return true;
+			}
+			else
+			{
+				return false;
+			}
 		}
-
-		stream.WriteInt(value.Count);
-		value.ForEach(item => FfiConverterTypeTransactionNote.INSTANCE.Write(item, stream));
 	}
 }
 
-class FfiConverterSequenceTypeTransactionSendDetail : FfiConverterRustBuffer<List<TransactionSendDetail>>
+[UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+internal delegate int ForeignCallback(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf);
+
+internal abstract class FfiConverterCallbackInterface<CallbackInterface> : FfiConverter<CallbackInterface, ulong>
+	where CallbackInterface : notnull
 {
-	public static FfiConverterSequenceTypeTransactionSendDetail INSTANCE = new FfiConverterSequenceTypeTransactionSendDetail();
+	ConcurrentHandleMap<CallbackInterface> handleMap = new ConcurrentHandleMap<CallbackInterface>();
 
-	public override List<TransactionSendDetail> Read(BigEndianStream stream)
+	// Registers the foreign callback with the Rust side.
+	// This method is generated for each callback interface.
+	public abstract void Register();
+
+	public RustBuffer Drop(ulong handle)
 	{
-		var length = stream.ReadInt();
-		var result = new List<TransactionSendDetail>(length);
-		for (int i = 0; i < length; i++)
+		handleMap.Remove(handle);
+		return new RustBuffer();
+	}
+
+	public override CallbackInterface Lift(ulong handle)
+	{
+		if (!handleMap.TryGet(handle, out CallbackInterface result))
 		{
-			result.Add(FfiConverterTypeTransactionSendDetail.INSTANCE.Read(stream));
+			throw new InternalException($"No callback in handlemap '{handle}'");
 		}
 		return result;
 	}
 
-	public override int AllocationSize(List<TransactionSendDetail> value)
+	public override CallbackInterface Read(BigEndianStream stream)
 	{
-		var sizeForLength = 4;
-
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
-		{
-			return sizeForLength;
-		}
+		return Lift(stream.ReadULong());
+	}
 
-		var sizeForItems = value.Select(item => FfiConverterTypeTransactionSendDetail.INSTANCE.AllocationSize(item)).Sum();
-		return sizeForLength + sizeForItems;
+	public override ulong Lower(CallbackInterface value)
+	{
+		return handleMap.Insert(value);
 	}
 
-	public override void Write(List<TransactionSendDetail> value, BigEndianStream stream)
+	public override int AllocationSize(CallbackInterface value)
 	{
-		// details/1-empty-list-as-default-method-parameter.md
-		if (value == null)
-		{
-			stream.WriteInt(0);
-			return;
-		}
+		return 8;
+	}
 
-		stream.WriteInt(value.Count);
-		value.ForEach(item => FfiConverterTypeTransactionSendDetail.INSTANCE.Write(item, stream));
+	public override void Write(CallbackInterface value, BigEndianStream stream)
+	{
+		stream.WriteULong(Lower(value));
 	}
 }
 
-class FfiConverterSequenceTypeTransparentNote : FfiConverterRustBuffer<List<TransparentNote>>
+internal interface CancellationSource
 {
-	public static FfiConverterSequenceTypeTransparentNote INSTANCE = new FfiConverterSequenceTypeTransparentNote();
+	void SetCancellationId(uint @id);
+}
 
-	public override List<TransparentNote> Read(BigEndianStream stream)
-	{
-		var length = stream.ReadInt();
-		var result = new List<TransparentNote>(length);
-		for (int i = 0; i < length; i++)
-		{
-			result.Add(FfiConverterTypeTransparentNote.INSTANCE.Read(stream));
-		}
-		return result;
-	}
+// The ForeignCallback that is passed to Rust.
+class ForeignCallbackTypeCancellationSource
+{
+	public static readonly ForeignCallback INSTANCE = INSTANCE_FUNC;
+
+#if IOS
+	[ObjCRuntime.MonoPInvokeCallback(typeof(ForeignCallback))]
+#endif
+	private static int INSTANCE_FUNC(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf)
+	{
+		var cb = FfiConverterTypeCancellationSource.INSTANCE.Lift(handle);
+		switch (method)
+		{
+			case 0:
+			{
+				// 0 means Rust is done with the callback, and the callback
+				// can be dropped by the foreign language.
+				FfiConverterTypeCancellationSource.INSTANCE.Drop(handle);
+				// No return value.
+				// See docs of ForeignCallback in `uniffi/src/ffi/foreigncallbacks.rs`
+				return 0;
+			}
+
+			case 1:
+			{
+				try
+				{
+					outBuf = InvokeSetCancellationId(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			default:
+			{
+				// This should never happen, because an out of bounds method index won't
+				// ever be used. Once we can catch errors, we should return an InternalException.
+				// https://github.com/mozilla/uniffi-rs/issues/351
+				return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+			}
+		}
+	}
+
+	static RustBuffer InvokeSetCancellationId(CancellationSource callback, BigEndianStream stream)
+	{
+		callback.SetCancellationId(FfiConverterUInt32.INSTANCE.Read(stream));
+		return new RustBuffer();
+	}
+}
+
+// The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
+class FfiConverterTypeCancellationSource : FfiConverterCallbackInterface<CancellationSource>
+{
+	public static FfiConverterTypeCancellationSource INSTANCE = new FfiConverterTypeCancellationSource();
+
+	public override void Register()
+	{
+		_UniffiHelpers.RustCall(
+			(ref RustCallStatus status) =>
+			{
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_init_callback_cancellationsource(
+					ForeignCallbackTypeCancellationSource.INSTANCE,
+					ref status
+				);
+			}
+		);
+	}
+}
+
+internal interface LogSink
+{
+	void Log(LogLevel @level, String @target, String @message);
+}
+
+// The ForeignCallback that is passed to Rust.
+class ForeignCallbackTypeLogSink
+{
+	public static readonly ForeignCallback INSTANCE = INSTANCE_FUNC;
+
+#if IOS
+	[ObjCRuntime.MonoPInvokeCallback(typeof(ForeignCallback))]
+#endif
+	private static int INSTANCE_FUNC(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf)
+	{
+		var cb = FfiConverterTypeLogSink.INSTANCE.Lift(handle);
+		switch (method)
+		{
+			case 0:
+			{
+				// 0 means Rust is done with the callback, and the callback
+				// can be dropped by the foreign language.
+				FfiConverterTypeLogSink.INSTANCE.Drop(handle);
+				// No return value.
+				// See docs of ForeignCallback in `uniffi/src/ffi/foreigncallbacks.rs`
+				return 0;
+			}
+
+			case 1:
+			{
+				try
+				{
+					outBuf = InvokeLog(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			default:
+			{
+				// This should never happen, because an out of bounds method index won't
+				// ever be used. Once we can catch errors, we should return an InternalException.
+				// https://github.com/mozilla/uniffi-rs/issues/351
+				return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+			}
+		}
+	}
+
+	static RustBuffer InvokeLog(LogSink callback, BigEndianStream stream)
+	{
+		callback.Log(
+			FfiConverterTypeLogLevel.INSTANCE.Read(stream),
+			FfiConverterString.INSTANCE.Read(stream),
+			FfiConverterString.INSTANCE.Read(stream)
+		);
+		return new RustBuffer();
+	}
+}
+
+// The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
+class FfiConverterTypeLogSink : FfiConverterCallbackInterface<LogSink>
+{
+	public static FfiConverterTypeLogSink INSTANCE = new FfiConverterTypeLogSink();
+
+	public override void Register()
+	{
+		_UniffiHelpers.RustCall(
+			(ref RustCallStatus status) =>
+			{
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_init_callback_logsink(ForeignCallbackTypeLogSink.INSTANCE, ref status);
+			}
+		);
+	}
+}
+
+internal interface ParameterDownloadProgress
+{
+	void Report(ulong @bytesDownloaded, ulong @totalBytes);
+}
+
+// The ForeignCallback that is passed to Rust.
+class ForeignCallbackTypeParameterDownloadProgress
+{
+	public static readonly ForeignCallback INSTANCE = INSTANCE_FUNC;
+
+#if IOS
+	[ObjCRuntime.MonoPInvokeCallback(typeof(ForeignCallback))]
+#endif
+	private static int INSTANCE_FUNC(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf)
+	{
+		var cb = FfiConverterTypeParameterDownloadProgress.INSTANCE.Lift(handle);
+		switch (method)
+		{
+			case 0:
+			{
+				// 0 means Rust is done with the callback, and the callback
+				// can be dropped by the foreign language.
+				FfiConverterTypeParameterDownloadProgress.INSTANCE.Drop(handle);
+				// No return value.
+				// See docs of ForeignCallback in `uniffi/src/ffi/foreigncallbacks.rs`
+				return 0;
+			}
+
+			case 1:
+			{
+				try
+				{
+					outBuf = InvokeReport(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			default:
+			{
+				// This should never happen, because an out of bounds method index won't
+				// ever be used. Once we can catch errors, we should return an InternalException.
+				// https://github.com/mozilla/uniffi-rs/issues/351
+				return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+			}
+		}
+	}
+
+	static RustBuffer InvokeReport(ParameterDownloadProgress callback, BigEndianStream stream)
+	{
+		callback.Report(FfiConverterUInt64.INSTANCE.Read(stream), FfiConverterUInt64.INSTANCE.Read(stream));
+		return new RustBuffer();
+	}
+}
+
+// The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
+class FfiConverterTypeParameterDownloadProgress : FfiConverterCallbackInterface<ParameterDownloadProgress>
+{
+	public static FfiConverterTypeParameterDownloadProgress INSTANCE = new FfiConverterTypeParameterDownloadProgress();
+
+	public override void Register()
+	{
+		_UniffiHelpers.RustCall(
+			(ref RustCallStatus status) =>
+			{
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_init_callback_parameterdownloadprogress(
+					ForeignCallbackTypeParameterDownloadProgress.INSTANCE,
+					ref status
+				);
+			}
+		);
+	}
+}
+
+internal interface SyncUpdate
+{
+	void UpdateStatus(SyncUpdateData @data);
+	void ReportTransactions(List<Transaction> @transactions);
+	/// <summary>
+	/// Reports that the chain was reorganized at `from_height`, and that the wallet rewound to `to_height`.
+	/// Confirmations and transactions from `to_height` onward may have changed.
+	/// </summary>
+	void ReorgDetected(uint @fromHeight, uint @toHeight);
+	/// <summary>
+	/// Reports that a request to the server failed and will be retried (starting from 1) after `delay_ms`.
+	/// </summary>
+	void OnRetry(uint @retry, ulong @delayMs, String @error);
+}
+
+// The ForeignCallback that is passed to Rust.
+class ForeignCallbackTypeSyncUpdate
+{
+	public static readonly ForeignCallback INSTANCE = INSTANCE_FUNC;
+
+#if IOS
+	[ObjCRuntime.MonoPInvokeCallback(typeof(ForeignCallback))]
+#endif
+	private static int INSTANCE_FUNC(ulong handle, uint method, IntPtr argsData, int argsLength, ref RustBuffer outBuf)
+	{
+		var cb = FfiConverterTypeSyncUpdate.INSTANCE.Lift(handle);
+		switch (method)
+		{
+			case 0:
+			{
+				// 0 means Rust is done with the callback, and the callback
+				// can be dropped by the foreign language.
+				FfiConverterTypeSyncUpdate.INSTANCE.Drop(handle);
+				// No return value.
+				// See docs of ForeignCallback in `uniffi/src/ffi/foreigncallbacks.rs`
+				return 0;
+			}
+
+			case 1:
+			{
+				try
+				{
+					outBuf = InvokeUpdateStatus(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			case 2:
+			{
+				try
+				{
+					outBuf = InvokeReportTransactions(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			case 3:
+			{
+				try
+				{
+					outBuf = InvokeReorgDetected(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			case 4:
+			{
+				try
+				{
+					outBuf = InvokeOnRetry(cb, RustBuffer.MemoryStream(argsData, argsLength));
+					return UniffiCallbackResponseCode.SUCCESS;
+				}
+				catch (Exception e)
+				{
+					// Unexpected error
+					try
+					{
+						// Try to serialize the error into a string
+						outBuf = FfiConverterString.INSTANCE.Lower(e.Message);
+					}
+					catch
+					{
+						// If that fails, then it's time to give up and just return
+					}
+					return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+				}
+			}
+
+			default:
+			{
+				// This should never happen, because an out of bounds method index won't
+				// ever be used. Once we can catch errors, we should return an InternalException.
+				// https://github.com/mozilla/uniffi-rs/issues/351
+				return UniffiCallbackResponseCode.UNEXPECTED_ERROR;
+			}
+		}
+	}
+
+	static RustBuffer InvokeUpdateStatus(SyncUpdate callback, BigEndianStream stream)
+	{
+		callback.UpdateStatus(FfiConverterTypeSyncUpdateData.INSTANCE.Read(stream));
+		return new RustBuffer();
+	}
+
+	static RustBuffer InvokeReportTransactions(SyncUpdate callback, BigEndianStream stream)
+	{
+		callback.ReportTransactions(FfiConverterSequenceTypeTransaction.INSTANCE.Read(stream));
+		return new RustBuffer();
+	}
+
+	static RustBuffer InvokeReorgDetected(SyncUpdate callback, BigEndianStream stream)
+	{
+		callback.ReorgDetected(FfiConverterUInt32.INSTANCE.Read(stream), FfiConverterUInt32.INSTANCE.Read(stream));
+		return new RustBuffer();
+	}
+
+	static RustBuffer InvokeOnRetry(SyncUpdate callback, BigEndianStream stream)
+	{
+		callback.OnRetry(
+			FfiConverterUInt32.INSTANCE.Read(stream),
+			FfiConverterUInt64.INSTANCE.Read(stream),
+			FfiConverterString.INSTANCE.Read(stream)
+		);
+		return new RustBuffer();
+	}
+}
+
+// The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
+class FfiConverterTypeSyncUpdate : FfiConverterCallbackInterface<SyncUpdate>
+{
+	public static FfiConverterTypeSyncUpdate INSTANCE = new FfiConverterTypeSyncUpdate();
+
+	public override void Register()
+	{
+		_UniffiHelpers.RustCall(
+			(ref RustCallStatus status) =>
+			{
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_init_callback_syncupdate(ForeignCallbackTypeSyncUpdate.INSTANCE, ref status);
+			}
+		);
+	}
+}
+
+class FfiConverterOptionalUInt32 : FfiConverterRustBuffer<uint?>
+{
+	public static FfiConverterOptionalUInt32 INSTANCE = new FfiConverterOptionalUInt32();
+
+	public override uint? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterUInt32.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(uint? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterUInt32.INSTANCE.AllocationSize((uint)value);
+		}
+	}
+
+	public override void Write(uint? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterUInt32.INSTANCE.Write((uint)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalUInt64 : FfiConverterRustBuffer<ulong?>
+{
+	public static FfiConverterOptionalUInt64 INSTANCE = new FfiConverterOptionalUInt64();
+
+	public override ulong? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterUInt64.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(ulong? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterUInt64.INSTANCE.AllocationSize((ulong)value);
+		}
+	}
+
+	public override void Write(ulong? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterUInt64.INSTANCE.Write((ulong)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalString : FfiConverterRustBuffer<String?>
+{
+	public static FfiConverterOptionalString INSTANCE = new FfiConverterOptionalString();
+
+	public override String? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterString.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(String? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterString.INSTANCE.AllocationSize((String)value);
+		}
+	}
+
+	public override void Write(String? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterString.INSTANCE.Write((String)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalByteArray : FfiConverterRustBuffer<byte[]?>
+{
+	public static FfiConverterOptionalByteArray INSTANCE = new FfiConverterOptionalByteArray();
+
+	public override byte[]? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterByteArray.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(byte[]? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterByteArray.INSTANCE.AllocationSize((byte[])value);
+		}
+	}
+
+	public override void Write(byte[]? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterByteArray.INSTANCE.Write((byte[])value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTimestamp : FfiConverterRustBuffer<DateTime?>
+{
+	public static FfiConverterOptionalTimestamp INSTANCE = new FfiConverterOptionalTimestamp();
+
+	public override DateTime? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTimestamp.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(DateTime? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTimestamp.INSTANCE.AllocationSize((DateTime)value);
+		}
+	}
+
+	public override void Write(DateTime? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTimestamp.INSTANCE.Write((DateTime)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeBatchOptions : FfiConverterRustBuffer<BatchOptions?>
+{
+	public static FfiConverterOptionalTypeBatchOptions INSTANCE = new FfiConverterOptionalTypeBatchOptions();
+
+	public override BatchOptions? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeBatchOptions.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(BatchOptions? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeBatchOptions.INSTANCE.AllocationSize((BatchOptions)value);
+		}
+	}
+
+	public override void Write(BatchOptions? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeBatchOptions.INSTANCE.Write((BatchOptions)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeBatchResult : FfiConverterRustBuffer<BatchResult?>
+{
+	public static FfiConverterOptionalTypeBatchResult INSTANCE = new FfiConverterOptionalTypeBatchResult();
+
+	public override BatchResult? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeBatchResult.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(BatchResult? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeBatchResult.INSTANCE.AllocationSize((BatchResult)value);
+		}
+	}
+
+	public override void Write(BatchResult? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeBatchResult.INSTANCE.Write((BatchResult)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeCoinSelection : FfiConverterRustBuffer<CoinSelection?>
+{
+	public static FfiConverterOptionalTypeCoinSelection INSTANCE = new FfiConverterOptionalTypeCoinSelection();
+
+	public override CoinSelection? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeCoinSelection.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(CoinSelection? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeCoinSelection.INSTANCE.AllocationSize((CoinSelection)value);
+		}
+	}
+
+	public override void Write(CoinSelection? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeCoinSelection.INSTANCE.Write((CoinSelection)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeConnectionOptions : FfiConverterRustBuffer<ConnectionOptions?>
+{
+	public static FfiConverterOptionalTypeConnectionOptions INSTANCE = new FfiConverterOptionalTypeConnectionOptions();
+
+	public override ConnectionOptions? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeConnectionOptions.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(ConnectionOptions? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeConnectionOptions.INSTANCE.AllocationSize((ConnectionOptions)value);
+		}
+	}
+
+	public override void Write(ConnectionOptions? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeConnectionOptions.INSTANCE.Write((ConnectionOptions)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeHeightRange : FfiConverterRustBuffer<HeightRange?>
+{
+	public static FfiConverterOptionalTypeHeightRange INSTANCE = new FfiConverterOptionalTypeHeightRange();
+
+	public override HeightRange? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeHeightRange.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(HeightRange? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeHeightRange.INSTANCE.AllocationSize((HeightRange)value);
+		}
+	}
+
+	public override void Write(HeightRange? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeHeightRange.INSTANCE.Write((HeightRange)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeParameterPaths : FfiConverterRustBuffer<ParameterPaths?>
+{
+	public static FfiConverterOptionalTypeParameterPaths INSTANCE = new FfiConverterOptionalTypeParameterPaths();
+
+	public override ParameterPaths? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeParameterPaths.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(ParameterPaths? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeParameterPaths.INSTANCE.AllocationSize((ParameterPaths)value);
+		}
+	}
+
+	public override void Write(ParameterPaths? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeParameterPaths.INSTANCE.Write((ParameterPaths)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeRetryPolicy : FfiConverterRustBuffer<RetryPolicy?>
+{
+	public static FfiConverterOptionalTypeRetryPolicy INSTANCE = new FfiConverterOptionalTypeRetryPolicy();
+
+	public override RetryPolicy? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeRetryPolicy.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(RetryPolicy? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeRetryPolicy.INSTANCE.AllocationSize((RetryPolicy)value);
+		}
+	}
+
+	public override void Write(RetryPolicy? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeRetryPolicy.INSTANCE.Write((RetryPolicy)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeSyncOptions : FfiConverterRustBuffer<SyncOptions?>
+{
+	public static FfiConverterOptionalTypeSyncOptions INSTANCE = new FfiConverterOptionalTypeSyncOptions();
+
+	public override SyncOptions? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeSyncOptions.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(SyncOptions? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeSyncOptions.INSTANCE.AllocationSize((SyncOptions)value);
+		}
+	}
+
+	public override void Write(SyncOptions? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeSyncOptions.INSTANCE.Write((SyncOptions)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeTorOptions : FfiConverterRustBuffer<TorOptions?>
+{
+	public static FfiConverterOptionalTypeTorOptions INSTANCE = new FfiConverterOptionalTypeTorOptions();
+
+	public override TorOptions? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeTorOptions.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(TorOptions? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeTorOptions.INSTANCE.AllocationSize((TorOptions)value);
+		}
+	}
+
+	public override void Write(TorOptions? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeTorOptions.INSTANCE.Write((TorOptions)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeScanRangePriority : FfiConverterRustBuffer<ScanRangePriority?>
+{
+	public static FfiConverterOptionalTypeScanRangePriority INSTANCE = new FfiConverterOptionalTypeScanRangePriority();
+
+	public override ScanRangePriority? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeScanRangePriority.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(ScanRangePriority? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeScanRangePriority.INSTANCE.AllocationSize((ScanRangePriority)value);
+		}
+	}
+
+	public override void Write(ScanRangePriority? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeScanRangePriority.INSTANCE.Write((ScanRangePriority)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeCancellationSource : FfiConverterRustBuffer<CancellationSource?>
+{
+	public static FfiConverterOptionalTypeCancellationSource INSTANCE = new FfiConverterOptionalTypeCancellationSource();
+
+	public override CancellationSource? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeCancellationSource.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(CancellationSource? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeCancellationSource.INSTANCE.AllocationSize((CancellationSource)value);
+		}
+	}
+
+	public override void Write(CancellationSource? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeCancellationSource.INSTANCE.Write((CancellationSource)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeLogSink : FfiConverterRustBuffer<LogSink?>
+{
+	public static FfiConverterOptionalTypeLogSink INSTANCE = new FfiConverterOptionalTypeLogSink();
+
+	public override LogSink? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeLogSink.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(LogSink? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeLogSink.INSTANCE.AllocationSize((LogSink)value);
+		}
+	}
+
+	public override void Write(LogSink? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeLogSink.INSTANCE.Write((LogSink)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeParameterDownloadProgress : FfiConverterRustBuffer<ParameterDownloadProgress?>
+{
+	public static FfiConverterOptionalTypeParameterDownloadProgress INSTANCE = new FfiConverterOptionalTypeParameterDownloadProgress();
+
+	public override ParameterDownloadProgress? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeParameterDownloadProgress.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(ParameterDownloadProgress? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeParameterDownloadProgress.INSTANCE.AllocationSize((ParameterDownloadProgress)value);
+		}
+	}
+
+	public override void Write(ParameterDownloadProgress? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeParameterDownloadProgress.INSTANCE.Write((ParameterDownloadProgress)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalTypeSyncUpdate : FfiConverterRustBuffer<SyncUpdate?>
+{
+	public static FfiConverterOptionalTypeSyncUpdate INSTANCE = new FfiConverterOptionalTypeSyncUpdate();
+
+	public override SyncUpdate? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterTypeSyncUpdate.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(SyncUpdate? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterTypeSyncUpdate.INSTANCE.AllocationSize((SyncUpdate)value);
+		}
+	}
+
+	public override void Write(SyncUpdate? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterTypeSyncUpdate.INSTANCE.Write((SyncUpdate)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalSequenceTypeNoteId : FfiConverterRustBuffer<List<NoteId>?>
+{
+	public static FfiConverterOptionalSequenceTypeNoteId INSTANCE = new FfiConverterOptionalSequenceTypeNoteId();
+
+	public override List<NoteId>? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterSequenceTypeNoteId.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(List<NoteId>? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterSequenceTypeNoteId.INSTANCE.AllocationSize((List<NoteId>)value);
+		}
+	}
+
+	public override void Write(List<NoteId>? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterSequenceTypeNoteId.INSTANCE.Write((List<NoteId>)value, stream);
+		}
+	}
+}
+
+class FfiConverterOptionalSequenceTypePool : FfiConverterRustBuffer<List<Pool>?>
+{
+	public static FfiConverterOptionalSequenceTypePool INSTANCE = new FfiConverterOptionalSequenceTypePool();
+
+	public override List<Pool>? Read(BigEndianStream stream)
+	{
+		if (stream.ReadByte() == 0)
+		{
+			return null;
+		}
+		return FfiConverterSequenceTypePool.INSTANCE.Read(stream);
+	}
+
+	public override int AllocationSize(List<Pool>? value)
+	{
+		if (value == null)
+		{
+			return 1;
+		}
+		else
+		{
+			return 1 + FfiConverterSequenceTypePool.INSTANCE.AllocationSize((List<Pool>)value);
+		}
+	}
+
+	public override void Write(List<Pool>? value, BigEndianStream stream)
+	{
+		if (value == null)
+		{
+			stream.WriteByte(0);
+		}
+		else
+		{
+			stream.WriteByte(1);
+			FfiConverterSequenceTypePool.INSTANCE.Write((List<Pool>)value, stream);
+		}
+	}
+}
+
+class FfiConverterSequenceUInt32 : FfiConverterRustBuffer<List<uint>>
+{
+	public static FfiConverterSequenceUInt32 INSTANCE = new FfiConverterSequenceUInt32();
+
+	public override List<uint> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<uint>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterUInt32.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<uint> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterUInt32.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<uint> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterUInt32.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceString : FfiConverterRustBuffer<List<String>>
+{
+	public static FfiConverterSequenceString INSTANCE = new FfiConverterSequenceString();
+
+	public override List<String> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<String>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterString.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<String> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterString.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<String> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterString.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceByteArray : FfiConverterRustBuffer<List<byte[]>>
+{
+	public static FfiConverterSequenceByteArray INSTANCE = new FfiConverterSequenceByteArray();
+
+	public override List<byte[]> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<byte[]>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterByteArray.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<byte[]> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterByteArray.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<byte[]> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterByteArray.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeAccountAddress : FfiConverterRustBuffer<List<AccountAddress>>
+{
+	public static FfiConverterSequenceTypeAccountAddress INSTANCE = new FfiConverterSequenceTypeAccountAddress();
+
+	public override List<AccountAddress> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<AccountAddress>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeAccountAddress.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<AccountAddress> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeAccountAddress.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<AccountAddress> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeAccountAddress.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeAccountInfo : FfiConverterRustBuffer<List<AccountInfo>>
+{
+	public static FfiConverterSequenceTypeAccountInfo INSTANCE = new FfiConverterSequenceTypeAccountInfo();
+
+	public override List<AccountInfo> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<AccountInfo>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeAccountInfo.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<AccountInfo> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeAccountInfo.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<AccountInfo> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeAccountInfo.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeAddressBookEntry : FfiConverterRustBuffer<List<AddressBookEntry>>
+{
+	public static FfiConverterSequenceTypeAddressBookEntry INSTANCE = new FfiConverterSequenceTypeAddressBookEntry();
+
+	public override List<AddressBookEntry> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<AddressBookEntry>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeAddressBookEntry.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<AddressBookEntry> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeAddressBookEntry.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<AddressBookEntry> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeAddressBookEntry.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeBatchPayment : FfiConverterRustBuffer<List<BatchPayment>>
+{
+	public static FfiConverterSequenceTypeBatchPayment INSTANCE = new FfiConverterSequenceTypeBatchPayment();
+
+	public override List<BatchPayment> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<BatchPayment>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeBatchPayment.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<BatchPayment> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeBatchPayment.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<BatchPayment> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeBatchPayment.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeDiscoveredAccount : FfiConverterRustBuffer<List<DiscoveredAccount>>
+{
+	public static FfiConverterSequenceTypeDiscoveredAccount INSTANCE = new FfiConverterSequenceTypeDiscoveredAccount();
+
+	public override List<DiscoveredAccount> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<DiscoveredAccount>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeDiscoveredAccount.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<DiscoveredAccount> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeDiscoveredAccount.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<DiscoveredAccount> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeDiscoveredAccount.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeHeightRange : FfiConverterRustBuffer<List<HeightRange>>
+{
+	public static FfiConverterSequenceTypeHeightRange INSTANCE = new FfiConverterSequenceTypeHeightRange();
+
+	public override List<HeightRange> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<HeightRange>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeHeightRange.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<HeightRange> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeHeightRange.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<HeightRange> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeHeightRange.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeNoteId : FfiConverterRustBuffer<List<NoteId>>
+{
+	public static FfiConverterSequenceTypeNoteId INSTANCE = new FfiConverterSequenceTypeNoteId();
+
+	public override List<NoteId> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<NoteId>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeNoteId.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<NoteId> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeNoteId.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<NoteId> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeNoteId.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeSendTransactionResult : FfiConverterRustBuffer<List<SendTransactionResult>>
+{
+	public static FfiConverterSequenceTypeSendTransactionResult INSTANCE = new FfiConverterSequenceTypeSendTransactionResult();
+
+	public override List<SendTransactionResult> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<SendTransactionResult>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeSendTransactionResult.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<SendTransactionResult> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeSendTransactionResult.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<SendTransactionResult> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeSendTransactionResult.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeServerStatus : FfiConverterRustBuffer<List<ServerStatus>>
+{
+	public static FfiConverterSequenceTypeServerStatus INSTANCE = new FfiConverterSequenceTypeServerStatus();
+
+	public override List<ServerStatus> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<ServerStatus>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeServerStatus.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<ServerStatus> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeServerStatus.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<ServerStatus> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeServerStatus.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeTransaction : FfiConverterRustBuffer<List<Transaction>>
+{
+	public static FfiConverterSequenceTypeTransaction INSTANCE = new FfiConverterSequenceTypeTransaction();
+
+	public override List<Transaction> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<Transaction>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeTransaction.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<Transaction> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeTransaction.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<Transaction> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeTransaction.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeTransactionNote : FfiConverterRustBuffer<List<TransactionNote>>
+{
+	public static FfiConverterSequenceTypeTransactionNote INSTANCE = new FfiConverterSequenceTypeTransactionNote();
+
+	public override List<TransactionNote> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<TransactionNote>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeTransactionNote.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<TransactionNote> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeTransactionNote.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<TransactionNote> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeTransactionNote.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeTransactionSendDetail : FfiConverterRustBuffer<List<TransactionSendDetail>>
+{
+	public static FfiConverterSequenceTypeTransactionSendDetail INSTANCE = new FfiConverterSequenceTypeTransactionSendDetail();
+
+	public override List<TransactionSendDetail> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<TransactionSendDetail>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeTransactionSendDetail.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<TransactionSendDetail> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeTransactionSendDetail.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<TransactionSendDetail> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeTransactionSendDetail.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeTransparentNote : FfiConverterRustBuffer<List<TransparentNote>>
+{
+	public static FfiConverterSequenceTypeTransparentNote INSTANCE = new FfiConverterSequenceTypeTransparentNote();
+
+	public override List<TransparentNote> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<TransparentNote>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeTransparentNote.INSTANCE.Read(stream));
+		}
+		return result;
+	}
 
 	public override int AllocationSize(List<TransparentNote> value)
 	{
@@ -2811,6 +5813,184 @@ class FfiConverterSequenceTypeTransparentNote : FfiConverterRustBuffer<List<Tran
 		value.ForEach(item => FfiConverterTypeTransparentNote.INSTANCE.Write(item, stream));
 	}
 }
+
+class FfiConverterSequenceTypeUnspentNote : FfiConverterRustBuffer<List<UnspentNote>>
+{
+	public static FfiConverterSequenceTypeUnspentNote INSTANCE = new FfiConverterSequenceTypeUnspentNote();
+
+	public override List<UnspentNote> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<UnspentNote>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeUnspentNote.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<UnspentNote> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeUnspentNote.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<UnspentNote> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeUnspentNote.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypePool : FfiConverterRustBuffer<List<Pool>>
+{
+	public static FfiConverterSequenceTypePool INSTANCE = new FfiConverterSequenceTypePool();
+
+	public override List<Pool> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<Pool>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypePool.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<Pool> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypePool.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<Pool> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypePool.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterSequenceTypeWalletRepair : FfiConverterRustBuffer<List<WalletRepair>>
+{
+	public static FfiConverterSequenceTypeWalletRepair INSTANCE = new FfiConverterSequenceTypeWalletRepair();
+
+	public override List<WalletRepair> Read(BigEndianStream stream)
+	{
+		var length = stream.ReadInt();
+		var result = new List<WalletRepair>(length);
+		for (int i = 0; i < length; i++)
+		{
+			result.Add(FfiConverterTypeWalletRepair.INSTANCE.Read(stream));
+		}
+		return result;
+	}
+
+	public override int AllocationSize(List<WalletRepair> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterTypeWalletRepair.INSTANCE.AllocationSize(item)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(List<WalletRepair> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		value.ForEach(item => FfiConverterTypeWalletRepair.INSTANCE.Write(item, stream));
+	}
+}
+
+class FfiConverterDictionaryStringString : FfiConverterRustBuffer<Dictionary<String, String>>
+{
+	public static FfiConverterDictionaryStringString INSTANCE = new FfiConverterDictionaryStringString();
+
+	public override Dictionary<String, String> Read(BigEndianStream stream)
+	{
+		var result = new Dictionary<String, String>();
+		var len = stream.ReadInt();
+		for (int i = 0; i < len; i++)
+		{
+			var key = FfiConverterString.INSTANCE.Read(stream);
+			var value = FfiConverterString.INSTANCE.Read(stream);
+			result[key] = value;
+		}
+		return result;
+	}
+
+	public override int AllocationSize(Dictionary<String, String> value)
+	{
+		var sizeForLength = 4;
+
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			return sizeForLength;
+		}
+
+		var sizeForItems = value.Select(item => FfiConverterString.INSTANCE.AllocationSize(item.Key) + FfiConverterString.INSTANCE.AllocationSize(item.Value)).Sum();
+		return sizeForLength + sizeForItems;
+	}
+
+	public override void Write(Dictionary<String, String> value, BigEndianStream stream)
+	{
+		// details/1-empty-list-as-default-method-parameter.md
+		if (value == null)
+		{
+			stream.WriteInt(0);
+			return;
+		}
+
+		stream.WriteInt(value.Count);
+		foreach (var item in value)
+		{
+			FfiConverterString.INSTANCE.Write(item.Key, stream);
+			FfiConverterString.INSTANCE.Write(item.Value, stream);
+		}
+	}
+}
 #pragma warning restore 8625
 internal static class LightWalletMethods
 {
@@ -2841,17 +6021,317 @@ internal static class LightWalletMethods
 		);
 	}
 
+	/// <summary>
+	/// Gets the account's address at the given diversifier index.
+	/// When `receiver_types` is specified, the address includes exactly those receivers.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static String AddDiversifier(DbInit @config, uint @accountId, byte[] @diversifierIndex, List<Pool>? @receiverTypes)
+	{
+		return FfiConverterString.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_add_diversifier(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						FfiConverterByteArray.INSTANCE.Lower(@diversifierIndex),
+						FfiConverterOptionalSequenceTypePool.INSTANCE.Lower(@receiverTypes),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static void Cancel(uint @id)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_cancel(FfiConverterUInt32.INSTANCE.Lower(@id), ref _status)
+		);
+	}
+
+	/// <summary>
+	/// Checks the wallet database for inconsistencies that can leave it stuck.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static WalletCheckReport CheckWallet(DbInit @config)
+	{
+		return FfiConverterTypeWalletCheckReport.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_check_wallet(FfiConverterTypeDbInit.INSTANCE.Lower(@config), ref _status)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static bool DisconnectServer(String @uri)
+	{
+		return FfiConverterBoolean.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_disconnect_server(FfiConverterString.INSTANCE.Lower(@uri), ref _status)
+			)
+		);
+	}
+
+	/// <summary>
+	/// Adds every account derived from the seed that has history on the chain,
+	/// probing ZIP-32 account indices until `max_gap` consecutive accounts are found unused.
+	/// `birthday_height` is the earliest height the seed may have been used at, which bounds the scan.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static List<DiscoveredAccount> DiscoverAccounts(
+		DbInit @config,
+		String @uri,
+		byte[] @seed,
+		uint @maxGap,
+		uint? @birthdayHeight,
+		CancellationSource? @cancellation
+	)
+	{
+		return FfiConverterSequenceTypeDiscoveredAccount.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_discover_accounts(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterString.INSTANCE.Lower(@uri),
+						FfiConverterByteArray.INSTANCE.Lower(@seed),
+						FfiConverterUInt32.INSTANCE.Lower(@maxGap),
+						FfiConverterOptionalUInt32.INSTANCE.Lower(@birthdayHeight),
+						FfiConverterOptionalTypeCancellationSource.INSTANCE.Lower(@cancellation),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <summary>
+	/// Writes the entire wallet to a single file encrypted with `passphrase`.
+	/// This is safe to call while the wallet is syncing.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void ExportBackup(DbInit @config, String @path, String @passphrase)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_export_backup(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterString.INSTANCE.Lower(@path),
+					FfiConverterString.INSTANCE.Lower(@passphrase),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Finds the account that was derived from the given seed at the given ZIP-32 account index.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static uint? FindAccountBySeed(DbInit @config, byte[] @seed, uint @accountIndex)
+	{
+		return FfiConverterOptionalUInt32.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_find_account_by_seed(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterByteArray.INSTANCE.Lower(@seed),
+						FfiConverterUInt32.INSTANCE.Lower(@accountIndex),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static List<AccountInfo> GetAccounts(DbInit @config)
+	{
+		return FfiConverterSequenceTypeAccountInfo.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_accounts(FfiConverterTypeDbInit.INSTANCE.Lower(@config), ref _status)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static List<AddressBookEntry> GetAddressBook(DbInit @config)
+	{
+		return FfiConverterSequenceTypeAddressBookEntry.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_address_book(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <summary>
+	/// Gets the progress of a batch started by `send_batch`.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static String AddDiversifier(DbInit @config, uint @accountId, byte[] @diversifierIndex)
+	public static BatchResult? GetBatch(DbInit @config, String @batchId)
+	{
+		return FfiConverterOptionalTypeBatchResult.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_batch(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterString.INSTANCE.Lower(@batchId),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <summary>
+	/// Gets the oldest birthday height for any account in the wallet.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static uint? GetBirthdayHeight(DbInit @config)
+	{
+		return FfiConverterOptionalUInt32.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_birthday_height(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static BirthdayHeights GetBirthdayHeights(DbInit @config, uint @accountId)
+	{
+		return FfiConverterTypeBirthdayHeights.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_birthday_heights(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static uint GetBlockHeight(String @uri, CancellationSource? @cancellation)
+	{
+		return FfiConverterUInt32.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_block_height(
+						FfiConverterString.INSTANCE.Lower(@uri),
+						FfiConverterOptionalTypeCancellationSource.INSTANCE.Lower(@cancellation),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static uint? GetSyncHeight(DbInit @config)
+	{
+		return FfiConverterOptionalUInt32.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_sync_height(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static List<Transaction> GetTransactions(DbInit @config, uint @accountId, uint @startingBlock)
+	{
+		return FfiConverterSequenceTypeTransaction.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_transactions(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						FfiConverterUInt32.INSTANCE.Lower(@startingBlock),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static List<TransparentNote> GetUnshieldedUtxos(DbInit @config, uint @accountId)
+	{
+		return FfiConverterSequenceTypeTransparentNote.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_unshielded_utxos(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static UserBalances GetUserBalances(DbInit @config, uint @accountId)
+	{
+		return FfiConverterTypeUserBalances.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_user_balances(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static uint ImportAccountUfvk(
+		DbInit @config,
+		String @uri,
+		String @ufvk,
+		bool @spendingKeyAvailable,
+		uint? @birthdayHeight,
+		CancellationSource? @cancellation
+	)
 	{
-		return FfiConverterString.INSTANCE.Lift(
+		return FfiConverterUInt32.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_add_diversifier(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_import_account_ufvk(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
-						FfiConverterUInt32.INSTANCE.Lower(@accountId),
-						FfiConverterByteArray.INSTANCE.Lower(@diversifierIndex),
+						FfiConverterString.INSTANCE.Lower(@uri),
+						FfiConverterString.INSTANCE.Lower(@ufvk),
+						FfiConverterBoolean.INSTANCE.Lower(@spendingKeyAvailable),
+						FfiConverterOptionalUInt32.INSTANCE.Lower(@birthdayHeight),
+						FfiConverterOptionalTypeCancellationSource.INSTANCE.Lower(@cancellation),
 						ref _status
 					)
 			)
@@ -2859,98 +6339,169 @@ internal static class LightWalletMethods
 	}
 
 	/// <exception cref="LightWalletException"></exception>
-	public static void Cancel(uint @id)
+	public static void Init(DbInit @config)
 	{
 		_UniffiHelpers.RustCallWithError(
 			FfiConverterTypeLightWalletException.INSTANCE,
 			(ref RustCallStatus _status) =>
-				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_cancel(FfiConverterUInt32.INSTANCE.Lower(@id), ref _status)
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_init(FfiConverterTypeDbInit.INSTANCE.Lower(@config), ref _status)
 		);
 	}
 
+	/// <summary>
+	/// Lists the addresses the account has handed out, with the value each has received.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static bool DisconnectServer(String @uri)
+	public static List<AccountAddress> ListAddresses(DbInit @config, uint @accountId)
 	{
-		return FfiConverterBoolean.INSTANCE.Lift(
+		return FfiConverterSequenceTypeAccountAddress.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_disconnect_server(FfiConverterString.INSTANCE.Lower(@uri), ref _status)
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_list_addresses(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						ref _status
+					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Lists each unspent note and UTXO in the account, with how it contributes to `get_user_balances`.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static List<AccountInfo> GetAccounts(DbInit @config)
+	public static List<UnspentNote> ListNotes(DbInit @config, uint @accountId)
 	{
-		return FfiConverterSequenceTypeAccountInfo.INSTANCE.Lift(
+		return FfiConverterSequenceTypeUnspentNote.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_accounts(FfiConverterTypeDbInit.INSTANCE.Lower(@config), ref _status)
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_list_notes(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						ref _status
+					)
 			)
 		);
 	}
 
 	/// <summary>
-	/// Gets the oldest birthday height for any account in the wallet.
+	/// Prunes data the wallet no longer needs and compacts the database file.
+	/// Call this between syncs rather than while one is running.
 	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static uint? GetBirthdayHeight(DbInit @config)
+	public static MaintenanceReport MaintainDatabase(DbInit @config, MaintenanceOptions @options)
 	{
-		return FfiConverterOptionalUInt32.INSTANCE.Lift(
+		return FfiConverterTypeMaintenanceReport.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_birthday_height(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_maintain_database(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterTypeMaintenanceOptions.INSTANCE.Lower(@options),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Generates an address at a diversifier index that has never been handed out, with the given receiver types.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static BirthdayHeights GetBirthdayHeights(DbInit @config, uint @accountId)
+	public static String NextUnusedAddress(DbInit @config, uint @accountId, List<Pool> @receiverTypes)
 	{
-		return FfiConverterTypeBirthdayHeights.INSTANCE.Lift(
+		return FfiConverterString.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_birthday_heights(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_next_unused_address(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
 						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						FfiConverterSequenceTypePool.INSTANCE.Lower(@receiverTypes),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Downloads and checks the Sapling parameters ahead of time, unless they are already present,
+	/// so that sending works offline and the first send doesn't wait for the download.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static uint GetBlockHeight(String @uri, CancellationSource? @cancellation)
+	public static void PrefetchParameters(ParameterDownloadProgress? @progress)
 	{
-		return FfiConverterUInt32.INSTANCE.Lift(
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_prefetch_parameters(
+					FfiConverterOptionalTypeParameterDownloadProgress.INSTANCE.Lower(@progress),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Probes the given servers and sorts them from the most to the least preferred.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static List<ServerStatus> RankServers(List<String> @uris)
+	{
+		return FfiConverterSequenceTypeServerStatus.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_block_height(
-						FfiConverterString.INSTANCE.Lower(@uri),
-						FfiConverterOptionalTypeCancellationSource.INSTANCE.Lower(@cancellation),
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_rank_servers(
+						FfiConverterSequenceString.INSTANCE.Lower(@uris),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Changes the passphrase the database is encrypted with.
+	/// A null `new_passphrase` decrypts the database, and a null `config.passphrase` encrypts a plaintext database.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static uint? GetSyncHeight(DbInit @config)
+	public static void RekeyDatabase(DbInit @config, String? @newPassphrase)
 	{
-		return FfiConverterOptionalUInt32.INSTANCE.Lift(
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_rekey_database(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterOptionalString.INSTANCE.Lower(@newPassphrase),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Frees the memory held by the Sapling parameters, which are otherwise kept loaded between sends.
+	/// They are loaded again by the next send.
+	/// </summary>
+	public static void ReleaseProver()
+	{
+		_UniffiHelpers.RustCall((ref RustCallStatus _status) => _UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_release_prover(ref _status));
+	}
+
+	/// <summary>
+	/// Removes an account and everything the wallet tracks that only pertains to it.
+	/// Returns false if the account does not exist.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static bool RemoveAccount(DbInit @config, uint @accountId)
+	{
+		return FfiConverterBoolean.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_sync_height(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_remove_account(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
 						ref _status
 					)
 			)
@@ -2958,109 +6509,311 @@ internal static class LightWalletMethods
 	}
 
 	/// <exception cref="LightWalletException"></exception>
-	public static List<Transaction> GetTransactions(DbInit @config, uint @accountId, uint @startingBlock)
+	public static bool RemoveAddressBookEntry(DbInit @config, uint @id)
 	{
-		return FfiConverterSequenceTypeTransaction.INSTANCE.Lift(
+		return FfiConverterBoolean.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_transactions(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_remove_address_book_entry(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
-						FfiConverterUInt32.INSTANCE.Lower(@accountId),
-						FfiConverterUInt32.INSTANCE.Lower(@startingBlock),
+						FfiConverterUInt32.INSTANCE.Lower(@id),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Applies the given repairs for problems reported by `check_wallet`, then checks the wallet again.
+	/// Call this between syncs rather than while one is running.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static List<TransparentNote> GetUnshieldedUtxos(DbInit @config, uint @accountId)
+	public static WalletCheckReport RepairWallet(DbInit @config, List<WalletRepair> @repairs)
 	{
-		return FfiConverterSequenceTypeTransparentNote.INSTANCE.Lift(
+		return FfiConverterTypeWalletCheckReport.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_unshielded_utxos(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_repair_wallet(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
-						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						FfiConverterSequenceTypeWalletRepair.INSTANCE.Lower(@repairs),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Schedules the blocks from the account's birthday onward to be scanned again by the next `sync`.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static UserBalances GetUserBalances(DbInit @config, uint @accountId)
+	public static void RescanAccount(DbInit @config, uint @accountId)
 	{
-		return FfiConverterTypeUserBalances.INSTANCE.Lift(
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_rescan_account(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterUInt32.INSTANCE.Lower(@accountId),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Schedules every block from `height` onward to be scanned again by the next `sync`.
+	/// Labels, the address book and account metadata are preserved.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void RescanFrom(DbInit @config, uint @height)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_rescan_from(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterUInt32.INSTANCE.Lower(@height),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Restores a backup made by `export_backup` to a new wallet database, which is encrypted with `new_passphrase` if specified.
+	/// Call `init` on the restored wallet before using it.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void RestoreBackup(String @path, String @passphrase, String @newDataFile, String? @newPassphrase)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_restore_backup(
+					FfiConverterString.INSTANCE.Lower(@path),
+					FfiConverterString.INSTANCE.Lower(@passphrase),
+					FfiConverterString.INSTANCE.Lower(@newDataFile),
+					FfiConverterOptionalString.INSTANCE.Lower(@newPassphrase),
+					ref _status
+				)
+		);
+	}
+
+	/// <exception cref="LightWalletException"></exception>
+	public static List<SendTransactionResult> Send(
+		DbInit @config,
+		String @uri,
+		byte[] @usk,
+		List<TransactionSendDetail> @sendDetails,
+		CoinSelection? @coinSelection
+	)
+	{
+		return FfiConverterSequenceTypeSendTransactionResult.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_get_user_balances(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_send(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
-						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						FfiConverterString.INSTANCE.Lower(@uri),
+						FfiConverterByteArray.INSTANCE.Lower(@usk),
+						FfiConverterSequenceTypeTransactionSendDetail.INSTANCE.Lower(@sendDetails),
+						FfiConverterOptionalTypeCoinSelection.INSTANCE.Lower(@coinSelection),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Pays many recipients, splitting the payments across as many transactions as `options` allows.
+	/// The batch's progress is saved, so calling this again with the same `batch_id` and payments resumes it
+	/// without paying anyone twice.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static uint ImportAccountUfvk(
+	public static BatchResult SendBatch(
 		DbInit @config,
 		String @uri,
-		String @ufvk,
-		bool @spendingKeyAvailable,
-		uint? @birthdayHeight,
-		CancellationSource? @cancellation
+		byte[] @usk,
+		String @batchId,
+		List<TransactionSendDetail> @sendDetails,
+		BatchOptions? @options
 	)
 	{
-		return FfiConverterUInt32.INSTANCE.Lift(
+		return FfiConverterTypeBatchResult.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_import_account_ufvk(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_send_batch(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
 						FfiConverterString.INSTANCE.Lower(@uri),
-						FfiConverterString.INSTANCE.Lower(@ufvk),
-						FfiConverterBoolean.INSTANCE.Lower(@spendingKeyAvailable),
-						FfiConverterOptionalUInt32.INSTANCE.Lower(@birthdayHeight),
-						FfiConverterOptionalTypeCancellationSource.INSTANCE.Lower(@cancellation),
+						FfiConverterByteArray.INSTANCE.Lower(@usk),
+						FfiConverterString.INSTANCE.Lower(@batchId),
+						FfiConverterSequenceTypeTransactionSendDetail.INSTANCE.Lower(@sendDetails),
+						FfiConverterOptionalTypeBatchOptions.INSTANCE.Lower(@options),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Sets or (when `value` is null) removes a metadata value on an account.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static void Init(DbInit @config)
+	public static void SetAccountMetadata(DbInit @config, uint @accountId, String @key, String? @value)
 	{
 		_UniffiHelpers.RustCallWithError(
 			FfiConverterTypeLightWalletException.INSTANCE,
 			(ref RustCallStatus _status) =>
-				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_init(FfiConverterTypeDbInit.INSTANCE.Lower(@config), ref _status)
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_account_metadata(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterUInt32.INSTANCE.Lower(@accountId),
+					FfiConverterString.INSTANCE.Lower(@key),
+					FfiConverterOptionalString.INSTANCE.Lower(@value),
+					ref _status
+				)
 		);
 	}
 
+	/// <summary>
+	/// Sets or (when `name` is null) clears the name of an account.
+	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static List<SendTransactionResult> Send(DbInit @config, String @uri, byte[] @usk, List<TransactionSendDetail> @sendDetails)
+	public static void SetAccountName(DbInit @config, uint @accountId, String? @name)
 	{
-		return FfiConverterSequenceTypeSendTransactionResult.INSTANCE.Lift(
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_account_name(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterUInt32.INSTANCE.Lower(@accountId),
+					FfiConverterOptionalString.INSTANCE.Lower(@name),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Adds or updates an address book entry, returning its ID.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static uint SetAddressBookEntry(DbInit @config, AddressBookEntry @entry)
+	{
+		return FfiConverterUInt32.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
 				FfiConverterTypeLightWalletException.INSTANCE,
 				(ref RustCallStatus _status) =>
-					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_send(
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_address_book_entry(
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
-						FfiConverterString.INSTANCE.Lower(@uri),
-						FfiConverterByteArray.INSTANCE.Lower(@usk),
-						FfiConverterSequenceTypeTransactionSendDetail.INSTANCE.Lower(@sendDetails),
+						FfiConverterTypeAddressBookEntry.INSTANCE.Lower(@entry),
 						ref _status
 					)
 			)
 		);
 	}
 
+	/// <summary>
+	/// Sets or (when `label` is null) clears the label on one of the account's diversified addresses.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void SetAddressLabel(DbInit @config, uint @accountId, byte[] @diversifierIndex, String? @label)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_address_label(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterUInt32.INSTANCE.Lower(@accountId),
+					FfiConverterByteArray.INSTANCE.Lower(@diversifierIndex),
+					FfiConverterOptionalString.INSTANCE.Lower(@label),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Sets the options for connecting to the server at the given URI, or restores the defaults when null.
+	/// The next request to the server reconnects with the new options.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void SetConnectionOptions(String @uri, ConnectionOptions? @options)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_connection_options(
+					FfiConverterString.INSTANCE.Lower(@uri),
+					FfiConverterOptionalTypeConnectionOptions.INSTANCE.Lower(@options),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Forwards this library's log messages at or above `level` to `sink`, replacing any sink set before.
+	/// A null sink stops forwarding log messages.
+	/// </summary>
+	public static void SetLogSink(LogSink? @sink, LogLevel @level)
+	{
+		_UniffiHelpers.RustCall(
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_log_sink(
+					FfiConverterOptionalTypeLogSink.INSTANCE.Lower(@sink),
+					FfiConverterTypeLogLevel.INSTANCE.Lower(@level),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Proves with the given Sapling parameter files instead of those in the default folder,
+	/// or goes back to the default folder when null. The files must have the expected hashes.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void SetParameterPaths(ParameterPaths? @paths)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_parameter_paths(
+					FfiConverterOptionalTypeParameterPaths.INSTANCE.Lower(@paths),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Routes all server traffic and parameter downloads through Tor, or stops doing so when null.
+	/// </summary>
+	public static void SetTor(TorOptions? @options)
+	{
+		_UniffiHelpers.RustCall(
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_tor(
+					FfiConverterOptionalTypeTorOptions.INSTANCE.Lower(@options),
+					ref _status
+				)
+		);
+	}
+
+	/// <summary>
+	/// Sets or (when `label` is null) clears the label on a transaction.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static void SetTransactionLabel(DbInit @config, byte[] @txid, String? @label)
+	{
+		_UniffiHelpers.RustCallWithError(
+			FfiConverterTypeLightWalletException.INSTANCE,
+			(ref RustCallStatus _status) =>
+				_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_set_transaction_label(
+					FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+					FfiConverterByteArray.INSTANCE.Lower(@txid),
+					FfiConverterOptionalString.INSTANCE.Lower(@label),
+					ref _status
+				)
+		);
+	}
+
 	/// <exception cref="LightWalletException"></exception>
 	public static List<SendTransactionResult> Shield(DbInit @config, String @uri, byte[] @usk, String @address)
 	{
@@ -3081,9 +6834,15 @@ internal static class LightWalletMethods
 
 	/// <summary>
 	/// Constructs a proposal for how a given spend can be executed, and returns details for how it would work.
+	/// When `coin_selection` is provided, only the notes it allows will be used to fund the spend.
 	/// </summary>
 	/// <exception cref="LightWalletException"></exception>
-	public static SendDetails SimulateSend(DbInit @config, String @ufvk, List<TransactionSendDetail> @sendDetails)
+	public static SendDetails SimulateSend(
+		DbInit @config,
+		String @ufvk,
+		List<TransactionSendDetail> @sendDetails,
+		CoinSelection? @coinSelection
+	)
 	{
 		return FfiConverterTypeSendDetails.INSTANCE.Lift(
 			_UniffiHelpers.RustCallWithError(
@@ -3093,6 +6852,7 @@ internal static class LightWalletMethods
 						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
 						FfiConverterString.INSTANCE.Lower(@ufvk),
 						FfiConverterSequenceTypeTransactionSendDetail.INSTANCE.Lower(@sendDetails),
+						FfiConverterOptionalTypeCoinSelection.INSTANCE.Lower(@coinSelection),
 						ref _status
 					)
 			)
@@ -3109,7 +6869,8 @@ internal static class LightWalletMethods
 		String @uri,
 		SyncUpdate? @progress,
 		bool @continually,
-		CancellationSource? @cancellation
+		CancellationSource? @cancellation,
+		SyncOptions? @options = null
 	)
 	{
 		return FfiConverterTypeSyncUpdateData.INSTANCE.Lift(
@@ -3122,6 +6883,28 @@ internal static class LightWalletMethods
 						FfiConverterOptionalTypeSyncUpdate.INSTANCE.Lower(@progress),
 						FfiConverterBoolean.INSTANCE.Lower(@continually),
 						FfiConverterOptionalTypeCancellationSource.INSTANCE.Lower(@cancellation),
+						FfiConverterOptionalTypeSyncOptions.INSTANCE.Lower(@options),
+						ref _status
+					)
+			)
+		);
+	}
+
+	/// <summary>
+	/// Checks whether a unified spending key belongs to the given account,
+	/// so that a wrong seed can be caught before a transaction is built.
+	/// </summary>
+	/// <exception cref="LightWalletException"></exception>
+	public static bool VerifySpendingKey(DbInit @config, uint @accountId, byte[] @usk)
+	{
+		return FfiConverterBoolean.INSTANCE.Lift(
+			_UniffiHelpers.RustCallWithError(
+				FfiConverterTypeLightWalletException.INSTANCE,
+				(ref RustCallStatus _status) =>
+					_UniFFILib.uniffi_nerdbank_zcash_rust_fn_func_verify_spending_key(
+						FfiConverterTypeDbInit.INSTANCE.Lower(@config),
+						FfiConverterUInt32.INSTANCE.Lower(@accountId),
+						FfiConverterByteArray.INSTANCE.Lower(@usk),
 						ref _status
 					)
 			)
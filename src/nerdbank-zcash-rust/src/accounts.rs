@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use rusqlite::{named_params, Connection, OptionalExtension};
+use secrecy::{ExposeSecret, SecretVec};
+use zcash_client_backend::{
+    data_api::{Account, WalletRead},
+    keys::UnifiedSpendingKey,
+};
+use zcash_client_sqlite::AccountId;
+use zip32::fingerprint::SeedFingerprint;
+
+use crate::{
+    backing_store::{open_connection, Db},
+    error::Error,
+    interop::DbInit,
+    sql_statements::{
+        CLEAR_CHAIN_STATE, GET_MIN_BIRTHDAY_HEIGHT, PRUNE_NULLIFIER_MAP, PRUNE_TX_LOCATOR_MAP,
+        REMOVE_ACCOUNT, TRIM_SCAN_QUEUE_TO_BIRTHDAYS,
+    },
+};
+
+/// Removes an account from the wallet, along with its notes, addresses and any transactions
+/// that do not involve another account in the wallet.
+///
+/// Scan ranges and spend-detection data that only the removed account needed are dropped.
+/// Removing the last account drops everything the wallet learned from the chain.
+///
+/// Returns `false` if no account with the given ID exists.
+pub fn remove_account(config: &DbInit, account_id: AccountId) -> Result<bool, Error> {
+    let mut conn = open_connection(&config.data_file)?;
+    let tx = conn.transaction()?;
+
+    let exists = tx
+        .query_row(
+            "SELECT 1 FROM accounts WHERE id = :account_id",
+            named_params! { ":account_id": u32::from(account_id) },
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !exists {
+        return Ok(false);
+    }
+
+    tx.execute(
+        "CREATE TEMP TABLE removed_account (id INTEGER NOT NULL)",
+        [],
+    )?;
+    tx.execute(
+        "INSERT INTO removed_account (id) VALUES (:account_id)",
+        named_params! { ":account_id": u32::from(account_id) },
+    )?;
+    tx.execute_batch(REMOVE_ACCOUNT)?;
+
+    // The note commitment trees are shared by all accounts, so chain data is only dropped
+    // where no remaining account can need it.
+    match tx.query_row(GET_MIN_BIRTHDAY_HEIGHT, [], |row| {
+        row.get::<_, Option<u32>>(0)
+    })? {
+        Some(min_birthday) => {
+            tx.execute_batch(TRIM_SCAN_QUEUE_TO_BIRTHDAYS)?;
+            tx.execute(
+                PRUNE_NULLIFIER_MAP,
+                named_params! { ":height": min_birthday },
+            )?;
+            tx.execute(
+                PRUNE_TX_LOCATOR_MAP,
+                named_params! { ":height": min_birthday },
+            )?;
+        }
+        None => tx.execute_batch(CLEAR_CHAIN_STATE)?,
+    }
+
+    tx.commit()?;
+
+    Ok(true)
+}
+
+/// Sets or (when `name` is `None`) clears the name of an account.
+pub fn set_account_name(
+    config: &DbInit,
+    account_id: AccountId,
+    name: Option<String>,
+) -> Result<(), Error> {
+    let conn = open_connection(&config.data_file)?;
+    ensure_account_exists(&conn, account_id)?;
+    conn.execute(
+        "INSERT INTO account_info (account_id, name) VALUES (:account_id, :name)
+		ON CONFLICT (account_id) DO UPDATE SET name = excluded.name",
+        named_params! {
+            ":account_id": u32::from(account_id),
+            ":name": name,
+        },
+    )?;
+    Ok(())
+}
+
+/// Sets or (when `value` is `None`) removes a metadata value on an account.
+pub fn set_account_metadata(
+    config: &DbInit,
+    account_id: AccountId,
+    key: String,
+    value: Option<String>,
+) -> Result<(), Error> {
+    let conn = open_connection(&config.data_file)?;
+    ensure_account_exists(&conn, account_id)?;
+    match value {
+        Some(value) => conn.execute(
+            "INSERT INTO account_metadata (account_id, key, value) VALUES (:account_id, :key, :value)
+			ON CONFLICT (account_id, key) DO UPDATE SET value = excluded.value",
+            named_params! {
+                ":account_id": u32::from(account_id),
+                ":key": key,
+                ":value": value,
+            },
+        )?,
+        None => conn.execute(
+            "DELETE FROM account_metadata WHERE account_id = :account_id AND key = :key",
+            named_params! {
+                ":account_id": u32::from(account_id),
+                ":key": key,
+            },
+        )?,
+    };
+    Ok(())
+}
+
+/// Gets the name and metadata of an account.
+pub(crate) fn get_account_metadata(
+    conn: &Connection,
+    account_id: AccountId,
+) -> Result<(Option<String>, HashMap<String, String>), Error> {
+    let name: Option<String> = conn
+        .query_row(
+            "SELECT name FROM account_info WHERE account_id = :account_id",
+            named_params! { ":account_id": u32::from(account_id) },
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+
+    let mut stmt =
+        conn.prepare("SELECT key, value FROM account_metadata WHERE account_id = :account_id")?;
+    let metadata = stmt
+        .query_map(
+            named_params! { ":account_id": u32::from(account_id) },
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok((name, metadata))
+}
+
+/// Finds the account in the wallet that was derived from the given seed at the given ZIP-32 account index.
+pub fn find_account_by_seed(
+    config: &DbInit,
+    seed: &SecretVec<u8>,
+    account_index: zip32::AccountId,
+) -> Result<Option<AccountId>, Error> {
+    let seed_fingerprint = SeedFingerprint::from_seed(seed.expose_secret()).ok_or_else(|| {
+        Error::InvalidArgument("The seed must be between 32 and 252 bytes in length.".to_string())
+    })?;
+    let db = Db::load(&config.data_file, config.network.into())?;
+    Ok(db
+        .data
+        .get_derived_account(&seed_fingerprint, account_index)?
+        .map(|account| account.id()))
+}
+
+/// Checks whether a unified spending key belongs to the given account.
+pub fn verify_spending_key(
+    config: &DbInit,
+    account_id: AccountId,
+    usk: &UnifiedSpendingKey,
+) -> Result<bool, Error> {
+    let db = Db::load(&config.data_file, config.network.into())?;
+    if db.data.get_account(account_id)?.is_none() {
+        return Err(Error::InvalidArgument("Unknown account.".to_string()));
+    }
+
+    Ok(db
+        .data
+        .get_account_for_ufvk(&usk.to_unified_full_viewing_key())?
+        .is_some_and(|account| account.id() == account_id))
+}
+
+fn ensure_account_exists(conn: &Connection, account_id: AccountId) -> Result<(), Error> {
+    conn.query_row(
+        "SELECT 1 FROM accounts WHERE id = :account_id",
+        named_params! { ":account_id": u32::from(account_id) },
+        |_| Ok(()),
+    )
+    .optional()?
+    .ok_or_else(|| Error::InvalidArgument("Unknown account.".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use matches::assert_matches;
+
+    use crate::{
+        analysis::get_unspent_notes,
+        interop::TransactionSendDetail,
+        send::create_send_proposal,
+        test_constants::{setup_test, MIN_CONFIRMATIONS, VALID_SAPLING_TESTNET},
+    };
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_account_metadata() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+
+        set_account_name(&setup.db_init, account_id, Some("Savings".to_string())).unwrap();
+        set_account_metadata(
+            &setup.db_init,
+            account_id,
+            "color".to_string(),
+            Some("blue".to_string()),
+        )
+        .unwrap();
+
+        let conn = open_connection(&setup.data_file).unwrap();
+        let (name, metadata) = get_account_metadata(&conn, account_id).unwrap();
+        assert_eq!(name.as_deref(), Some("Savings"));
+        assert_eq!(metadata.get("color").map(String::as_str), Some("blue"));
+
+        set_account_metadata(&setup.db_init, account_id, "color".to_string(), None).unwrap();
+        let (_, metadata) = get_account_metadata(&conn, account_id).unwrap();
+        assert!(metadata.is_empty());
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_remove_account() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+        set_account_name(&setup.db_init, account_id, Some("Spending".to_string())).unwrap();
+        setup.sync().await;
+
+        assert!(remove_account(&setup.db_init, account_id).unwrap());
+        assert!(setup.db.data.get_account(account_id).unwrap().is_none());
+        assert!(!remove_account(&setup.db_init, account_id).unwrap());
+
+        // With no accounts left, nothing learned from the chain is kept.
+        let conn = open_connection(&setup.data_file).unwrap();
+        for table in [
+            "blocks",
+            "scan_queue",
+            "sapling_tree_shards",
+            "nullifier_map",
+        ] {
+            let count: u32 = conn
+                .query_row(&format!("SELECT count(*) FROM {}", table), [], |row| {
+                    row.get(0)
+                })
+                .unwrap();
+            assert_eq!(count, 0, "{} should be empty.", table);
+        }
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_remove_account_keeps_other_accounts_working() {
+        let mut setup = setup_test().await;
+        let (_, _, kept, usk) = setup.create_account().await.unwrap();
+        let (_, _, removed, _) = setup.create_account().await.unwrap();
+        setup.sync().await;
+
+        // Record a transfer from the kept account that names only the removed account as its recipient.
+        let conn = open_connection(&setup.data_file).unwrap();
+        conn.execute(
+            "INSERT INTO transactions (txid) VALUES (:txid)",
+            named_params! { ":txid": vec![1u8; 32] },
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO sent_notes (tx, output_pool, output_index, from_account_id, to_account_id, value)
+			VALUES (:tx, 2, 0, :from_account_id, :to_account_id, 1000)",
+            named_params! {
+                ":tx": conn.last_insert_rowid(),
+                ":from_account_id": u32::from(kept),
+                ":to_account_id": u32::from(removed),
+            },
+        )
+        .unwrap();
+
+        assert!(remove_account(&setup.db_init, removed).unwrap());
+
+        let (to_address, to_account_id): (Option<String>, Option<u32>) = conn
+            .query_row(
+                "SELECT to_address, to_account_id FROM sent_notes WHERE from_account_id = :account_id",
+                named_params! { ":account_id": u32::from(kept) },
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(to_address.is_some());
+        assert_eq!(to_account_id, None);
+
+        // The remaining account still syncs, and can propose spends from its (empty) balance.
+        setup.sync().await;
+        let result = create_send_proposal(
+            &mut setup.db,
+            setup.network,
+            &usk.to_unified_full_viewing_key(),
+            NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap(),
+            vec![TransactionSendDetail {
+                value: 1000,
+                memo: None,
+                recipient: VALID_SAPLING_TESTNET.to_string(),
+            }],
+            None,
+        );
+        assert_matches!(result, Err(Error::InsufficientFunds { .. }));
+    }
+
+    #[tokio_shared_rt::test]
+    #[ignore = "requires NERDBANK_ZCASH_FUNDED_TESTNET_SEED"]
+    async fn test_remove_account_keeps_funded_account_spendable() {
+        let mut setup = setup_test().await;
+        let (kept, usk) = setup.create_funded_account().await;
+        let (_, _, removed, _) = setup.create_account().await.unwrap();
+        setup.sync().await;
+        let balance_before = get_unspent_notes(
+            &setup.db_init,
+            kept,
+            NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap(),
+        )
+        .unwrap();
+
+        assert!(remove_account(&setup.db_init, removed).unwrap());
+        setup.sync().await;
+
+        let balance_after = get_unspent_notes(
+            &setup.db_init,
+            kept,
+            NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(balance_before.len(), balance_after.len());
+        create_send_proposal(
+            &mut setup.db,
+            setup.network,
+            &usk.to_unified_full_viewing_key(),
+            NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap(),
+            vec![TransactionSendDetail {
+                value: 10_000,
+                memo: None,
+                recipient: VALID_SAPLING_TESTNET.to_string(),
+            }],
+            None,
+        )
+        .unwrap();
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_find_account_by_seed_and_verify_key() {
+        let mut setup = setup_test().await;
+        let (seed, _, account_id, usk) = setup.create_account().await.unwrap();
+
+        assert_eq!(
+            find_account_by_seed(&setup.db_init, &seed, zip32::AccountId::ZERO).unwrap(),
+            Some(account_id)
+        );
+        assert_eq!(
+            find_account_by_seed(
+                &setup.db_init,
+                &seed,
+                zip32::AccountId::try_from(1).unwrap()
+            )
+            .unwrap(),
+            None
+        );
+
+        assert!(verify_spending_key(&setup.db_init, account_id, &usk).unwrap());
+        let other_usk =
+            UnifiedSpendingKey::from_seed(&setup.network, &[7u8; 32], zip32::AccountId::ZERO)
+                .unwrap();
+        assert!(!verify_spending_key(&setup.db_init, account_id, &other_usk).unwrap());
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use rusqlite::{named_params, Connection, OptionalExtension};
+use zcash_client_backend::encoding::AddressCodec;
+use zcash_keys::address::{Address, UnifiedAddress};
+use zcash_primitives::{consensus::Network, zip32::DiversifierIndex};
+
+use crate::{
+    addresses::diversifier_index_be,
+    backing_store::open_connection,
+    error::Error,
+    interop::DbInit,
+    sql_statements::{
+        GET_ADDRESS_BOOK, GET_OWN_ADDRESS_LABELS, HAS_ACCOUNT_ADDRESS, INSERT_ADDRESS_BOOK_ENTRY,
+        REMOVE_ADDRESS_BOOK_ENTRY, REMOVE_ADDRESS_LABEL, REMOVE_TRANSACTION_LABEL,
+        SET_ADDRESS_LABEL, SET_TRANSACTION_LABEL, UPDATE_ADDRESS_BOOK_ENTRY,
+    },
+};
+
+/// A named address that the user sends to (or receives from).
+#[derive(Debug, Clone)]
+pub struct AddressBookEntry {
+    /// The ID of the entry, or `None` when creating a new entry.
+    pub id: Option<u32>,
+    pub address: String,
+    pub label: String,
+}
+
+/// Adds or updates an address book entry.
+///
+/// An entry without an `id` that names an address already in the address book
+/// updates the label on the existing entry.
+///
+/// Returns the ID of the entry.
+pub fn set_address_book_entry(config: &DbInit, entry: AddressBookEntry) -> Result<u32, Error> {
+    let network: Network = config.network.into();
+    if Address::decode(&network, &entry.address).is_none() {
+        return Err(Error::InvalidAddress);
+    }
+
+    let conn = open_connection(&config.data_file)?;
+    let id = match entry.id {
+        Some(id) => {
+            let updated = conn.execute(
+                UPDATE_ADDRESS_BOOK_ENTRY,
+                named_params! {
+                    ":id": id,
+                    ":address": entry.address,
+                    ":label": entry.label,
+                },
+            )?;
+            if updated == 0 {
+                return Err(Error::InvalidArgument(format!(
+                    "No address book entry with ID {}.",
+                    id
+                )));
+            }
+            id
+        }
+        None => conn.query_row(
+            INSERT_ADDRESS_BOOK_ENTRY,
+            named_params! {
+                ":address": entry.address,
+                ":label": entry.label,
+            },
+            |row| row.get(0),
+        )?,
+    };
+
+    Ok(id)
+}
+
+pub fn get_address_book(config: &DbInit) -> Result<Vec<AddressBookEntry>, Error> {
+    let conn = open_connection(&config.data_file)?;
+    let mut stmt = conn.prepare(GET_ADDRESS_BOOK)?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(AddressBookEntry {
+                id: row.get("id")?,
+                address: row.get("address")?,
+                label: row.get("label")?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Removes an address book entry.
+///
+/// Returns `true` if the entry existed.
+pub fn remove_address_book_entry(config: &DbInit, id: u32) -> Result<bool, Error> {
+    let conn = open_connection(&config.data_file)?;
+    let removed = conn.execute(REMOVE_ADDRESS_BOOK_ENTRY, named_params! { ":id": id })?;
+    Ok(removed > 0)
+}
+
+/// Sets or (when `label` is `None`) clears the label on one of the wallet's own diversified addresses.
+pub fn set_address_label(
+    config: &DbInit,
+    account_id: u32,
+    diversifier_index: DiversifierIndex,
+    label: Option<String>,
+) -> Result<(), Error> {
+    let conn = open_connection(&config.data_file)?;
+
+    let diversifier_index_be = diversifier_index_be(&diversifier_index);
+
+    match label {
+        Some(label) => {
+            let known = conn
+                .query_row(
+                    HAS_ACCOUNT_ADDRESS,
+                    named_params! {
+                        ":account_id": account_id,
+                        ":diversifier_index_be": diversifier_index_be,
+                    },
+                    |_| Ok(()),
+                )
+                .optional()?;
+            if known.is_none() {
+                return Err(Error::InvalidArgument(
+                    "The account has no address at this diversifier index. Call add_diversifier first.".to_string(),
+                ));
+            }
+
+            conn.execute(
+                SET_ADDRESS_LABEL,
+                named_params! {
+                    ":account_id": account_id,
+                    ":diversifier_index_be": diversifier_index_be,
+                    ":label": label,
+                },
+            )?;
+        }
+        None => {
+            conn.execute(
+                REMOVE_ADDRESS_LABEL,
+                named_params! {
+                    ":account_id": account_id,
+                    ":diversifier_index_be": diversifier_index_be,
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets or (when `label` is `None`) clears the label on a transaction.
+pub fn set_transaction_label(
+    config: &DbInit,
+    txid: Vec<u8>,
+    label: Option<String>,
+) -> Result<(), Error> {
+    if txid.len() != 32 {
+        return Err(Error::InvalidArgument("Invalid txid.".to_string()));
+    }
+
+    let conn = open_connection(&config.data_file)?;
+    match label {
+        Some(label) => conn.execute(
+            SET_TRANSACTION_LABEL,
+            named_params! {
+                ":txid": txid,
+                ":label": label,
+            },
+        )?,
+        None => conn.execute(REMOVE_TRANSACTION_LABEL, named_params! { ":txid": txid })?,
+    };
+
+    Ok(())
+}
+
+/// Gets the labels for every address the user has named, keyed by the encoded address.
+///
+/// Labels on the wallet's own unified addresses are also keyed by each of their receivers
+/// (encoded the way `get_transactions` reports recipients), since a received note only
+/// records the receiver it was sent to.
+pub(crate) fn get_address_labels(
+    conn: &Connection,
+    network: &Network,
+) -> Result<HashMap<String, String>, Error> {
+    let mut labels = HashMap::new();
+
+    let mut stmt = conn.prepare(GET_ADDRESS_BOOK)?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        labels.insert(row.get("address")?, row.get("label")?);
+    }
+
+    let mut stmt = conn.prepare(GET_OWN_ADDRESS_LABELS)?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let address: String = row.get("address")?;
+        let label: String = row.get("label")?;
+        if let Some(Address::Unified(ua)) = Address::decode(network, &address) {
+            if let Some(orchard) = ua.orchard() {
+                if let Some(orchard_only) =
+                    UnifiedAddress::from_receivers(Some(*orchard), None, None)
+                {
+                    labels.insert(orchard_only.encode(network), label.clone());
+                }
+            }
+            if let Some(sapling) = ua.sapling() {
+                labels.insert(sapling.encode(network), label.clone());
+            }
+            if let Some(transparent) = ua.transparent() {
+                labels.insert(transparent.encode(network), label.clone());
+            }
+        }
+
+        labels.insert(address, label);
+    }
+
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+
+    use crate::test_constants::{setup_test, VALID_SAPLING_TESTNET};
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_address_book_crud() {
+        let setup = setup_test().await;
+        let id = set_address_book_entry(
+            &setup.db_init,
+            AddressBookEntry {
+                id: None,
+                address: VALID_SAPLING_TESTNET.to_string(),
+                label: "Alice".to_string(),
+            },
+        )
+        .unwrap();
+
+        // Adding the same address again updates the existing entry.
+        let same_id = set_address_book_entry(
+            &setup.db_init,
+            AddressBookEntry {
+                id: None,
+                address: VALID_SAPLING_TESTNET.to_string(),
+                label: "Alice Smith".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(id, same_id);
+
+        let entries = get_address_book(&setup.db_init).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].label, "Alice Smith");
+
+        assert!(remove_address_book_entry(&setup.db_init, id).unwrap());
+        assert!(get_address_book(&setup.db_init).unwrap().is_empty());
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_address_book_rejects_invalid_address() {
+        let setup = setup_test().await;
+        let result = set_address_book_entry(
+            &setup.db_init,
+            AddressBookEntry {
+                id: None,
+                address: "not an address".to_string(),
+                label: "Bob".to_string(),
+            },
+        );
+        assert_matches!(result, Err(Error::InvalidAddress));
+    }
+}
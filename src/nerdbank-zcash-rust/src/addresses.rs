@@ -0,0 +1,354 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{named_params, Connection};
+use zcash_client_backend::encoding::AddressCodec;
+use zcash_client_sqlite::AccountId;
+use zcash_keys::{
+    address::{Address, UnifiedAddress},
+    keys::{AddressGenerationError, UnifiedAddressRequest},
+};
+use zcash_primitives::{consensus::Network, zip32::DiversifierIndex};
+
+use crate::{
+    backing_store::{open_connection, Db},
+    error::Error,
+    interop::{DbInit, Pool},
+    sql_statements::{
+        GET_ACCOUNT_ADDRESSES, GET_ADDRESS_AT_DIVERSIFIER_INDEX, GET_RECEIVED_BY_RECEIVER,
+        INSERT_ADDRESS_WITH_RECEIVERS,
+    },
+};
+
+/// A diversified address that an account has handed out.
+#[derive(Debug, Clone)]
+pub struct AccountAddress {
+    /// The diversifier index, in the little-endian byte order that `add_diversifier` takes.
+    pub diversifier_index: Vec<u8>,
+    /// The unified address.
+    pub address: String,
+    /// The unified address with only its Orchard receiver, if it has one.
+    pub orchard_receiver: Option<String>,
+    pub sapling_receiver: Option<String>,
+    pub transparent_receiver: Option<String>,
+    /// When the address was first handed out, if known.
+    /// Addresses created before this was tracked have no creation time.
+    pub created: Option<SystemTime>,
+    pub label: Option<String>,
+    /// The total value received to any of the address's receivers in mined transactions.
+    pub total_received: u64,
+}
+
+pub fn list_addresses(
+    config: &DbInit,
+    account_id: AccountId,
+) -> Result<Vec<AccountAddress>, Error> {
+    let network: Network = config.network.into();
+    let conn = open_connection(&config.data_file)?;
+
+    // Collect the value received by each receiver, so we can attribute it to the addresses they belong to.
+    let mut shielded_received: HashMap<(u32, Vec<u8>), u64> = HashMap::new();
+    let mut transparent_received: HashMap<String, u64> = HashMap::new();
+    let mut stmt = conn.prepare(GET_RECEIVED_BY_RECEIVER)?;
+    let mut rows = stmt.query(named_params! { ":account_id": u32::from(account_id) })?;
+    while let Some(row) = rows.next()? {
+        let output_pool: u32 = row.get("output_pool")?;
+        let value: u64 = row.get("value")?;
+        if output_pool == 0 {
+            if let Some(address) = row.get::<_, Option<String>>("address")? {
+                transparent_received.insert(address, value);
+            }
+        } else if let Some(diversifier) = row.get::<_, Option<Vec<u8>>>("diversifier")? {
+            shielded_received.insert((output_pool, diversifier), value);
+        }
+    }
+
+    let mut stmt = conn.prepare(GET_ACCOUNT_ADDRESSES)?;
+    let mut rows = stmt.query(named_params! { ":account_id": u32::from(account_id) })?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next()? {
+        let address: String = row.get("address")?;
+        let ua = match Address::decode(&network, &address) {
+            Some(Address::Unified(ua)) => ua,
+            _ => {
+                return Err(Error::Internal(format!(
+                    "Stored address is not a unified address: {}",
+                    address
+                )))
+            }
+        };
+
+        let mut diversifier_index: Vec<u8> = row.get("diversifier_index_be")?;
+        diversifier_index.reverse();
+
+        let mut total_received = 0;
+        let orchard_receiver = ua.orchard().and_then(|orchard| {
+            total_received += shielded_received
+                .get(&(3, orchard.diversifier().as_array().to_vec()))
+                .unwrap_or(&0);
+            UnifiedAddress::from_receivers(Some(*orchard), None, None).map(|o| o.encode(&network))
+        });
+        let sapling_receiver = ua.sapling().map(|sapling| {
+            total_received += shielded_received
+                .get(&(2, sapling.diversifier().0.to_vec()))
+                .unwrap_or(&0);
+            sapling.encode(&network)
+        });
+        let transparent_receiver = ua.transparent().map(|transparent| {
+            let encoded = transparent.encode(&network);
+            total_received += transparent_received.get(&encoded).unwrap_or(&0);
+            encoded
+        });
+
+        result.push(AccountAddress {
+            diversifier_index,
+            address,
+            orchard_receiver,
+            sapling_receiver,
+            transparent_receiver,
+            created: row
+                .get::<_, Option<u64>>("created_at")?
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            label: row.get("label")?,
+            total_received,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Gets the account's address at the given diversifier index and records that it has been handed out.
+///
+/// When `receiver_types` is `None`, the address has the account's default set of receivers.
+/// Otherwise it has exactly the requested receivers, or an error is returned if any of them
+/// cannot be produced at this diversifier index.
+///
+/// The wallet records only one address per diversifier index. If an address with other receivers
+/// was already handed out at this index, an error is returned rather than an address the caller did not ask for.
+pub fn add_diversifier(
+    config: &DbInit,
+    account_id: AccountId,
+    diversifier_index: DiversifierIndex,
+    receiver_types: Option<&[Pool]>,
+) -> Result<String, Error> {
+    let network: Network = config.network.into();
+    let mut db = Db::load(&config.data_file, network)?;
+    let conn = open_connection(&config.data_file)?;
+    let diversifier_index_be = diversifier_index_be(&diversifier_index);
+
+    let address = match receiver_types {
+        None => db
+            .add_diversifier(account_id, diversifier_index)?
+            .encode(&network),
+        Some(receiver_types) => {
+            let ua = db.address_with_receivers(
+                account_id,
+                diversifier_index,
+                address_request(receiver_types)?,
+            )?;
+            let requested = ua.encode(&network);
+            conn.execute(
+                INSERT_ADDRESS_WITH_RECEIVERS,
+                named_params! {
+                    ":account_id": u32::from(account_id),
+                    ":diversifier_index_be": diversifier_index_be,
+                    ":address": requested,
+                    ":transparent_address": ua.transparent().map(|t| t.encode(&network)),
+                },
+            )?;
+            let stored: String = conn.query_row(
+                GET_ADDRESS_AT_DIVERSIFIER_INDEX,
+                named_params! {
+                    ":account_id": u32::from(account_id),
+                    ":diversifier_index_be": diversifier_index_be,
+                },
+                |row| row.get(0),
+            )?;
+
+            // Both addresses are derived at the same index, so they differ only if their receivers do.
+            if stored != requested {
+                let mut index = [0u8; 16];
+                index[..11].copy_from_slice(diversifier_index.as_bytes());
+                return Err(Error::InvalidArgument(format!(
+                    "An address with different receivers was already handed out at diversifier index {}.",
+                    u128::from_le_bytes(index)
+                )));
+            }
+
+            stored
+        }
+    };
+
+    record_address_creation(&conn, account_id, &diversifier_index_be)?;
+    Ok(address)
+}
+
+/// Generates a fresh address for the account that has never been handed out before,
+/// containing just the requested receiver types.
+pub fn next_unused_address(
+    config: &DbInit,
+    account_id: AccountId,
+    receiver_types: &[Pool],
+) -> Result<String, Error> {
+    let network: Network = config.network.into();
+    let request = address_request(receiver_types)?;
+
+    let mut db = Db::load(&config.data_file, network)?;
+    let ua = db.next_unused_address(account_id, request)?;
+    let address = ua.encode(&network);
+
+    let conn = open_connection(&config.data_file)?;
+    let diversifier_index_be: Vec<u8> = conn.query_row(
+        "SELECT diversifier_index_be FROM addresses WHERE account_id = :account_id AND address = :address",
+        named_params! {
+            ":account_id": u32::from(account_id),
+            ":address": address,
+        },
+        |row| row.get(0),
+    )?;
+    record_address_creation(&conn, account_id, &diversifier_index_be)?;
+
+    Ok(address)
+}
+
+/// Records the time that an address was first handed out.
+/// Calling this again for the same address leaves the original time in place.
+pub(crate) fn record_address_creation(
+    conn: &Connection,
+    account_id: AccountId,
+    diversifier_index_be: &[u8],
+) -> Result<(), Error> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    conn.execute(
+        "INSERT OR IGNORE INTO address_metadata (account_id, diversifier_index_be, created_at)
+		VALUES (:account_id, :diversifier_index_be, :created_at)",
+        named_params! {
+            ":account_id": u32::from(account_id),
+            ":diversifier_index_be": diversifier_index_be,
+            ":created_at": now,
+        },
+    )?;
+    Ok(())
+}
+
+fn address_request(receiver_types: &[Pool]) -> Result<UnifiedAddressRequest, Error> {
+    UnifiedAddressRequest::new(
+        receiver_types.contains(&Pool::Orchard),
+        receiver_types.contains(&Pool::Sapling),
+        receiver_types.contains(&Pool::Transparent),
+    )
+    .ok_or(Error::AddressGeneration(
+        AddressGenerationError::ShieldedReceiverRequired,
+    ))
+}
+
+/// Converts a diversifier index to the big-endian byte order that the addresses table uses.
+pub(crate) fn diversifier_index_be(diversifier_index: &DiversifierIndex) -> Vec<u8> {
+    let mut result = diversifier_index.as_bytes().to_vec();
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+
+    use crate::test_constants::setup_test;
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_next_unused_address_is_listed() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+
+        let address =
+            next_unused_address(&setup.db_init, account_id, &[Pool::Orchard, Pool::Sapling])
+                .unwrap();
+        let another =
+            next_unused_address(&setup.db_init, account_id, &[Pool::Orchard, Pool::Sapling])
+                .unwrap();
+        assert_ne!(address, another);
+
+        let addresses = list_addresses(&setup.db_init, account_id).unwrap();
+        let listed = addresses.iter().find(|a| a.address == address).unwrap();
+        assert!(listed.created.is_some());
+        assert!(listed.orchard_receiver.is_some());
+        assert!(listed.sapling_receiver.is_some());
+        assert!(listed.transparent_receiver.is_none());
+        assert_eq!(listed.total_received, 0);
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_next_unused_address_requires_shielded_receiver() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+        let result = next_unused_address(&setup.db_init, account_id, &[Pool::Transparent]);
+        assert_matches!(result, Err(Error::AddressGeneration(_)));
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_add_diversifier_with_receivers() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+
+        let address = add_diversifier(
+            &setup.db_init,
+            account_id,
+            DiversifierIndex::from(5u32),
+            Some(&[Pool::Orchard]),
+        )
+        .unwrap();
+        match Address::decode(&setup.network, &address) {
+            Some(Address::Unified(ua)) => {
+                assert!(ua.orchard().is_some());
+                assert!(ua.sapling().is_none());
+                assert!(ua.transparent().is_none());
+            }
+            _ => panic!("Expected a unified address."),
+        }
+
+        let addresses = list_addresses(&setup.db_init, account_id).unwrap();
+        assert!(addresses.iter().any(|a| a.address == address));
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_add_diversifier_rejects_different_receivers() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+        let index = DiversifierIndex::from(7u32);
+
+        let stored = add_diversifier(&setup.db_init, account_id, index, None).unwrap();
+        assert_matches!(
+            add_diversifier(&setup.db_init, account_id, index, Some(&[Pool::Orchard])),
+            Err(Error::InvalidArgument(message)) if message.contains(" 7.")
+        );
+
+        // Asking again for the same receivers returns the same address.
+        let orchard_index = DiversifierIndex::from(8u32);
+        let orchard = add_diversifier(
+            &setup.db_init,
+            account_id,
+            orchard_index,
+            Some(&[Pool::Orchard]),
+        )
+        .unwrap();
+        let again = add_diversifier(
+            &setup.db_init,
+            account_id,
+            orchard_index,
+            Some(&[Pool::Orchard]),
+        )
+        .unwrap();
+        assert_eq!(orchard, again);
+
+        let addresses = list_addresses(&setup.db_init, account_id).unwrap();
+        assert_eq!(addresses.iter().filter(|a| a.address == stored).count(), 1);
+        assert_eq!(addresses.iter().filter(|a| a.address == orchard).count(), 1);
+    }
+}
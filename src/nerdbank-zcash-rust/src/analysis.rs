@@ -1,16 +1,21 @@
 use std::num::NonZeroU32;
 
-use rusqlite::{named_params, Connection};
+use rusqlite::named_params;
 use zcash_client_sqlite::AccountId;
-use zcash_primitives::transaction::fees::zip317::{FeeRule, MINIMUM_FEE};
+use zcash_primitives::{
+    consensus::BlockHeight,
+    memo::Memo,
+    transaction::fees::zip317::{FeeRule, MINIMUM_FEE},
+};
 
-use zcash_client_backend::data_api::WalletRead;
+use zcash_client_backend::{data_api::WalletRead, PoolType};
 
 use crate::{
-    backing_store::Db,
+    backing_store::{open_connection, Db},
     error::Error,
-    interop::DbInit,
-    sql_statements::{GET_BIRTHDAY_HEIGHTS, GET_UNSPENT_NOTES},
+    interop::{DbInit, Pool},
+    sql_statements::{GET_BIRTHDAY_HEIGHTS, GET_UNSPENT_NOTES, GET_UNSPENT_NOTE_INVENTORY},
+    sync::address_from_diversifier,
 };
 
 pub struct BirthdayHeights {
@@ -28,7 +33,7 @@ pub fn get_birthday_heights(
     config: DbInit,
     account_id: AccountId,
 ) -> Result<BirthdayHeights, Error> {
-    let conn = Connection::open(config.data_file)?;
+    let conn = open_connection(config.data_file)?;
     let heights = conn.query_row(
         GET_BIRTHDAY_HEIGHTS,
         named_params! {
@@ -116,7 +121,7 @@ pub fn get_user_balances(
     let marginal_fee: u64 = FeeRule::standard().marginal_fee().into();
     let db = Db::load(&config.data_file, config.network.into())?;
     if let Some((_, anchor)) = db.data.get_target_and_anchor_heights(min_confirmations)? {
-        let conn = Connection::open(config.data_file.clone())?;
+        let conn = open_connection(config.data_file.clone())?;
         let mut balances_query = conn.prepare(GET_UNSPENT_NOTES)?;
         let mut rows = balances_query.query(named_params! {
             ":account_id": u32::from(account_id),
@@ -132,13 +137,11 @@ pub fn get_user_balances(
             let output_pool: u8 = row.get("output_pool")?;
             let is_change: bool = row.get("is_change")?;
 
-            let is_dust = value < marginal_fee;
-            let is_shielded = output_pool > 1; // sprout is unspendable, but can be upgraded just like transparent.
-            let is_mature = match block_height {
-                Some(height) => height <= anchor.into(),
-                None => false,
-            };
-            let is_spendable = is_mature && is_shielded;
+            let NoteMaturity {
+                is_dust,
+                is_mature,
+                is_spendable,
+            } = NoteMaturity::new(value, output_pool, block_height, anchor, marginal_fee);
 
             if !is_change && block_height.is_none() {
                 balances.incoming += value;
@@ -187,11 +190,161 @@ pub fn get_user_balances(
     }
 }
 
+/// How an unspent note contributes to the balances described by [`UserBalances`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NoteSpendability {
+    /// The note may be spent right away.
+    Spendable,
+    /// The note is mined but has too few confirmations to be spent,
+    /// or it is a UTXO that must be shielded before it can be spent.
+    Immature,
+    /// The note has not yet been mined.
+    Unconfirmed,
+    /// The note is mined but worth less than the fee required to spend it.
+    Dust,
+}
+
+/// An unspent note or UTXO that belongs to an account.
+#[derive(Debug, Clone)]
+pub struct UnspentNote {
+    pub pool: Pool,
+    pub value: u64,
+    pub txid: Vec<u8>,
+    /// The index of the output (or action) within the transaction's bundle for `pool`.
+    pub output_index: u32,
+    pub mined_height: Option<u32>,
+    /// The number of confirmations, counting the block that mined the note as the first one.
+    pub confirmations: Option<u32>,
+    pub is_change: bool,
+    /// The diversifier of the receiving shielded address.
+    pub diversifier: Option<Vec<u8>>,
+    /// The address that received this note, when it is known or can be regenerated.
+    pub recipient: Option<String>,
+    /// Whether the note carries a memo other than the empty memo.
+    pub has_memo: bool,
+    pub spendability: NoteSpendability,
+}
+
+/// The facts about a note that determine how it may be spent.
+struct NoteMaturity {
+    is_dust: bool,
+    is_mature: bool,
+    is_spendable: bool,
+}
+
+impl NoteMaturity {
+    fn new(
+        value: u64,
+        output_pool: u8,
+        block_height: Option<u32>,
+        anchor: BlockHeight,
+        marginal_fee: u64,
+    ) -> Self {
+        let is_dust = value < marginal_fee;
+        let is_shielded = output_pool > 1; // sprout is unspendable, but can be upgraded just like transparent.
+        let is_mature = match block_height {
+            Some(height) => height <= anchor.into(),
+            None => false,
+        };
+        NoteMaturity {
+            is_dust,
+            is_mature,
+            is_spendable: is_mature && is_shielded,
+        }
+    }
+
+    fn spendability(&self, is_confirmed: bool) -> NoteSpendability {
+        if !is_confirmed {
+            NoteSpendability::Unconfirmed
+        } else if self.is_dust {
+            NoteSpendability::Dust
+        } else if self.is_spendable {
+            NoteSpendability::Spendable
+        } else {
+            NoteSpendability::Immature
+        }
+    }
+}
+
+/// Returns every unspent note and UTXO that belongs to the given account,
+/// classified the same way [`get_user_balances`] does.
+pub fn get_unspent_notes(
+    config: &DbInit,
+    account_id: AccountId,
+    min_confirmations: NonZeroU32,
+) -> Result<Vec<UnspentNote>, Error> {
+    let marginal_fee: u64 = FeeRule::standard().marginal_fee().into();
+    let network = config.network.into();
+    let db = Db::load(&config.data_file, network)?;
+    let (target, anchor) = match db.data.get_target_and_anchor_heights(min_confirmations)? {
+        Some(heights) => heights,
+        None => return Ok(Vec::new()),
+    };
+    let ufvk = db.data.get_unified_full_viewing_keys()?.remove(&account_id);
+
+    let conn = open_connection(config.data_file.clone())?;
+    let mut notes_query = conn.prepare(GET_UNSPENT_NOTE_INVENTORY)?;
+    let mut rows = notes_query.query(named_params! {
+        ":account_id": u32::from(account_id),
+    })?;
+
+    let mut notes = Vec::new();
+    while let Some(row) = rows.next()? {
+        let block_height: Option<u32> = row.get("block")?;
+        let value: u64 = row.get("value")?;
+        let output_pool: u8 = row.get("output_pool")?;
+        let diversifier: Option<Vec<u8>> = row.get("diversifier")?;
+        let memo: Option<Vec<u8>> = row.get("memo")?;
+
+        let pool = match output_pool {
+            0 => PoolType::Transparent,
+            2 => PoolType::SAPLING,
+            3 => PoolType::ORCHARD,
+            _ => {
+                return Err(Error::Internal(format!(
+                    "Unknown output pool type: {}",
+                    output_pool
+                )))
+            }
+        };
+
+        let recipient = match (row.get::<_, Option<String>>("address")?, &diversifier) {
+            (Some(address), _) => Some(address),
+            (None, Some(d)) => address_from_diversifier(ufvk.as_ref(), pool, d.clone(), &network),
+            (None, None) => None,
+        };
+
+        notes.push(UnspentNote {
+            pool: match pool {
+                PoolType::Transparent => Pool::Transparent,
+                PoolType::SAPLING => Pool::Sapling,
+                PoolType::ORCHARD => Pool::Orchard,
+            },
+            value,
+            txid: row.get("txid")?,
+            output_index: row.get("output_index")?,
+            mined_height: block_height,
+            confirmations: block_height.map(|h| u32::from(target).saturating_sub(h)),
+            is_change: row.get("is_change")?,
+            diversifier,
+            recipient,
+            has_memo: memo
+                .is_some_and(|m| Memo::from_bytes(&m).is_ok_and(|m| !matches!(m, Memo::Empty))),
+            spendability: NoteMaturity::new(value, output_pool, block_height, anchor, marginal_fee)
+                .spendability(block_height.is_some()),
+        });
+    }
+
+    Ok(notes)
+}
+
 #[cfg(test)]
 mod tests {
     use matches::assert_matches;
 
-    use crate::test_constants::{setup_test, MIN_CONFIRMATIONS};
+    use zcash_primitives::memo::MemoBytes;
+
+    use crate::test_constants::{insert_received_note, setup_test, MIN_CONFIRMATIONS};
 
     use super::*;
 
@@ -219,4 +372,94 @@ mod tests {
         .unwrap();
         assert_eq!(balances.spendable, 0);
     }
+
+    #[tokio_shared_rt::test(flavor = "multi_thread")]
+    async fn test_get_unspent_notes() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+        setup.sync().await;
+
+        let notes = get_unspent_notes(
+            &setup.db_init,
+            account_id,
+            MIN_CONFIRMATIONS.try_into().unwrap(),
+        )
+        .unwrap();
+        assert!(notes.is_empty());
+    }
+
+    #[tokio_shared_rt::test(flavor = "multi_thread")]
+    async fn test_get_unspent_notes_classification() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+        let tip = setup.sync().await.tip_height;
+
+        let spendable = insert_received_note(
+            &setup.db_init,
+            account_id,
+            Some(tip - 10),
+            100_000,
+            Some(
+                "thanks"
+                    .parse::<Memo>()
+                    .unwrap()
+                    .encode()
+                    .as_slice()
+                    .to_vec(),
+            ),
+        );
+        let immature = insert_received_note(
+            &setup.db_init,
+            account_id,
+            Some(tip),
+            100_000,
+            Some(MemoBytes::empty().as_slice().to_vec()),
+        );
+        let unconfirmed = insert_received_note(&setup.db_init, account_id, None, 100_000, None);
+        let dust = insert_received_note(&setup.db_init, account_id, Some(tip - 10), 100, None);
+
+        let notes = get_unspent_notes(
+            &setup.db_init,
+            account_id,
+            MIN_CONFIRMATIONS.try_into().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(notes.len(), 4);
+        let note = |txid: &Vec<u8>| notes.iter().find(|n| &n.txid == txid).unwrap();
+
+        let n = note(&spendable);
+        assert_eq!(n.spendability, NoteSpendability::Spendable);
+        assert_eq!(n.pool, Pool::Sapling);
+        assert_eq!(n.value, 100_000);
+        assert_eq!(n.mined_height, Some(tip - 10));
+        assert_eq!(n.confirmations, Some(11));
+        assert!(n.has_memo);
+        assert!(!n.is_change);
+
+        let n = note(&immature);
+        assert_eq!(n.spendability, NoteSpendability::Immature);
+        assert_eq!(n.confirmations, Some(1));
+        assert!(!n.has_memo);
+
+        let n = note(&unconfirmed);
+        assert_eq!(n.spendability, NoteSpendability::Unconfirmed);
+        assert_eq!(n.mined_height, None);
+        assert_eq!(n.confirmations, None);
+        assert!(!n.has_memo);
+
+        let n = note(&dust);
+        assert_eq!(n.spendability, NoteSpendability::Dust);
+        assert_eq!(n.confirmations, Some(11));
+
+        let balances = get_user_balances(
+            &setup.db_init,
+            account_id,
+            MIN_CONFIRMATIONS.try_into().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(balances.spendable, 100_000);
+        assert_eq!(balances.immature_income, 100_000);
+        assert_eq!(balances.incoming, 100_000);
+        assert_eq!(balances.dust, 100);
+    }
 }
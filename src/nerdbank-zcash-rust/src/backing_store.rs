@@ -1,10 +1,15 @@
-use std::{fs, path::Path};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
-use rusqlite::Connection;
-use secrecy::SecretVec;
+use rusqlite::{named_params, Connection};
+use secrecy::{ExposeSecret, SecretString, SecretVec};
 use tonic::transport::Channel;
 use zcash_client_backend::{
-    data_api::{AccountBirthday, AccountPurpose, WalletWrite},
+    data_api::{Account as _, AccountBirthday, AccountPurpose, WalletRead, WalletWrite},
     keys::UnifiedSpendingKey,
     proto::service::{self, compact_tx_streamer_client::CompactTxStreamerClient},
 };
@@ -12,14 +17,21 @@ use zcash_client_sqlite::{
     wallet::{init::init_wallet_db, Account},
     AccountId, WalletDb,
 };
-use zcash_keys::{address::UnifiedAddress, keys::UnifiedFullViewingKey};
+use zcash_keys::{
+    address::UnifiedAddress,
+    keys::{UnifiedAddressRequest, UnifiedFullViewingKey},
+};
 use zcash_primitives::{consensus::Network, zip32::DiversifierIndex};
 
-use crate::{block_source::BlockCache, error::Error};
+use crate::{
+    block_source::BlockCache, error::Error, migrations::init_app_tables,
+    resilience::ResilientClient,
+};
 
 pub(crate) struct Db {
     pub(crate) data: WalletDb<Connection, Network>,
     pub(crate) blocks: BlockCache,
+    _open: OpenDatabase,
 }
 
 impl Db {
@@ -50,7 +62,9 @@ impl Db {
                 height: birthday - 1,
                 ..Default::default()
             };
-            let treestate = client.get_tree_state(request).await?.into_inner();
+            let treestate = ResilientClient::from(client.clone())
+                .get_tree_state(request)
+                .await?;
             AccountBirthday::from_treestate(treestate, None)?
         };
 
@@ -74,7 +88,9 @@ impl Db {
                 height: birthday - 1,
                 ..Default::default()
             };
-            let treestate = client.get_tree_state(request).await?.into_inner();
+            let treestate = ResilientClient::from(client.clone())
+                .get_tree_state(request)
+                .await?;
             AccountBirthday::from_treestate(treestate, None)?
         };
 
@@ -90,6 +106,236 @@ impl Db {
             .data
             .put_address_with_diversifier_index(&account_id, diversifier_index)?)
     }
+
+    /// Generates the account's address at the given diversifier index with just the requested receivers.
+    ///
+    /// Unlike [`Db::add_diversifier`], this does not record the address in the wallet.
+    pub(crate) fn address_with_receivers(
+        &self,
+        account_id: AccountId,
+        diversifier_index: DiversifierIndex,
+        request: UnifiedAddressRequest,
+    ) -> Result<UnifiedAddress, Error> {
+        let account = self
+            .data
+            .get_account(account_id)?
+            .ok_or_else(|| Error::InvalidArgument("Unknown account.".to_string()))?;
+        let ufvk = account.ufvk().ok_or_else(|| {
+            Error::InvalidArgument("The account has no unified full viewing key.".to_string())
+        })?;
+        Ok(ufvk.address(diversifier_index, request)?)
+    }
+
+    /// Generates the account's next address at a diversifier index beyond any it has handed out before.
+    pub(crate) fn next_unused_address(
+        &mut self,
+        account_id: AccountId,
+        request: UnifiedAddressRequest,
+    ) -> Result<UnifiedAddress, Error> {
+        self.data
+            .get_next_available_address(account_id, request)?
+            .ok_or_else(|| Error::InvalidArgument("Unknown account.".to_string()))
+    }
+}
+
+lazy_static! {
+    /// The passphrases for encrypted wallet databases, keyed by [`database_key`].
+    static ref PASSPHRASES: Mutex<HashMap<PathBuf, SecretString>> = Mutex::new(HashMap::new());
+
+    /// The number of [`Db`]s open on each wallet database, keyed by [`database_key`].
+    /// This lock is held while a database is rekeyed, so no [`Db`] can open during that time.
+    static ref OPEN_DATABASES: Mutex<HashMap<PathBuf, usize>> = Mutex::new(HashMap::new());
+}
+
+/// Identifies a database file by its canonical path,
+/// so that different spellings of the same path share a passphrase.
+///
+/// The file itself need not exist yet, but its directory should.
+fn database_key<P: AsRef<Path>>(data_file: P) -> PathBuf {
+    let data_file = data_file.as_ref();
+    match (data_file.parent(), data_file.file_name()) {
+        (Some(dir), Some(name)) => {
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            fs::canonicalize(dir)
+                .map(|dir| dir.join(name))
+                .unwrap_or_else(|_| data_file.to_owned())
+        }
+        _ => data_file.to_owned(),
+    }
+}
+
+/// Records that a [`Db`] is open on a wallet database, for as long as this lives.
+struct OpenDatabase(PathBuf);
+
+impl OpenDatabase {
+    fn new(key: PathBuf) -> Self {
+        *OPEN_DATABASES
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default() += 1;
+        OpenDatabase(key)
+    }
+}
+
+impl Drop for OpenDatabase {
+    fn drop(&mut self) {
+        let mut open = OPEN_DATABASES.lock().unwrap();
+        if let Some(count) = open.get_mut(&self.0) {
+            *count -= 1;
+            if *count == 0 {
+                open.remove(&self.0);
+            }
+        }
+    }
+}
+
+/// Sets or (when `passphrase` is `None`) clears the passphrase that is used
+/// to open the database at the given path for the rest of this session.
+pub(crate) fn set_passphrase<P: AsRef<Path>>(data_file: P, passphrase: Option<String>) {
+    let key = database_key(data_file);
+    let mut passphrases = PASSPHRASES.lock().unwrap();
+    match passphrase {
+        Some(passphrase) => {
+            passphrases.insert(key, SecretString::new(passphrase));
+        }
+        None => {
+            passphrases.remove(&key);
+        }
+    }
+}
+
+/// Applies the passphrase of one database to another, such as a scratch copy of it.
+pub(crate) fn copy_passphrase<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) {
+    set_passphrase(to, get_passphrase(from).map(|p| p.expose_secret().clone()));
+}
+
+/// Deletes a database file along with its `-wal` and `-shm` side files, where they exist.
+pub(crate) fn remove_database_files<P: AsRef<Path>>(data_file: P) -> std::io::Result<()> {
+    let data_file = data_file.as_ref();
+    for suffix in ["", "-wal", "-shm"] {
+        let path = PathBuf::from(format!("{}{}", data_file.display(), suffix));
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn get_passphrase<P: AsRef<Path>>(data_file: P) -> Option<SecretString> {
+    PASSPHRASES
+        .lock()
+        .unwrap()
+        .get(&database_key(data_file))
+        .map(|p| SecretString::new(p.expose_secret().clone()))
+}
+
+/// Opens a connection to a wallet database, applying its passphrase if it is encrypted.
+///
+/// Every connection to a wallet database should be opened with this function
+/// rather than `Connection::open`.
+pub(crate) fn open_connection<P: AsRef<Path>>(data_file: P) -> Result<Connection, Error> {
+    let conn = Connection::open(&data_file)?;
+    if let Some(passphrase) = get_passphrase(&data_file) {
+        conn.pragma_update(None, "key", passphrase.expose_secret())?;
+
+        // SQLCipher doesn't check the key until the database is first read.
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+            .map_err(|_| {
+                Error::InvalidArgument(
+                    "The passphrase does not unlock the wallet database.".to_string(),
+                )
+            })?;
+    }
+
+    Ok(conn)
+}
+
+/// Changes the passphrase of the wallet database,
+/// encrypting or decrypting it as necessary.
+///
+/// Pass `None` for `new_passphrase` to store the database in plaintext.
+///
+/// This fails if the wallet is in use, such as by a sync or send, since adding or removing
+/// encryption replaces the database file, and any connection still open on the old file would be lost.
+pub(crate) fn rekey<P: AsRef<Path>>(
+    data_file: P,
+    new_passphrase: Option<String>,
+) -> Result<(), Error> {
+    // Hold the lock throughout so that no Db can open on the file until it is rekeyed.
+    let open = OPEN_DATABASES.lock().unwrap();
+    if open.contains_key(&database_key(&data_file)) {
+        return Err(Error::InvalidArgument(
+            "The wallet is in use. Wait for or cancel other operations on it before changing its passphrase."
+                .to_string(),
+        ));
+    }
+
+    let conn = open_connection(&data_file)?;
+    match (get_passphrase(&data_file), &new_passphrase) {
+        (Some(_), Some(new_passphrase)) => {
+            conn.pragma_update(None, "rekey", new_passphrase)?;
+        }
+        (None, None) => {}
+        _ => {
+            // SQLCipher can only add or remove encryption by exporting to a new database.
+            let exported = PathBuf::from(format!("{}.rekey", data_file.as_ref().display()));
+            if exported.exists() {
+                fs::remove_file(&exported)?;
+            }
+
+            // Keep other connections (including those in other processes) out of the database
+            // until the new file replaces it. SQLite can't attach the export target inside a transaction,
+            // so the exclusive lock is retained with the exclusive locking mode instead.
+            conn.pragma_update(None, "locking_mode", "EXCLUSIVE")?;
+            conn.execute_batch("BEGIN EXCLUSIVE; COMMIT;")?;
+            export_database(&conn, &exported, new_passphrase.as_deref().unwrap_or(""))?;
+
+            // Close the old database and discard its side files so they can't be applied to the new one.
+            drop(conn);
+            for suffix in ["-wal", "-shm"] {
+                let side_file =
+                    PathBuf::from(format!("{}{}", data_file.as_ref().display(), suffix));
+                if side_file.exists() {
+                    fs::remove_file(&side_file)?;
+                }
+            }
+            fs::rename(&exported, &data_file)?;
+        }
+    }
+    drop(open);
+
+    set_passphrase(&data_file, new_passphrase);
+    Ok(())
+}
+
+/// Copies the entire database behind `conn` to a new file, encrypted with `key`
+/// (or in plaintext when `key` is empty).
+///
+/// The copy is made in one transaction, so it is a consistent snapshot even while other connections write.
+pub(crate) fn export_database(conn: &Connection, path: &Path, key: &str) -> Result<(), Error> {
+    // SQLite can't attach a database inside a transaction, so the transaction starts after it.
+    conn.execute(
+        "ATTACH DATABASE :path AS exported KEY :key",
+        named_params! {
+            ":path": path.to_string_lossy(),
+            ":key": key,
+        },
+    )?;
+    let result = conn.execute_batch("BEGIN").and_then(|()| {
+        conn.query_row("SELECT sqlcipher_export('exported')", [], |_| Ok(()))?;
+        conn.execute_batch("COMMIT")
+    });
+    if result.is_err() && !conn.is_autocommit() {
+        conn.execute_batch("ROLLBACK")?;
+    }
+    conn.execute("DETACH DATABASE exported", [])?;
+    result?;
+    Ok(())
 }
 
 fn get_db_internal<P: AsRef<Path>>(
@@ -103,27 +349,85 @@ fn get_db_internal<P: AsRef<Path>>(
         }
     }
 
-    let mut data = WalletDb::for_path(data_file, network)?;
+    let open = OpenDatabase::new(database_key(&data_file));
+    let conn = open_connection(&data_file)?;
+    rusqlite::vtab::array::load_module(&conn)?;
+    let mut data = WalletDb::from_connection(conn, network);
 
     if init {
         init_wallet_db(&mut data, None)?;
+        init_app_tables(&mut open_connection(&data_file)?)?;
     }
 
     Ok(Db {
         data,
         blocks: BlockCache::new(),
+        _open: open,
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use matches::assert_matches;
     use testdir::testdir;
 
+    use super::*;
+
     #[tokio_shared_rt::test]
     async fn test_init() {
         let wallet_dir = testdir!();
         let data_file = wallet_dir.join("wallet.sqlite");
         Db::init(data_file, Network::TestNetwork).unwrap();
     }
+
+    #[tokio_shared_rt::test]
+    async fn test_encrypted_database() {
+        let wallet_dir = testdir!();
+        let data_file = wallet_dir.join("wallet.sqlite");
+        set_passphrase(&data_file, Some("correct horse".to_string()));
+        Db::init(&data_file, Network::TestNetwork).unwrap();
+
+        // Without the passphrase, the database cannot be read.
+        let plaintext = Connection::open(&data_file).unwrap();
+        assert!(plaintext
+            .query_row("SELECT count(*) FROM accounts", [], |_| Ok(()))
+            .is_err());
+        drop(plaintext);
+
+        rekey(&data_file, Some("battery staple".to_string())).unwrap();
+        Db::load(&data_file, Network::TestNetwork).unwrap();
+
+        rekey(&data_file, None).unwrap();
+        let plaintext = Connection::open(&data_file).unwrap();
+        plaintext
+            .query_row("SELECT count(*) FROM accounts", [], |_| Ok(()))
+            .unwrap();
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_passphrase_applies_to_equivalent_paths() {
+        let wallet_dir = testdir!();
+        let data_file = wallet_dir.join("wallet.sqlite");
+        set_passphrase(&data_file, Some("correct horse".to_string()));
+        Db::init(&data_file, Network::TestNetwork).unwrap();
+
+        let other_spelling = wallet_dir.join(".").join("wallet.sqlite");
+        Db::load(&other_spelling, Network::TestNetwork).unwrap();
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_rekey_rejects_open_database() {
+        let wallet_dir = testdir!();
+        let data_file = wallet_dir.join("wallet.sqlite");
+        let db = Db::init(&data_file, Network::TestNetwork).unwrap();
+
+        assert_matches!(
+            rekey(&data_file, Some("correct horse".to_string())),
+            Err(Error::InvalidArgument(_))
+        );
+
+        drop(db);
+        rekey(&data_file, Some("correct horse".to_string())).unwrap();
+        Db::load(&data_file, Network::TestNetwork).unwrap();
+    }
 }
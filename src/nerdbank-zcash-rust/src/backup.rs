@@ -0,0 +1,131 @@
+use std::{fs, path::Path};
+
+use rusqlite::Connection;
+
+use crate::{
+    backing_store::{export_database, open_connection, set_passphrase},
+    error::Error,
+    interop::DbInit,
+};
+
+/// Writes the entire wallet to a single new file, encrypted with `passphrase`.
+///
+/// The backup includes the accounts (with their viewing keys and ZIP-32 indices),
+/// their birthdays, address and transaction labels, memos and sync progress.
+/// The wallet may be in use (e.g. by a sync) while the backup is taken,
+/// though writes to it wait until the backup is written.
+///
+/// An error is returned if a file already exists at `path`.
+pub fn export_backup<P: AsRef<Path>>(
+    config: &DbInit,
+    path: P,
+    passphrase: &str,
+) -> Result<(), Error> {
+    if passphrase.is_empty() {
+        return Err(Error::InvalidArgument(
+            "A passphrase is required to protect the backup.".to_string(),
+        ));
+    }
+    if path.as_ref().exists() {
+        return Err(Error::InvalidArgument(
+            "A file already exists at the path to back up to.".to_string(),
+        ));
+    }
+
+    // The export reads the wallet in a single transaction, so the backup is a consistent snapshot
+    // and no unencrypted copy is ever written.
+    let source = open_connection(&config.data_file)?;
+    let result = export_database(&source, path.as_ref(), passphrase);
+    if result.is_err() {
+        let _ = fs::remove_file(&path);
+    }
+
+    result
+}
+
+/// Restores a backup made by [`export_backup`] to a new wallet database.
+///
+/// The restored database is encrypted with `new_passphrase`, or stored in plaintext when it is `None`.
+/// The restored wallet should be opened with `init` before it is used,
+/// so that its schema is brought up to date.
+pub fn restore_backup<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    passphrase: &str,
+    new_data_file: Q,
+    new_passphrase: Option<String>,
+) -> Result<(), Error> {
+    if new_data_file.as_ref().exists() {
+        return Err(Error::InvalidArgument(
+            "A wallet already exists at the path to restore to.".to_string(),
+        ));
+    }
+    if let Some(data_dir) = new_data_file.as_ref().parent() {
+        fs::create_dir_all(data_dir)?;
+    }
+
+    let archive = Connection::open(&path)?;
+    archive.pragma_update(None, "key", passphrase)?;
+    archive
+        .query_row("SELECT count(*) FROM accounts", [], |_| Ok(()))
+        .map_err(|_| {
+            Error::InvalidArgument(
+                "The passphrase does not unlock the backup, or the file is not a wallet backup."
+                    .to_string(),
+            )
+        })?;
+
+    export_database(
+        &archive,
+        new_data_file.as_ref(),
+        new_passphrase.as_deref().unwrap_or(""),
+    )?;
+    set_passphrase(&new_data_file, new_passphrase);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use testdir::testdir;
+    use zcash_client_backend::data_api::WalletRead;
+
+    use crate::{backing_store::Db, test_constants::setup_test};
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_backup_round_trip() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+
+        let backup_dir = testdir!();
+        let backup_file = backup_dir.join("wallet.backup");
+        export_backup(&setup.db_init, &backup_file, "backup passphrase").unwrap();
+
+        let restored_file = backup_dir.join("restored.sqlite");
+        assert_matches!(
+            restore_backup(&backup_file, "wrong passphrase", &restored_file, None),
+            Err(Error::InvalidArgument(_))
+        );
+        restore_backup(&backup_file, "backup passphrase", &restored_file, None).unwrap();
+
+        let restored = Db::init(&restored_file, setup.network).unwrap();
+        assert!(restored.data.get_account(account_id).unwrap().is_some());
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_backup_keeps_existing_file() {
+        let setup = setup_test().await;
+        let backup_dir = testdir!();
+        let backup_file = backup_dir.join("wallet.backup");
+        fs::write(&backup_file, b"precious").unwrap();
+
+        assert_matches!(
+            export_backup(&setup.db_init, &backup_file, "backup passphrase"),
+            Err(Error::InvalidArgument(_))
+        );
+        assert_eq!(fs::read(&backup_file).unwrap(), b"precious");
+        assert_eq!(fs::read_dir(&backup_dir).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,755 @@
+use std::{collections::VecDeque, num::NonZeroU32};
+
+use http::Uri;
+use nonempty::NonEmpty;
+use rusqlite::{named_params, Connection, OptionalExtension};
+use zcash_client_backend::{
+    data_api::{
+        wallet::{create_proposed_transactions, input_selection::GreedyInputSelectorError},
+        Account, WalletRead,
+    },
+    fees::{zip317::SingleOutputChangeStrategy, ChangeStrategy},
+    keys::UnifiedSpendingKey,
+    proposal::Proposal,
+    wallet::OvkPolicy,
+};
+use zcash_client_sqlite::ReceivedNoteId;
+use zcash_primitives::{
+    consensus::{BlockHeight, Network},
+    transaction::{
+        fees::zip317::{FeeRule, MARGINAL_FEE},
+        TxId,
+    },
+};
+
+use crate::{
+    backing_store::{open_connection, Db},
+    error::Error,
+    grpc::get_isolated_client,
+    interop::{DbInit, TransactionSendDetail},
+    migrations::init_app_tables,
+    prover::get_prover,
+    resilience::ResilientClient,
+    send::{create_send_proposal, transmit_transaction},
+    sql_statements::{
+        ABANDON_BATCH_PAYMENTS, DELETE_BATCH_TRANSACTIONS, GET_BATCH_ACCOUNT,
+        GET_BATCH_PAYING_TRANSACTION, GET_BATCH_PAYMENTS, GET_UNBROADCAST_BATCH_TRANSACTIONS,
+        INSERT_BATCH, INSERT_BATCH_PAYMENT, INSERT_BATCH_TRANSACTION, SET_BATCH_PAYMENT_ERROR,
+        SET_BATCH_PAYMENT_TRANSACTION, SET_BATCH_TRANSACTION_BROADCAST,
+    },
+    tor::account_isolation,
+};
+
+/// The most ZIP-317 logical actions to put in one transaction.
+///
+/// An Orchard action adds about 3 KB to a transaction with its share of the proof,
+/// so this keeps transactions under the 100,000 bytes that nodes relay (zcashd's `MAX_STANDARD_TX_SIZE`).
+const MAX_LOGICAL_ACTIONS_PER_TRANSACTION: u64 = 32;
+
+/// The error code with which zcashd rejects a transaction that is invalid or against its policy (`RPC_VERIFY_REJECTED`),
+/// so that sending it again will not help.
+const RPC_VERIFY_REJECTED: i32 = -26;
+
+/// Controls how `send_batch` splits payments into transactions.
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// The most payments to include in one transaction.
+    pub max_payments_per_transaction: u32,
+    /// The highest fee (in zatoshis) to pay for one transaction.
+    /// Payments that would make a transaction cost more are split into smaller transactions.
+    pub max_fee_per_transaction: Option<u64>,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            max_payments_per_transaction: 50,
+            max_fee_per_transaction: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchPaymentStatus {
+    /// No transaction has been created for the payment yet.
+    Pending,
+    /// The transaction that pays it was created, but the server has not accepted it yet.
+    Created,
+    /// The transaction that pays it was accepted by the server.
+    Sent,
+    /// The last attempt to create a transaction for the payment failed.
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchPayment {
+    /// The position of the payment in the batch.
+    pub index: u32,
+    pub recipient: String,
+    pub value: u64,
+    pub status: BatchPaymentStatus,
+    pub txid: Option<Vec<u8>>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    pub batch_id: String,
+    pub payments: Vec<BatchPayment>,
+}
+
+/// Pays many recipients, splitting the payments across as many transactions as it takes
+/// to respect the limits in `options` and keep each transaction small enough for nodes to relay.
+///
+/// The progress of the batch is saved in the wallet, so calling this again with the same `batch_id`
+/// (and the same payments) resumes an interrupted or partially failed batch: transactions that were
+/// created but not accepted by the server are broadcast again, and payments that failed are retried.
+/// Payments whose transaction expired or was rejected outright are made again with a new transaction.
+/// Payments that were already made are never repeated.
+///
+/// Since change from earlier transactions in the batch is not spendable until it is confirmed,
+/// some payments may fail for lack of funds and succeed when the batch is resumed later.
+pub async fn send_batch(
+    config: &DbInit,
+    server_uri: Uri,
+    usk: &UnifiedSpendingKey,
+    batch_id: &str,
+    details: Vec<TransactionSendDetail>,
+    options: &BatchOptions,
+) -> Result<BatchResult, Error> {
+    if options.max_payments_per_transaction == 0 {
+        return Err(Error::InvalidArgument(
+            "At least one payment per transaction must be allowed.".to_string(),
+        ));
+    }
+
+    let network: Network = config.network.into();
+    let min_confirmations = NonZeroU32::try_from(config.min_confirmations)
+        .map_err(|_| Error::InvalidArgument("A positive integer is required.".to_string()))?;
+    let mut db = Db::load(&config.data_file, network)?;
+    let mut conn = open_connection(&config.data_file)?;
+    init_app_tables(&mut conn)?;
+    let ufvk = usk.to_unified_full_viewing_key();
+    let account_id = db
+        .data
+        .get_account_for_ufvk(&ufvk)?
+        .ok_or(Error::KeyNotRecognized)?
+        .id();
+    record_batch(&mut conn, batch_id, u32::from(account_id), &details)?;
+
+    // Finish what an earlier attempt started before paying anyone else.
+    let isolation = account_isolation(&network, &ufvk);
+    if !broadcast_created(&mut conn, batch_id, &server_uri, &isolation, &mut db).await? {
+        return get_batch_result(&conn, batch_id);
+    }
+
+    let unpaid = get_batch_result(&conn, batch_id)?
+        .payments
+        .into_iter()
+        .filter(|p| p.txid.is_none())
+        .map(|p| p.index)
+        .collect::<Vec<_>>();
+    let mut chunks = unpaid
+        .chunks(options.max_payments_per_transaction as usize)
+        .map(|c| c.to_vec())
+        .collect::<VecDeque<_>>();
+    if chunks.is_empty() {
+        return get_batch_result(&conn, batch_id);
+    }
+
+    let prover = get_prover()?;
+    while let Some(mut chunk) = chunks.pop_front() {
+        let chunk_details = chunk
+            .iter()
+            .map(|i| details[*i as usize].clone())
+            .collect::<Vec<_>>();
+        let proposal = match create_send_proposal(
+            &mut db,
+            network,
+            &ufvk,
+            min_confirmations,
+            chunk_details,
+            None,
+        ) {
+            Ok(proposal) => proposal,
+            Err(e) => {
+                record_failure(&conn, batch_id, &chunk, &e)?;
+                continue;
+            }
+        };
+
+        let fee: u64 = proposal
+            .steps()
+            .iter()
+            .map(|step| u64::from(step.balance().fee_required()))
+            .sum();
+        let actions = max_logical_actions(&proposal);
+        let too_costly = options
+            .max_fee_per_transaction
+            .filter(|max_fee| fee > *max_fee);
+        if too_costly.is_some() || actions > MAX_LOGICAL_ACTIONS_PER_TRANSACTION {
+            if chunk.len() > 1 {
+                let second_half = chunk.split_off(chunk.len() / 2);
+                chunks.push_front(second_half);
+                chunks.push_front(chunk);
+            } else {
+                let e = match too_costly {
+                    Some(max_fee) => Error::InvalidArgument(format!(
+                        "The fee of {} exceeds the limit of {}.",
+                        fee, max_fee
+                    )),
+                    None => Error::InvalidArgument(format!(
+                        "The payment takes {} logical actions, more than the {} that fit in one transaction.",
+                        actions, MAX_LOGICAL_ACTIONS_PER_TRANSACTION
+                    )),
+                };
+                record_failure(&conn, batch_id, &chunk, &e)?;
+            }
+            continue;
+        }
+
+        let txids = match create_proposed_transactions::<
+            _,
+            _,
+            GreedyInputSelectorError<
+                <SingleOutputChangeStrategy as ChangeStrategy>::Error,
+                ReceivedNoteId,
+            >,
+            _,
+            _,
+        >(
+            &mut db.data,
+            &network,
+            &*prover,
+            &*prover,
+            usk,
+            OvkPolicy::Sender,
+            &proposal,
+        ) {
+            Ok(txids) => txids,
+            Err(e) => {
+                record_failure(&conn, batch_id, &chunk, &e.into())?;
+                continue;
+            }
+        };
+
+        // The transactions are recorded before they are broadcast, so that they are never paid twice.
+        record_transactions(&mut conn, batch_id, &chunk, &txids)?;
+        if !broadcast_created(&mut conn, batch_id, &server_uri, &isolation, &mut db).await? {
+            break;
+        }
+    }
+
+    get_batch_result(&conn, batch_id)
+}
+
+/// Gets the progress of a batch started by `send_batch`, if there is one with the given ID.
+pub fn get_batch(config: &DbInit, batch_id: &str) -> Result<Option<BatchResult>, Error> {
+    let mut conn = open_connection(&config.data_file)?;
+    init_app_tables(&mut conn)?;
+    let exists = conn
+        .query_row(GET_BATCH_ACCOUNT, named_params! { ":id": batch_id }, |_| {
+            Ok(())
+        })
+        .optional()?
+        .is_some();
+    if !exists {
+        return Ok(None);
+    }
+
+    Ok(Some(get_batch_result(&conn, batch_id)?))
+}
+
+/// Saves a new batch, or checks that a batch being resumed makes the same payments from the same account.
+fn record_batch(
+    conn: &mut Connection,
+    batch_id: &str,
+    account_id: u32,
+    details: &[TransactionSendDetail],
+) -> Result<(), Error> {
+    let tx = conn.transaction()?;
+    let existing_account: Option<u32> = tx
+        .query_row(
+            GET_BATCH_ACCOUNT,
+            named_params! { ":id": batch_id },
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match existing_account {
+        Some(existing_account) => {
+            let mismatch = || {
+                Error::InvalidArgument(format!(
+                    "The batch {} was started with a different account or payments.",
+                    batch_id
+                ))
+            };
+            if existing_account != account_id {
+                return Err(mismatch());
+            }
+
+            let recorded = tx
+                .prepare(GET_BATCH_PAYMENTS)?
+                .query_map(named_params! { ":batch_id": batch_id }, |row| {
+                    Ok(TransactionSendDetail {
+                        recipient: row.get("recipient")?,
+                        value: row.get("value")?,
+                        memo: row.get("memo")?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            let same_payments = recorded.len() == details.len()
+                && recorded.iter().zip(details).all(|(a, b)| {
+                    a.recipient == b.recipient && a.value == b.value && a.memo == b.memo
+                });
+            if !same_payments {
+                return Err(mismatch());
+            }
+        }
+        None => {
+            tx.execute(
+                INSERT_BATCH,
+                named_params! { ":id": batch_id, ":account_id": account_id },
+            )?;
+            let mut insert = tx.prepare(INSERT_BATCH_PAYMENT)?;
+            for (index, detail) in details.iter().enumerate() {
+                insert.execute(named_params! {
+                    ":batch_id": batch_id,
+                    ":payment_index": index as u32,
+                    ":recipient": detail.recipient,
+                    ":value": detail.value,
+                    ":memo": detail.memo,
+                })?;
+            }
+        }
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Records the transactions created for some payments, the last of which pays them.
+fn record_transactions(
+    conn: &mut Connection,
+    batch_id: &str,
+    payments: &[u32],
+    txids: &NonEmpty<TxId>,
+) -> Result<(), Error> {
+    let tx = conn.transaction()?;
+    let mut transaction_id = 0i64;
+    for txid in txids {
+        transaction_id = tx.query_row(
+            INSERT_BATCH_TRANSACTION,
+            named_params! { ":batch_id": batch_id, ":txid": txid.as_ref() },
+            |row| row.get(0),
+        )?;
+    }
+    for index in payments {
+        tx.execute(
+            SET_BATCH_PAYMENT_TRANSACTION,
+            named_params! {
+                ":transaction_id": transaction_id,
+                ":batch_id": batch_id,
+                ":payment_index": index,
+            },
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn record_failure(
+    conn: &Connection,
+    batch_id: &str,
+    payments: &[u32],
+    error: &Error,
+) -> Result<(), Error> {
+    for index in payments {
+        conn.execute(
+            SET_BATCH_PAYMENT_ERROR,
+            named_params! {
+                ":error": error.to_string(),
+                ":batch_id": batch_id,
+                ":payment_index": index,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Broadcasts the batch's transactions that the server has not accepted yet, in the order they were created.
+///
+/// A transaction that can never be mined, because it expired, was rejected outright or is no longer in the wallet,
+/// is abandoned so that its payments are made again by a new transaction.
+///
+/// Returns `false` if one was not accepted for another reason, in which case the rest are left for a later attempt
+/// since they may depend on it.
+async fn broadcast_created(
+    conn: &mut Connection,
+    batch_id: &str,
+    server_uri: &Uri,
+    isolation: &Option<String>,
+    db: &mut Db,
+) -> Result<bool, Error> {
+    let created = conn
+        .prepare(GET_UNBROADCAST_BATCH_TRANSACTIONS)?
+        .query_map(named_params! { ":batch_id": batch_id }, |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (id, txid) in created {
+        let txid = TxId::from_bytes(
+            txid.try_into()
+                .map_err(|_| Error::Internal("Invalid txid in batch.".to_string()))?,
+        );
+        let Some(expiry_height) = db.data.get_transaction(txid)?.map(|tx| tx.expiry_height())
+        else {
+            abandon_transaction(
+                conn,
+                batch_id,
+                id,
+                "The wallet no longer has the transaction that paid this.",
+            )?;
+            continue;
+        };
+
+        // Duplicates of a transaction sent by an earlier, interrupted attempt count as sent.
+        let Err(error) =
+            transmit_transaction(txid, server_uri.clone(), isolation.clone(), &mut db.data).await
+        else {
+            conn.execute(
+                SET_BATCH_TRANSACTION_BROADCAST,
+                named_params! { ":broadcast": true, ":error": None::<String>, ":id": id },
+            )?;
+            continue;
+        };
+
+        if can_never_be_mined(&error, expiry_height, server_uri, isolation).await? {
+            abandon_transaction(
+                conn,
+                batch_id,
+                id,
+                &format!(
+                    "The transaction that paid this will never be mined: {}",
+                    error
+                ),
+            )?;
+            continue;
+        }
+
+        conn.execute(
+            SET_BATCH_TRANSACTION_BROADCAST,
+            named_params! { ":broadcast": false, ":error": error.to_string(), ":id": id },
+        )?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Checks whether a transaction that failed to broadcast with the given error was rejected outright,
+/// or has expired so that no block can include it.
+async fn can_never_be_mined(
+    error: &Error,
+    expiry_height: BlockHeight,
+    server_uri: &Uri,
+    isolation: &Option<String>,
+) -> Result<bool, Error> {
+    if matches!(error, Error::SendFailed { code, .. } if *code == RPC_VERIFY_REJECTED) {
+        return Ok(true);
+    }
+
+    // A transaction with no expiry height never expires.
+    if expiry_height == BlockHeight::from(0) {
+        return Ok(false);
+    }
+
+    // The next block is the first that could include the transaction.
+    let client =
+        ResilientClient::from(get_isolated_client(server_uri.clone(), isolation.clone()).await?);
+    let tip = client.get_latest_block().await?.height;
+    Ok(tip >= u64::from(u32::from(expiry_height)))
+}
+
+/// Drops a transaction that will never be mined, along with any later ones created from the same proposal,
+/// and returns the payments they would have made to the pending payments.
+fn abandon_transaction(
+    conn: &mut Connection,
+    batch_id: &str,
+    id: i64,
+    reason: &str,
+) -> Result<(), Error> {
+    let tx = conn.transaction()?;
+    let paying: Option<i64> = tx.query_row(
+        GET_BATCH_PAYING_TRANSACTION,
+        named_params! { ":batch_id": batch_id, ":id": id },
+        |row| row.get(0),
+    )?;
+    let paying = paying.unwrap_or(id);
+    tx.execute(
+        ABANDON_BATCH_PAYMENTS,
+        named_params! {
+            ":error": reason,
+            ":batch_id": batch_id,
+            ":transaction_id": paying,
+        },
+    )?;
+    tx.execute(
+        DELETE_BATCH_TRANSACTIONS,
+        named_params! { ":batch_id": batch_id, ":first": id, ":last": paying },
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Gets the most ZIP-317 logical actions in any of a proposal's transactions, as implied by the fee each pays.
+fn max_logical_actions(proposal: &Proposal<FeeRule, ReceivedNoteId>) -> u64 {
+    proposal
+        .steps()
+        .iter()
+        .map(|step| u64::from(step.balance().fee_required()) / u64::from(MARGINAL_FEE))
+        .max()
+        .unwrap_or(0)
+}
+
+fn get_batch_result(conn: &Connection, batch_id: &str) -> Result<BatchResult, Error> {
+    let payments = conn
+        .prepare(GET_BATCH_PAYMENTS)?
+        .query_map(named_params! { ":batch_id": batch_id }, |row| {
+            let txid: Option<Vec<u8>> = row.get("txid")?;
+            let broadcast: Option<bool> = row.get("broadcast")?;
+            let error: Option<String> = row.get("error")?;
+            let transaction_error: Option<String> = row.get("transaction_error")?;
+            let (status, error) = match (&txid, broadcast) {
+                (Some(_), Some(true)) => (BatchPaymentStatus::Sent, None),
+                (Some(_), _) => (BatchPaymentStatus::Created, transaction_error),
+                (None, _) if error.is_some() => (BatchPaymentStatus::Failed, error),
+                (None, _) => (BatchPaymentStatus::Pending, None),
+            };
+            Ok(BatchPayment {
+                index: row.get("payment_index")?,
+                recipient: row.get("recipient")?,
+                value: row.get("value")?,
+                status,
+                txid,
+                error,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(BatchResult {
+        batch_id: batch_id.to_string(),
+        payments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use matches::assert_matches;
+    use zcash_client_backend::encoding::AddressCodec;
+
+    use crate::test_constants::{setup_test, VALID_SAPLING_TESTNET};
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_send_batch_without_funds() {
+        let mut setup = setup_test().await;
+        let account = setup.create_account().await.unwrap();
+        setup.sync().await;
+
+        let details = (1..=3)
+            .map(|i| TransactionSendDetail {
+                recipient: VALID_SAPLING_TESTNET.to_string(),
+                value: i * 1000,
+                memo: None,
+            })
+            .collect::<Vec<_>>();
+        let options = BatchOptions {
+            max_payments_per_transaction: 2,
+            ..Default::default()
+        };
+
+        let result = send_batch(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            &account.3,
+            "payroll",
+            details.clone(),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.payments.len(), 3);
+        assert!(result
+            .payments
+            .iter()
+            .all(|p| p.status == BatchPaymentStatus::Failed && p.error.is_some()));
+
+        // The batch is saved, and can only be resumed with the same payments.
+        let saved = get_batch(&setup.db_init, "payroll").unwrap().unwrap();
+        assert_eq!(saved.payments.len(), 3);
+        assert!(get_batch(&setup.db_init, "other").unwrap().is_none());
+        assert_matches!(
+            send_batch(
+                &setup.db_init,
+                setup.server_uri.clone(),
+                &account.3,
+                "payroll",
+                details[..2].to_vec(),
+                &options,
+            )
+            .await,
+            Err(Error::InvalidArgument(_))
+        );
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_send_batch_replaces_lost_transaction() {
+        let mut setup = setup_test().await;
+        let account = setup.create_account().await.unwrap();
+        setup.sync().await;
+
+        let details = (1..=2)
+            .map(|i| TransactionSendDetail {
+                recipient: VALID_SAPLING_TESTNET.to_string(),
+                value: i * 1000,
+                memo: None,
+            })
+            .collect::<Vec<_>>();
+        let options = BatchOptions::default();
+        send_batch(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            &account.3,
+            "lost",
+            details.clone(),
+            &options,
+        )
+        .await
+        .unwrap();
+
+        // Pretend the first payment was paid by a transaction that the wallet has since lost.
+        let mut conn = open_connection(&setup.data_file).unwrap();
+        record_transactions(
+            &mut conn,
+            "lost",
+            &[0],
+            &NonEmpty::new(TxId::from_bytes([7; 32])),
+        )
+        .unwrap();
+        let saved = get_batch(&setup.db_init, "lost").unwrap().unwrap();
+        assert_eq!(saved.payments[0].status, BatchPaymentStatus::Created);
+
+        // Resuming abandons the lost transaction and tries to pay again, instead of broadcasting it forever.
+        let result = send_batch(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            &account.3,
+            "lost",
+            details,
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.payments[0].status, BatchPaymentStatus::Failed);
+        assert!(result.payments[0].txid.is_none());
+        let transactions: u32 = conn
+            .query_row("SELECT COUNT(*) FROM batch_transactions", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(transactions, 0);
+    }
+
+    #[tokio_shared_rt::test]
+    #[ignore = "requires NERDBANK_ZCASH_FUNDED_TESTNET_SEED"]
+    async fn test_send_batch_splits_and_resumes() {
+        let mut setup = setup_test().await;
+        let (account_id, usk) = setup.create_funded_account().await;
+
+        // Pay the wallet itself, so that the test only spends fees.
+        let recipient = setup
+            .db
+            .data
+            .get_current_address(account_id)
+            .unwrap()
+            .unwrap()
+            .sapling()
+            .unwrap()
+            .encode(&setup.network);
+        let details = (1..=4)
+            .map(|i| TransactionSendDetail {
+                recipient: recipient.clone(),
+                value: i * 10_000,
+                memo: None,
+            })
+            .collect::<Vec<_>>();
+
+        // Paying one Sapling recipient with Sapling change takes 2 logical actions (10,000 ZATs),
+        // and each more recipient one more, so the limit forces one payment per transaction.
+        let max_fee = 10_000;
+        let options = BatchOptions {
+            max_payments_per_transaction: 4,
+            max_fee_per_transaction: Some(max_fee),
+        };
+
+        let result = send_batch(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            &usk,
+            "split",
+            details.clone(),
+            &options,
+        )
+        .await
+        .unwrap();
+
+        // Payments that are not paid now wait for change to be confirmed, or for more funds.
+        let paid = result
+            .payments
+            .iter()
+            .filter_map(|p| p.txid.clone())
+            .collect::<Vec<_>>();
+        assert!(
+            !paid.is_empty(),
+            "The funded wallet needs a spendable Sapling note to pay from."
+        );
+        assert_eq!(
+            paid.iter().collect::<HashSet<_>>().len(),
+            paid.len(),
+            "Each transaction should pay just one recipient."
+        );
+        let conn = open_connection(&setup.data_file).unwrap();
+        for txid in &paid {
+            let fee: Option<u64> = conn
+                .query_row(
+                    "SELECT fee FROM transactions WHERE txid = :txid",
+                    named_params! { ":txid": txid },
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert!(fee.is_some_and(|fee| fee <= max_fee));
+        }
+
+        // Resuming never pays a payment twice.
+        let resumed = send_batch(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            &usk,
+            "split",
+            details,
+            &options,
+        )
+        .await
+        .unwrap();
+        for (before, after) in result.payments.iter().zip(&resumed.payments) {
+            if before.txid.is_some() {
+                assert_eq!(before.txid, after.txid);
+            }
+        }
+    }
+}
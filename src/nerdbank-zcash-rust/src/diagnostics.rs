@@ -0,0 +1,176 @@
+use std::{
+    fmt::Write,
+    sync::{Arc, Once, RwLock},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    Event, Level, Metadata, Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+    Layer,
+};
+
+/// The severity of a log message.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+impl From<&Level> for LogLevel {
+    fn from(value: &Level) -> Self {
+        match *value {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        }
+    }
+}
+
+/// Receives the log messages and traces emitted by this library.
+pub trait LogSink: Send + Sync + std::fmt::Debug {
+    fn log(&self, level: LogLevel, target: String, message: String);
+}
+
+lazy_static! {
+    static ref LOG_SINK: RwLock<Option<(Arc<dyn LogSink>, LevelFilter)>> = RwLock::new(None);
+}
+
+static SUBSCRIBER_INSTALLED: Once = Once::new();
+
+/// Sends log messages at or above `level` to `sink`, replacing any sink set before.
+///
+/// Messages are prefixed with the spans they were emitted within (e.g. `sync:scan_blocks:`).
+/// A `None` sink stops forwarding log messages.
+pub fn set_log_sink(sink: Option<Box<dyn LogSink>>, level: LogLevel) {
+    SUBSCRIBER_INSTALLED.call_once(|| {
+        // Another subscriber may already be installed by the host (e.g. in tests), in which case it wins.
+        let _ = tracing_subscriber::registry()
+            .with(CallbackLayer)
+            .try_init();
+    });
+
+    *LOG_SINK.write().unwrap() = sink.map(|sink| (Arc::from(sink), level.into()));
+
+    // Callsites cache whether they are enabled, so they must be re-evaluated for the new level.
+    tracing::callsite::rebuild_interest_cache();
+}
+
+/// A tracing layer that forwards events to the installed `LogSink`.
+struct CallbackLayer;
+
+impl<S> Layer<S> for CallbackLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        LOG_SINK
+            .read()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|(_, level)| metadata.level() <= level)
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // The sink is called without the lock held, so that it may log or replace itself.
+        let Some(sink) = LOG_SINK
+            .read()
+            .unwrap()
+            .as_ref()
+            .filter(|(_, level)| event.metadata().level() <= level)
+            .map(|(sink, _)| sink.clone())
+        else {
+            return;
+        };
+
+        let mut message = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                let _ = write!(message, "{}:", span.name());
+            }
+            if !message.is_empty() {
+                message.push(' ');
+            }
+        }
+        event.record(&mut MessageVisitor(&mut message));
+
+        sink.log(
+            event.metadata().level().into(),
+            event.metadata().target().to_string(),
+            message,
+        );
+    }
+}
+
+/// Formats an event's message, followed by its other fields as `name=value` pairs.
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tracing::{info, info_span};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct TestSink(Arc<Mutex<Vec<(LogLevel, String)>>>);
+
+    impl LogSink for TestSink {
+        fn log(&self, level: LogLevel, _target: String, message: String) {
+            self.0.lock().unwrap().push((level, message));
+        }
+    }
+
+    #[test]
+    fn test_log_sink() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        set_log_sink(Some(Box::new(TestSink(messages.clone()))), LogLevel::Info);
+
+        info_span!("outer").in_scope(|| {
+            info!(height = 5, "Scanned block");
+            tracing::debug!("Too verbose");
+        });
+        set_log_sink(None, LogLevel::Info);
+        info!("After the sink was removed");
+
+        let messages = messages.lock().unwrap();
+        assert!(messages
+            .iter()
+            .any(|(level, m)| *level == LogLevel::Info && m == "outer: Scanned block height=5"));
+        assert!(!messages.iter().any(|(_, m)| m.contains("Too verbose")));
+        assert!(!messages.iter().any(|(_, m)| m.contains("After the sink")));
+    }
+}
@@ -0,0 +1,410 @@
+use std::{collections::BTreeMap, ops::Range, path::PathBuf};
+
+use http::Uri;
+use rusqlite::named_params;
+use secrecy::{ExposeSecret, SecretVec};
+use tokio_util::sync::CancellationToken;
+use zcash_client_backend::{
+    data_api::{AccountPurpose, WalletRead},
+    encoding::AddressCodec,
+    keys::UnifiedSpendingKey,
+    proto::service::{self, TransparentAddressBlockFilter},
+};
+use zcash_client_sqlite::AccountId;
+use zcash_keys::keys::UnifiedFullViewingKey;
+use zcash_primitives::{
+    consensus::{Network, NetworkUpgrade, Parameters},
+    legacy::keys::{IncomingViewingKey, NonHardenedChildIndex},
+    zip32,
+};
+
+use crate::{
+    backing_store::{copy_passphrase, open_connection, remove_database_files, set_passphrase, Db},
+    error::Error,
+    grpc::get_isolated_client,
+    interop::DbInit,
+    lightclient::get_block_height,
+    resilience::{ResilientClient, RetryPolicy},
+    sql_statements::GET_FIRST_TRANSACTION_HEIGHT,
+    sync::scan_block_range,
+    tor::account_isolation,
+};
+
+/// The number of external transparent addresses probed for each candidate account.
+const TRANSPARENT_ADDRESSES_TO_PROBE: u32 = 20;
+
+/// The number of blocks after the starting height that are scanned for each candidate account's shielded activity.
+/// This is about three months of blocks.
+const SHIELDED_BLOCKS_TO_PROBE: u32 = 100_000;
+
+/// An account that was found to have history on the chain and was added to the wallet.
+#[derive(Debug, Clone)]
+pub struct DiscoveredAccount {
+    pub account_id: u32,
+    /// The ZIP-32 account index.
+    pub account_index: u32,
+    /// The birthday height assigned to the account, based on its earliest transaction.
+    pub birthday_height: u32,
+}
+
+/// Finds the ZIP-32 accounts derived from `seed` that have been used on the chain
+/// and adds each of them to the wallet.
+///
+/// Accounts are probed in order of their ZIP-32 index until `max_gap` consecutive accounts
+/// show no activity. Each candidate is probed by looking up the transactions for its first few
+/// transparent addresses, and by scanning a limited window of compact blocks from `birthday_height`
+/// (or Sapling activation, when not specified) with a scratch wallet that holds only the candidates.
+/// Shielded activity is only found if it starts within that window, so `birthday_height`
+/// should be no earlier than necessary.
+///
+/// Accounts that are already in the wallet count as used, but are not returned.
+pub async fn discover_accounts(
+    config: &DbInit,
+    uri: Uri,
+    seed: SecretVec<u8>,
+    max_gap: u32,
+    birthday_height: Option<u32>,
+    cancellation_token: CancellationToken,
+) -> Result<Vec<DiscoveredAccount>, Error> {
+    if max_gap == 0 {
+        return Err(Error::InvalidArgument(
+            "max_gap must be at least 1.".to_string(),
+        ));
+    }
+
+    let network: Network = config.network.into();
+    let start_height = match birthday_height {
+        Some(h) => h,
+        None => network
+            .activation_height(NetworkUpgrade::Sapling)
+            .ok_or(Error::InvalidHeight)?
+            .into(),
+    };
+    let tip_height = get_block_height(uri.clone(), cancellation_token.clone()).await?;
+
+    let mut db = Db::load(&config.data_file, network)?;
+
+    // The earliest height at which each used account (by ZIP-32 index) has activity.
+    let mut used: BTreeMap<u32, Option<u32>> = BTreeMap::new();
+    let mut next_index = 0;
+    loop {
+        let window_end = match used.keys().next_back() {
+            Some(last_used) => last_used + 1 + max_gap,
+            None => max_gap,
+        };
+        if next_index >= window_end {
+            break;
+        }
+
+        let mut candidates = Vec::new();
+        for index in next_index..window_end {
+            let account_index = zip32::AccountId::try_from(index)
+                .map_err(|_| Error::InvalidArgument("Account index out of range.".to_string()))?;
+            let ufvk = UnifiedSpendingKey::from_seed(&network, seed.expose_secret(), account_index)
+                .map_err(|e| Error::Internal(format!("Unable to derive account keys: {:?}", e)))?
+                .to_unified_full_viewing_key();
+            if db.data.get_account_for_ufvk(&ufvk)?.is_some() {
+                used.insert(index, None);
+                continue;
+            }
+
+            if let Some(height) = probe_transparent(
+                uri.clone(),
+                &network,
+                &ufvk,
+                start_height,
+                tip_height,
+                cancellation_token.clone(),
+            )
+            .await?
+            {
+                used.insert(index, Some(height));
+            }
+
+            candidates.push((index, ufvk));
+        }
+
+        for (index, height) in probe_shielded(
+            config,
+            uri.clone(),
+            &candidates,
+            start_height..tip_height.min(start_height + SHIELDED_BLOCKS_TO_PROBE) + 1,
+            cancellation_token.clone(),
+        )
+        .await?
+        {
+            let earliest = used.entry(index).or_insert(Some(height));
+            *earliest = earliest.map(|h| h.min(height));
+        }
+
+        next_index = window_end;
+    }
+
+    let mut result = Vec::new();
+    for (index, birthday_height) in used {
+        // Accounts without a birthday are the ones already in the wallet.
+        if let Some(birthday_height) = birthday_height {
+            let account_index = zip32::AccountId::try_from(index)
+                .map_err(|_| Error::InvalidArgument("Account index out of range.".to_string()))?;
+            let ufvk = UnifiedSpendingKey::from_seed(&network, seed.expose_secret(), account_index)
+                .map_err(|e| Error::Internal(format!("Unable to derive account keys: {:?}", e)))?
+                .to_unified_full_viewing_key();
+            let mut client =
+                get_isolated_client(uri.clone(), account_isolation(&network, &ufvk)).await?;
+            let (account, _) = db
+                .add_account(&seed, account_index, birthday_height.into(), &mut client)
+                .await?;
+            result.push(DiscoveredAccount {
+                account_id: account.id().into(),
+                account_index: index,
+                birthday_height,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Looks up transactions involving the account's first few external transparent addresses.
+///
+/// Returns the height of the earliest one, if any.
+async fn probe_transparent(
+    uri: Uri,
+    network: &Network,
+    ufvk: &UnifiedFullViewingKey,
+    start_height: u32,
+    tip_height: u32,
+    cancellation_token: CancellationToken,
+) -> Result<Option<u32>, Error> {
+    let Some(account_pubkey) = ufvk.transparent() else {
+        return Ok(None);
+    };
+    let ivk = account_pubkey
+        .derive_external_ivk()
+        .map_err(|e| Error::Internal(format!("Unable to derive transparent keys: {:?}", e)))?;
+
+    let client = ResilientClient::new(
+        get_isolated_client(uri, account_isolation(network, ufvk))
+            .await?
+            .into(),
+        RetryPolicy::default(),
+        cancellation_token,
+    );
+    let mut earliest: Option<u32> = None;
+    for i in 0..TRANSPARENT_ADDRESSES_TO_PROBE {
+        let Some(child_index) = NonHardenedChildIndex::from_index(i) else {
+            continue;
+        };
+        let Ok(address) = ivk.derive_address(child_index) else {
+            continue;
+        };
+        let request = TransparentAddressBlockFilter {
+            address: address.encode(network),
+            range: Some(service::BlockRange {
+                start: Some(service::BlockId {
+                    height: start_height.into(),
+                    ..Default::default()
+                }),
+                end: Some(service::BlockId {
+                    height: tip_height.into(),
+                    ..Default::default()
+                }),
+            }),
+        };
+        let transactions = client.get_taddress_txids(request).await?;
+        for tx in transactions {
+            let height = tx.height as u32;
+            earliest = Some(earliest.map_or(height, |e| e.min(height)));
+        }
+    }
+
+    Ok(earliest)
+}
+
+/// Scans the blocks in `range` with a scratch wallet that holds just the candidate accounts.
+///
+/// Returns the ZIP-32 index and the height of the earliest transaction of each candidate
+/// that has any shielded history.
+async fn probe_shielded(
+    config: &DbInit,
+    uri: Uri,
+    candidates: &[(u32, UnifiedFullViewingKey)],
+    range: Range<u32>,
+    cancellation_token: CancellationToken,
+) -> Result<Vec<(u32, u32)>, Error> {
+    if candidates.is_empty() || range.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let network: Network = config.network.into();
+    let scratch_file = PathBuf::from(format!("{}.discovery", config.data_file));
+    remove_database_files(&scratch_file)?;
+    copy_passphrase(&config.data_file, &scratch_file);
+
+    let result = async {
+        let mut scratch = Db::init(&scratch_file, network)?;
+        let mut accounts: Vec<(u32, AccountId)> = Vec::new();
+        for (index, ufvk) in candidates {
+            let mut client =
+                get_isolated_client(uri.clone(), account_isolation(&network, ufvk)).await?;
+            let account = scratch
+                .import_account_ufvk(
+                    ufvk,
+                    AccountPurpose::ViewOnly,
+                    range.start.into(),
+                    &mut client,
+                )
+                .await?;
+            accounts.push((*index, account.id()));
+        }
+        drop(scratch);
+
+        scan_block_range(
+            uri,
+            &scratch_file,
+            range.start.into()..range.end.into(),
+            cancellation_token,
+        )
+        .await?;
+
+        let conn = open_connection(&scratch_file)?;
+        let mut found = Vec::new();
+        for (index, account_id) in accounts {
+            let height: Option<u32> = conn.query_row(
+                GET_FIRST_TRANSACTION_HEIGHT,
+                named_params! { ":account_id": u32::from(account_id) },
+                |row| row.get(0),
+            )?;
+            if let Some(height) = height {
+                found.push((index, height));
+            }
+        }
+
+        Ok(found)
+    }
+    .await;
+
+    // The scratch wallet is only useful for this one probe.
+    let _ = remove_database_files(&scratch_file);
+    set_passphrase(&scratch_file, None);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU32, time::Duration};
+
+    use bip0039::{Count, English, Mnemonic};
+    use zcash_client_backend::proto::service::TxFilter;
+
+    use crate::{
+        grpc::get_client,
+        interop::TransactionSendDetail,
+        send::send_transaction,
+        test_constants::{setup_test, MIN_CONFIRMATIONS},
+    };
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_discover_accounts_unused_seed() {
+        let setup = setup_test().await;
+        let seed = SecretVec::new(
+            Mnemonic::<English>::generate(Count::Words24)
+                .to_seed("")
+                .to_vec(),
+        );
+        let birthday = setup.server_info.block_height as u32 - 10;
+
+        let discovered = discover_accounts(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            seed,
+            1,
+            Some(birthday),
+            CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        assert!(discovered.is_empty());
+    }
+
+    #[tokio_shared_rt::test]
+    #[ignore = "requires NERDBANK_ZCASH_FUNDED_TESTNET_SEED"]
+    async fn test_discover_accounts_finds_account_after_unused_one() {
+        let mut funded = setup_test().await;
+        let (_, usk) = funded.create_funded_account().await;
+
+        // Pay account 1 of a new seed, leaving account 0 unused.
+        let seed = SecretVec::new(
+            Mnemonic::<English>::generate(Count::Words24)
+                .to_seed("")
+                .to_vec(),
+        );
+        let ufvk = UnifiedSpendingKey::from_seed(
+            &funded.network,
+            seed.expose_secret(),
+            zip32::AccountId::try_from(1).unwrap(),
+        )
+        .unwrap()
+        .to_unified_full_viewing_key();
+        let recipient = ufvk.sapling().unwrap().default_address().1;
+        let birthday = get_block_height(funded.server_uri.clone(), CancellationToken::new())
+            .await
+            .unwrap();
+        let sent = send_transaction(
+            &funded.data_file,
+            funded.server_uri.clone(),
+            funded.network,
+            &usk,
+            NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap(),
+            vec![TransactionSendDetail {
+                recipient: recipient.encode(&funded.network),
+                value: 10_000,
+                memo: None,
+            }],
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Discovery only finds the payment once it is mined.
+        let client = ResilientClient::new(
+            get_client(funded.server_uri.clone()).await.unwrap().into(),
+            RetryPolicy::default(),
+            CancellationToken::new(),
+        );
+        let mined_height = loop {
+            let tx = client
+                .get_transaction(TxFilter {
+                    hash: sent.head.txid.as_ref().to_vec(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            if let Some(height) = tx
+                .map(|tx| tx.height)
+                .filter(|h| *h > 0 && *h < u64::from(u32::MAX))
+            {
+                break height as u32;
+            }
+            tokio::time::sleep(Duration::from_secs(15)).await;
+        };
+
+        let setup = setup_test().await;
+        let discovered = discover_accounts(
+            &setup.db_init,
+            setup.server_uri.clone(),
+            seed,
+            2,
+            Some(birthday),
+            CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(discovered.len(), 1);
+        assert_eq!(discovered[0].account_index, 1);
+        assert_eq!(discovered[0].birthday_height, mined_height);
+    }
+}
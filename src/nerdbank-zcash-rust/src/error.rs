@@ -7,6 +7,7 @@ use zcash_client_backend::{
     zip321::Zip321Error,
 };
 use zcash_client_sqlite::{error::SqliteClientError, wallet::init::WalletMigrationError};
+use zcash_keys::keys::AddressGenerationError;
 use zcash_primitives::{
     memo,
     transaction::components::amount::{BalanceError, NonNegativeAmount},
@@ -96,6 +97,9 @@ pub enum Error {
 
     KeyNotRecognized,
 
+    /// An address with the requested receivers cannot be produced.
+    AddressGeneration(AddressGenerationError),
+
     Join(JoinError),
 
     Canceled,
@@ -140,6 +144,22 @@ impl std::fmt::Display for Error {
             Error::ProposalNotSupported => f.write_str("Proposal not supported"),
             Error::NoSpendingKey(e) => write!(f, "No spending key: {}", e),
             Error::KeyNotRecognized => f.write_str("No account found with the given key."),
+            Error::AddressGeneration(e) => match e {
+                AddressGenerationError::InvalidTransparentChildIndex(_) => f.write_str(
+                    "A transparent receiver cannot be produced at this diversifier index.",
+                ),
+                AddressGenerationError::InvalidSaplingDiversifierIndex(_) => {
+                    f.write_str("A Sapling receiver cannot be produced at this diversifier index.")
+                }
+                AddressGenerationError::KeyNotAvailable(typecode) => write!(
+                    f,
+                    "The account has no key for the requested receiver type {:?}.",
+                    typecode
+                ),
+                AddressGenerationError::ShieldedReceiverRequired => {
+                    f.write_str("A unified address must include at least one shielded receiver.")
+                }
+            },
             Error::Join(e) => e.fmt(f),
             Error::Canceled => f.write_str("Canceled"),
         }
@@ -152,6 +172,12 @@ impl From<BalanceError> for Error {
     }
 }
 
+impl From<AddressGenerationError> for Error {
+    fn from(e: AddressGenerationError) -> Self {
+        Error::AddressGeneration(e)
+    }
+}
+
 impl From<JoinError> for Error {
     fn from(e: JoinError) -> Self {
         Error::Join(e)
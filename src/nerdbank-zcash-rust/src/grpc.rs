@@ -1,33 +1,181 @@
+use futures_util::future::join_all;
 use http::Uri;
-use std::{collections::HashMap, sync::Mutex};
-use tonic::transport::{Channel, ClientTlsConfig};
-use zcash_client_backend::proto::service::compact_tx_streamer_client::CompactTxStreamerClient;
+use hyper_util::rt::TokioIo;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio_socks::tcp::Socks5Stream;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
+use tracing::warn;
+use zcash_client_backend::proto::service::{
+    self, compact_tx_streamer_client::CompactTxStreamerClient,
+};
 
-// We'll use a MUTEX to store the shareable gRPC channels, indexed by server URI.
+use crate::{error::Error, tor::tor_options};
+
+/// How long a server has to connect and describe itself before it is considered unhealthy.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The most blocks a server's tip may trail the highest tip of the other servers
+/// before it is ranked after every server that is up to date.
+const MAX_TIP_LAG: u32 = 3;
+
+// We'll use a MUTEX to store the shareable gRPC channels, indexed by server URI and Tor stream isolation key.
 // gRPC channels are expensive to create, cannot be used concurrently, but cheap to clone for each user.
 lazy_static! {
-    static ref CHANNELS: Mutex<HashMap<Uri, Channel>> = Mutex::new(HashMap::new());
+    static ref CHANNELS: Mutex<HashMap<(Uri, Option<String>), Channel>> =
+        Mutex::new(HashMap::new());
+    static ref CONNECTION_OPTIONS: Mutex<HashMap<Uri, ConnectionOptions>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Options for how to connect to a light wallet server.
+///
+/// Whether TLS is used follows from the server URI's scheme (`http` or `https`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// PEM-encoded certificates of additional certificate authorities to trust.
+    pub ca_certificates: Vec<String>,
+    /// Trusts only `ca_certificates` and not the public certificate authorities,
+    /// e.g. to pin the server's self-signed certificate.
+    pub trust_only_custom_certificates: bool,
+    /// The name to check the server's certificate against, when it differs from the URI's host.
+    pub tls_domain_name: Option<String>,
+    pub connect_timeout_ms: Option<u64>,
+    /// How long to wait for the response to each request, not counting the time spent reading a streamed response.
+    pub request_timeout_ms: Option<u64>,
+    /// How often to send HTTP/2 keepalive pings.
+    pub keepalive_interval_ms: Option<u64>,
+    /// How long to wait for a keepalive ping to be acknowledged before the connection is closed.
+    pub keepalive_timeout_ms: Option<u64>,
+    /// The address (`host:port`) of a SOCKS5 proxy to connect through.
+    /// Host names are resolved by the proxy.
+    pub socks5_proxy: Option<String>,
+}
+
+/// Sets the options for connecting to the server at the given URI, or restores the defaults when `None`.
+///
+/// The cached channel to the server, if any, is released so that the next request reconnects with the new options.
+pub(crate) fn set_connection_options(uri: Uri, options: Option<ConnectionOptions>) {
+    {
+        let mut all_options = CONNECTION_OPTIONS.lock().unwrap();
+        match options {
+            Some(options) => all_options.insert(uri.clone(), options),
+            None => all_options.remove(&uri),
+        };
+    }
+    destroy_channel(uri);
 }
 
 /// Return a gRPC channel for the given URI, creating one if necessary.
-pub(crate) async fn get_grpc_channel(uri: Uri) -> Result<Channel, tonic::transport::Error> {
+///
+/// Channels with different isolation keys use separate Tor circuits when Tor is in use.
+pub(crate) async fn get_grpc_channel(
+    uri: Uri,
+    isolation: Option<String>,
+) -> Result<Channel, Error> {
+    let tor = tor_options();
+    let key = (uri, isolation.filter(|_| tor.is_some()));
     {
         let clients = CHANNELS.lock().unwrap();
-        if let Some(channel) = clients.get(&uri) {
+        if let Some(channel) = clients.get(&key) {
             return Ok(channel.clone());
         }
     }
+    let (uri, isolation) = key;
+
+    let options = CONNECTION_OPTIONS
+        .lock()
+        .unwrap()
+        .get(&uri)
+        .cloned()
+        .unwrap_or_default();
 
-    let tls = ClientTlsConfig::new()
-        .with_webpki_roots()
-        .domain_name(uri.host().unwrap());
-    let channel = Channel::builder(uri.clone())
-        .tls_config(tls)?
-        .connect()
-        .await?;
+    // Tor takes the place of any other proxy, with the isolation key as the SOCKS credentials.
+    let proxy = match tor {
+        Some(tor) => Some((tor.socks_address, isolation.clone())),
+        None => options.socks5_proxy.clone().map(|proxy| (proxy, None)),
+    };
+    let channel = connect(&uri, &options, proxy).await?;
 
     let mut clients = CHANNELS.lock().unwrap();
-    clients.insert(uri, channel.clone());
+    clients.insert((uri, isolation), channel.clone());
+    Ok(channel)
+}
+
+async fn connect(
+    uri: &Uri,
+    options: &ConnectionOptions,
+    proxy: Option<(String, Option<String>)>,
+) -> Result<Channel, Error> {
+    let host = uri
+        .host()
+        .ok_or_else(|| Error::InvalidArgument(format!("The server URI has no host: {}", uri)))?;
+
+    let mut endpoint = Endpoint::from(uri.clone());
+    if uri.scheme_str() != Some("http") {
+        let mut tls = ClientTlsConfig::new().domain_name(
+            options
+                .tls_domain_name
+                .clone()
+                .unwrap_or_else(|| host.to_string()),
+        );
+        if !options.trust_only_custom_certificates {
+            tls = tls.with_webpki_roots();
+        }
+        for pem in &options.ca_certificates {
+            tls = tls.ca_certificate(Certificate::from_pem(pem));
+        }
+        endpoint = endpoint.tls_config(tls)?;
+    }
+
+    if let Some(ms) = options.connect_timeout_ms {
+        endpoint = endpoint.connect_timeout(Duration::from_millis(ms));
+    }
+    if let Some(ms) = options.request_timeout_ms {
+        endpoint = endpoint.timeout(Duration::from_millis(ms));
+    }
+    if let Some(ms) = options.keepalive_interval_ms {
+        endpoint = endpoint
+            .http2_keep_alive_interval(Duration::from_millis(ms))
+            .keep_alive_while_idle(true);
+    }
+    if let Some(ms) = options.keepalive_timeout_ms {
+        endpoint = endpoint.keep_alive_timeout(Duration::from_millis(ms));
+    }
+
+    let channel = match proxy {
+        None => endpoint.connect().await?,
+        Some((proxy, credentials)) => {
+            let connector = tower::service_fn(move |uri: Uri| {
+                let proxy = proxy.clone();
+                let credentials = credentials.clone();
+                async move {
+                    let host = uri.host().unwrap_or_default().to_string();
+                    let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+                        Some("http") => 80,
+                        _ => 443,
+                    });
+                    let target = (host.as_str(), port);
+                    let stream = match credentials.as_deref() {
+                        Some(key) => {
+                            Socks5Stream::connect_with_password(proxy.as_str(), target, key, key)
+                                .await?
+                        }
+                        None => Socks5Stream::connect(proxy.as_str(), target).await?,
+                    };
+                    Ok::<_, tokio_socks::Error>(TokioIo::new(stream.into_inner()))
+                }
+            });
+            endpoint.connect_with_connector(connector).await?
+        }
+    };
+
     Ok(channel)
 }
 
@@ -38,27 +186,290 @@ pub(crate) async fn get_grpc_channel(uri: Uri) -> Result<Channel, tonic::transpo
 /// The function returns true if the channel was found and removed, false otherwise.
 pub(crate) fn destroy_channel(uri: Uri) -> bool {
     let mut clients = CHANNELS.lock().unwrap();
-    clients.remove(&uri).is_some()
+    let count = clients.len();
+    clients.retain(|(channel_uri, _), _| *channel_uri != uri);
+    clients.len() < count
+}
+
+/// Releases every cached gRPC channel, e.g. after the way to reach servers has changed.
+pub(crate) fn destroy_all_channels() {
+    CHANNELS.lock().unwrap().clear();
 }
 
 /// Gets the CompactTxStreamerClient for the given URI for use with communicating with the lightwalletd server.
-pub async fn get_client(
+pub async fn get_client(uri: Uri) -> Result<CompactTxStreamerClient<Channel>, Error> {
+    get_isolated_client(uri, None).await
+}
+
+/// Gets a CompactTxStreamerClient whose connection is isolated from those with other isolation keys
+/// (see `tor::account_isolation`).
+pub(crate) async fn get_isolated_client(
     uri: Uri,
-) -> Result<CompactTxStreamerClient<Channel>, tonic::transport::Error> {
-    let channel = get_grpc_channel(uri).await?;
+    isolation: Option<String>,
+) -> Result<CompactTxStreamerClient<Channel>, Error> {
+    let channel = get_grpc_channel(uri, isolation).await?;
     Ok(CompactTxStreamerClient::new(channel))
 }
 
+/// The health of a light wallet server, as measured by `rank_servers`.
+#[derive(Debug, Clone)]
+pub struct ServerStatus {
+    pub uri: String,
+    /// Whether the server could be reached and serves the same network as the first reachable server given.
+    pub healthy: bool,
+    /// How long the server took to describe itself, in milliseconds.
+    pub latency_ms: Option<u64>,
+    pub tip_height: Option<u32>,
+    pub error: Option<String>,
+}
+
+/// Probes the given servers and sorts them from the most to the least preferred.
+///
+/// Healthy servers come first, those that are up to date with the chain tip before those that lag behind,
+/// and then the fastest to respond.
+pub async fn rank_servers(uris: Vec<Uri>) -> Vec<ServerStatus> {
+    let probes = join_all(uris.into_iter().map(probe)).await;
+
+    // Servers on another network than the first reachable one cannot stand in for it.
+    let chain_name = probes.iter().find_map(|(_, chain)| chain.clone());
+    let mut statuses = probes
+        .into_iter()
+        .map(|(mut status, chain)| {
+            if status.healthy && chain != chain_name {
+                status.healthy = false;
+                status.error = Some(format!("Serves a different network: {:?}", chain));
+            }
+            status
+        })
+        .collect::<Vec<_>>();
+
+    let best_tip = statuses
+        .iter()
+        .filter(|s| s.healthy)
+        .filter_map(|s| s.tip_height)
+        .max()
+        .unwrap_or(0);
+    statuses.sort_by_key(|s| {
+        let lagging = s.tip_height.unwrap_or(0).saturating_add(MAX_TIP_LAG) < best_tip;
+        (!s.healthy, lagging, s.latency_ms.unwrap_or(u64::MAX))
+    });
+    statuses
+}
+
+/// Connects to a server and asks it to describe itself, returning its status and network.
+async fn probe(uri: Uri) -> (ServerStatus, Option<String>) {
+    let mut status = ServerStatus {
+        uri: uri.to_string(),
+        healthy: false,
+        latency_ms: None,
+        tip_height: None,
+        error: None,
+    };
+
+    let result = tokio::time::timeout(PROBE_TIMEOUT, async {
+        let mut client = get_client(uri).await?;
+        let started = Instant::now();
+        let info = client
+            .get_lightd_info(service::Empty {})
+            .await?
+            .into_inner();
+        Ok::<_, Error>((info, started.elapsed()))
+    })
+    .await;
+
+    match result {
+        Ok(Ok((info, latency))) => {
+            status.healthy = true;
+            status.latency_ms = Some(latency.as_millis() as u64);
+            status.tip_height = u32::try_from(info.block_height).ok();
+            (status, Some(info.chain_name))
+        }
+        Ok(Err(e)) => {
+            status.error = Some(e.to_string());
+            (status, None)
+        }
+        Err(_) => {
+            status.error = Some("Timed out".to_string());
+            (status, None)
+        }
+    }
+}
+
+/// A ranked set of light wallet servers, one of which is used at a time.
+#[derive(Debug)]
+pub(crate) struct ServerPool {
+    servers: Vec<(Option<Uri>, CompactTxStreamerClient<Channel>)>,
+    current: AtomicUsize,
+}
+
+impl From<CompactTxStreamerClient<Channel>> for ServerPool {
+    fn from(client: CompactTxStreamerClient<Channel>) -> Self {
+        ServerPool {
+            servers: vec![(None, client)],
+            current: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl ServerPool {
+    /// Connects to the given servers, ranked by `rank_servers` when there is more than one.
+    ///
+    /// Unhealthy servers are left out, unless none are healthy, in which case the first server is used
+    /// so that its connection error surfaces.
+    pub(crate) async fn connect(uris: Vec<Uri>) -> Result<Self, Error> {
+        let mut ranked = Vec::new();
+        if uris.len() > 1 {
+            for status in rank_servers(uris.clone()).await {
+                if status.healthy {
+                    let uri: Uri = status.uri.parse().map_err(|_| {
+                        Error::Internal(format!("Invalid server URI: {}", status.uri))
+                    })?;
+                    ranked.push(uri);
+                }
+            }
+        }
+        if ranked.is_empty() {
+            ranked.extend(uris.into_iter().take(1));
+        }
+
+        let mut servers = Vec::with_capacity(ranked.len());
+        for uri in ranked {
+            let client = get_client(uri.clone()).await?;
+            servers.push((Some(uri), client));
+        }
+
+        if servers.is_empty() {
+            return Err(Error::InvalidArgument("No server was given.".to_string()));
+        }
+
+        Ok(ServerPool {
+            servers,
+            current: AtomicUsize::new(0),
+        })
+    }
+
+    /// Gets the client for the server currently in use.
+    pub(crate) fn client(&self) -> CompactTxStreamerClient<Channel> {
+        self.servers[self.current.load(Ordering::Relaxed)].1.clone()
+    }
+
+    /// Gets the URI of the server currently in use, if it is known.
+    pub(crate) fn uri(&self) -> Option<&Uri> {
+        self.servers[self.current.load(Ordering::Relaxed)]
+            .0
+            .as_ref()
+    }
+
+    /// Switches to the next server in the pool, after a request to the current one failed.
+    ///
+    /// The failed server's channel is kept, since other users may share it
+    /// and it reconnects on its own when the pool rotates back to it.
+    pub(crate) fn fail_over(&self) {
+        if self.servers.len() < 2 {
+            return;
+        }
+
+        let failed = self.current.load(Ordering::Relaxed);
+        let next = (failed + 1) % self.servers.len();
+        if self
+            .current
+            .compare_exchange(failed, next, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            warn!(
+                "Failing over from {:?} to {:?}.",
+                self.servers[failed].0, self.servers[next].0
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test_constants::LIGHTSERVER_URI;
+    use crate::test_constants::{lock_global_state, LIGHTSERVER_URI, MAINNET_LIGHTSERVER_URI};
 
     use super::*;
 
+    /// Restores the default connection options for a server when dropped, even if the test fails.
+    struct DefaultConnectionOptions(Uri);
+
+    impl Drop for DefaultConnectionOptions {
+        fn drop(&mut self) {
+            set_connection_options(self.0.clone(), None);
+        }
+    }
+
     #[tokio_shared_rt::test]
     async fn get_client_twice_then_destroy() {
         get_client(LIGHTSERVER_URI.to_owned()).await.unwrap();
         get_client(LIGHTSERVER_URI.to_owned()).await.unwrap();
         assert!(destroy_channel(LIGHTSERVER_URI.to_owned()));
     }
+
+    #[tokio_shared_rt::test]
+    async fn test_connection_options() {
+        let _lock = lock_global_state().await;
+        let uri = MAINNET_LIGHTSERVER_URI.to_owned();
+        let restore = DefaultConnectionOptions(uri.clone());
+
+        // The server's certificate is not signed by any of the (zero) trusted authorities.
+        set_connection_options(
+            uri.clone(),
+            Some(ConnectionOptions {
+                trust_only_custom_certificates: true,
+                connect_timeout_ms: Some(10_000),
+                ..Default::default()
+            }),
+        );
+        assert!(get_client(uri.clone()).await.is_err());
+
+        drop(restore);
+        get_client(uri).await.unwrap();
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_rank_servers() {
+        // The unreachable server is given first, and should be ranked last.
+        let unreachable = Uri::from_static("https://localhost:1/");
+        let statuses = rank_servers(vec![unreachable, LIGHTSERVER_URI.to_owned()]).await;
+
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].uri, LIGHTSERVER_URI.to_string());
+        assert!(statuses[0].tip_height.is_some());
+        assert!(!statuses[1].healthy);
+        assert!(statuses[1].error.is_some());
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_fail_over_keeps_shared_channels() {
+        let uris = [
+            Uri::from_static("http://fail-over-a.invalid/"),
+            Uri::from_static("http://fail-over-b.invalid/"),
+        ];
+        let servers = uris
+            .iter()
+            .map(|uri| {
+                let channel = Endpoint::from(uri.clone()).connect_lazy();
+                CHANNELS
+                    .lock()
+                    .unwrap()
+                    .insert((uri.clone(), None), channel.clone());
+                (Some(uri.clone()), CompactTxStreamerClient::new(channel))
+            })
+            .collect();
+        let pool = ServerPool {
+            servers,
+            current: AtomicUsize::new(0),
+        };
+
+        pool.fail_over();
+        assert_eq!(pool.uri(), Some(&uris[1]));
+        pool.fail_over();
+        assert_eq!(pool.uri(), Some(&uris[0]));
+
+        // Other users of the failed servers keep their channels.
+        for uri in uris {
+            assert!(destroy_channel(uri));
+        }
+    }
 }
@@ -0,0 +1,279 @@
+use rusqlite::{named_params, Connection};
+use zcash_client_backend::data_api::WalletWrite;
+use zcash_primitives::consensus::{BlockHeight, Network};
+
+use crate::{
+    backing_store::{open_connection, Db},
+    error::Error,
+    interop::DbInit,
+    sql_statements::{
+        COUNT_ORPHANED_NOTES, COUNT_UNKNOWN_OUTPUT_POOLS, FIND_MISSING_FEES,
+        FIND_MISSING_RAW_TRANSACTIONS, FIND_SCAN_QUEUE_GAPS, FIND_TREE_INCONSISTENCIES,
+        REMOVE_ORPHANED_NOTES,
+    },
+};
+
+/// The `tx_retrieval_queue.query_type` that requests the full transaction.
+const TX_QUERY_TYPE_ENHANCEMENT: u32 = 1;
+
+/// The `scan_queue.priority` for ranges that should be scanned after more urgent ones.
+pub(crate) const SCAN_PRIORITY_HISTORIC: u32 = 20;
+
+/// A range of block heights, with an exclusive end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeightRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// The problems that `check_wallet` found.
+#[derive(Debug, Clone)]
+pub struct WalletCheckReport {
+    /// Heights of blocks whose note commitment tree sizes don't follow from the block before them.
+    pub tree_inconsistencies: Vec<u32>,
+    /// Block ranges after the wallet birthday that are missing from the scan queue,
+    /// and so would never be scanned.
+    pub scan_queue_gaps: Vec<HeightRange>,
+    /// The number of received notes that belong to a missing transaction or account.
+    pub orphaned_notes: u64,
+    /// Transactions whose raw data is missing, with no pending request to download it.
+    pub missing_raw_transactions: Vec<Vec<u8>>,
+    /// Mined transactions funded by the wallet whose fee is unknown.
+    pub missing_fees: Vec<Vec<u8>>,
+    /// The number of transaction outputs recorded with an unrecognized pool.
+    pub unknown_output_pools: u64,
+    /// The height that the wallet can be truncated to in order to discard inconsistent data,
+    /// if any such data was found.
+    pub safe_truncation_height: Option<u32>,
+}
+
+/// A targeted repair for problems that `check_wallet` reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletRepair {
+    /// Requests that the next sync download transactions whose raw data is missing.
+    RequeueEnhancements,
+    /// Deletes notes that belong to a missing transaction or account.
+    RemoveOrphanedNotes,
+    /// Adds scan ranges for the gaps in the scan queue.
+    FillScanQueueGaps,
+    /// Rewinds the wallet to `safe_truncation_height` so that the next sync rescans from there.
+    TruncateToSafeHeight,
+}
+
+/// Checks the wallet database for inconsistencies that can leave it stuck.
+pub fn check_wallet(config: &DbInit) -> Result<WalletCheckReport, Error> {
+    let conn = open_connection(&config.data_file)?;
+    check(&conn)
+}
+
+/// Applies the given repairs, then checks the wallet again.
+pub fn repair_wallet(
+    config: &DbInit,
+    repairs: &[WalletRepair],
+) -> Result<WalletCheckReport, Error> {
+    let network: Network = config.network.into();
+    let mut conn = open_connection(&config.data_file)?;
+    let report = check(&conn)?;
+
+    let tx = conn.transaction()?;
+    if repairs.contains(&WalletRepair::RequeueEnhancements) {
+        for txid in &report.missing_raw_transactions {
+            tx.execute(
+                "INSERT INTO tx_retrieval_queue (txid, query_type) VALUES (:txid, :query_type)
+				ON CONFLICT (txid) DO NOTHING",
+                named_params! {
+                    ":txid": txid,
+                    ":query_type": TX_QUERY_TYPE_ENHANCEMENT,
+                },
+            )?;
+        }
+    }
+
+    if repairs.contains(&WalletRepair::RemoveOrphanedNotes) {
+        tx.execute_batch(REMOVE_ORPHANED_NOTES)?;
+    }
+
+    if repairs.contains(&WalletRepair::FillScanQueueGaps) {
+        for gap in &report.scan_queue_gaps {
+            tx.execute(
+                "INSERT INTO scan_queue (block_range_start, block_range_end, priority) VALUES (:start, :end, :priority)",
+                named_params! {
+                    ":start": gap.start,
+                    ":end": gap.end,
+                    ":priority": SCAN_PRIORITY_HISTORIC,
+                },
+            )?;
+        }
+    }
+    tx.commit()?;
+
+    if repairs.contains(&WalletRepair::TruncateToSafeHeight) {
+        if let Some(height) = report.safe_truncation_height {
+            let mut db = Db::load(&config.data_file, network)?;
+            db.data.truncate_to_height(BlockHeight::from_u32(height))?;
+        }
+    }
+
+    check(&conn)
+}
+
+fn check(conn: &Connection) -> Result<WalletCheckReport, Error> {
+    let tree_inconsistencies = conn
+        .prepare(FIND_TREE_INCONSISTENCIES)?
+        .query_map([], |row| row.get::<_, u32>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let scan_queue_gaps = conn
+        .prepare(FIND_SCAN_QUEUE_GAPS)?
+        .query_map([], |row| {
+            Ok(HeightRange {
+                start: row.get("gap_start")?,
+                end: row.get("gap_end")?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let orphaned_notes: u64 = conn.query_row(COUNT_ORPHANED_NOTES, [], |row| row.get(0))?;
+
+    let missing_raw_transactions = conn
+        .prepare(FIND_MISSING_RAW_TRANSACTIONS)?
+        .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let missing_fees = conn
+        .prepare(FIND_MISSING_FEES)?
+        .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let unknown_output_pools: u64 =
+        conn.query_row(COUNT_UNKNOWN_OUTPUT_POOLS, [], |row| row.get(0))?;
+
+    // Inconsistent commitment trees can only be fixed by discarding the blocks from the first bad one onward.
+    let safe_truncation_height = tree_inconsistencies.first().map(|h| h.saturating_sub(1));
+
+    Ok(WalletCheckReport {
+        tree_inconsistencies,
+        scan_queue_gaps,
+        orphaned_notes,
+        missing_raw_transactions,
+        missing_fees,
+        unknown_output_pools,
+        safe_truncation_height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_constants::{setup_test, VALID_SAPLING_TESTNET};
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_check_wallet_after_sync() {
+        let mut setup = setup_test().await;
+        setup.create_account().await.unwrap();
+        setup.sync().await;
+
+        let report = check_wallet(&setup.db_init).unwrap();
+        assert!(report.tree_inconsistencies.is_empty());
+        assert!(report.scan_queue_gaps.is_empty());
+        assert_eq!(report.orphaned_notes, 0);
+        assert_eq!(report.unknown_output_pools, 0);
+        assert_eq!(report.safe_truncation_height, None);
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_repair_wallet() {
+        let mut setup = setup_test().await;
+        let (_, _, account_id, _) = setup.create_account().await.unwrap();
+        setup.sync().await;
+        let conn = open_connection(&setup.data_file).unwrap();
+        let height: u32 = conn
+            .query_row("SELECT MAX(height) FROM blocks", [], |row| row.get(0))
+            .unwrap();
+
+        // An incoming transaction without its raw data or fee, and one the wallet funded.
+        let incoming = vec![1u8; 32];
+        let outgoing = vec![2u8; 32];
+        for txid in [&incoming, &outgoing] {
+            conn.execute(
+                "INSERT INTO transactions (txid, block) VALUES (:txid, :block)",
+                named_params! { ":txid": txid, ":block": height },
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO sent_notes (tx, output_pool, output_index, from_account_id, to_address, value)
+			VALUES (:tx, 2, 0, :account_id, :to_address, 1000)",
+            named_params! {
+                ":tx": conn.last_insert_rowid(),
+                ":account_id": u32::from(account_id),
+                ":to_address": VALID_SAPLING_TESTNET,
+            },
+        )
+        .unwrap();
+
+        // Split the last scan range in three and drop the middle, leaving a gap.
+        let (start, end): (u32, u32) = conn
+            .query_row(
+                "SELECT block_range_start, block_range_end FROM scan_queue ORDER BY block_range_start DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(end - start >= 3);
+        let gap = HeightRange {
+            start: start + 1,
+            end: start + 2,
+        };
+        conn.execute(
+            "UPDATE scan_queue SET block_range_end = :end WHERE block_range_start = :start",
+            named_params! { ":start": start, ":end": gap.start },
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO scan_queue (block_range_start, block_range_end, priority)
+			SELECT :start, :end, priority FROM scan_queue WHERE block_range_start = :original_start",
+            named_params! { ":start": gap.end, ":end": end, ":original_start": start },
+        )
+        .unwrap();
+
+        let report = check_wallet(&setup.db_init).unwrap();
+        assert!(report.missing_raw_transactions.contains(&incoming));
+        assert!(report.missing_raw_transactions.contains(&outgoing));
+        assert_eq!(report.missing_fees, vec![outgoing.clone()]);
+        assert_eq!(report.scan_queue_gaps, vec![gap.clone()]);
+
+        let report = repair_wallet(
+            &setup.db_init,
+            &[
+                WalletRepair::RequeueEnhancements,
+                WalletRepair::FillScanQueueGaps,
+            ],
+        )
+        .unwrap();
+        assert!(report.missing_raw_transactions.is_empty());
+        assert!(report.scan_queue_gaps.is_empty());
+
+        let queued: u32 = conn
+            .query_row(
+                "SELECT count(*) FROM tx_retrieval_queue WHERE txid IN (:incoming, :outgoing) AND query_type = :query_type",
+                named_params! {
+                    ":incoming": incoming,
+                    ":outgoing": outgoing,
+                    ":query_type": TX_QUERY_TYPE_ENHANCEMENT,
+                },
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(queued, 2);
+        let gap_priority: u32 = conn
+            .query_row(
+                "SELECT priority FROM scan_queue WHERE block_range_start = :start AND block_range_end = :end",
+                named_params! { ":start": gap.start, ":end": gap.end },
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(gap_priority, SCAN_PRIORITY_HISTORIC);
+    }
+}
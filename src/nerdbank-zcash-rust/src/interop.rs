@@ -9,12 +9,11 @@ use std::{
 };
 
 use http::{uri::InvalidUri, Uri};
-use rusqlite::Connection;
 use secrecy::SecretVec;
 use tokio::runtime::Runtime;
 use tokio_util::sync::CancellationToken;
 use zcash_client_backend::{
-    data_api::{Account, AccountPurpose, WalletRead},
+    data_api::{scanning::ScanPriority, Account, AccountPurpose, AccountSource, WalletRead},
     encoding::AddressCodec,
     keys::{Era, UnifiedSpendingKey},
 };
@@ -23,10 +22,20 @@ use zcash_keys::keys::UnifiedFullViewingKey;
 use zcash_primitives::{consensus::Network, legacy::TransparentAddress, zip32::DiversifierIndex};
 
 use crate::{
-    analysis::{BirthdayHeights, UserBalances},
-    backing_store::Db,
+    accounts::get_account_metadata,
+    address_book::AddressBookEntry,
+    addresses::AccountAddress,
+    analysis::{BirthdayHeights, UnspentNote, UserBalances},
+    backing_store::{open_connection, rekey, set_passphrase, Db},
+    batch::{BatchOptions, BatchResult},
+    diagnostics::{LogLevel, LogSink},
+    discovery::DiscoveredAccount,
     error::Error,
-    grpc::{destroy_channel, get_client},
+    grpc::{destroy_channel, get_client, ConnectionOptions, ServerStatus},
+    integrity::{HeightRange, WalletCheckReport, WalletRepair},
+    maintenance::{MaintenanceOptions, MaintenanceReport},
+    prover::{ParameterDownloadProgress, ParameterPaths},
+    resilience::RetryPolicy,
     send::{create_send_proposal, send_transaction},
     shield::shield_funds_at_address,
 };
@@ -38,6 +47,8 @@ lazy_static! {
 pub trait SyncUpdate: Send + Sync + std::fmt::Debug {
     fn update_status(&self, data: SyncUpdateData);
     fn report_transactions(&self, transactions: Vec<Transaction>);
+    fn reorg_detected(&self, from_height: u32, to_height: u32);
+    fn on_retry(&self, retry: u32, delay_ms: u64, error: String);
 }
 
 pub trait CancellationSource: Send + Sync + std::fmt::Debug {
@@ -59,6 +70,85 @@ pub struct SyncUpdateData {
     pub current_step: u64,
     pub total_steps: u64,
     pub last_error: Option<String>,
+    pub phase: SyncPhase,
+    /// The fraction of the sync that is complete, from 0 to 1.
+    /// This never decreases during a sync, even when scan ranges are re-prioritized.
+    pub progress: f64,
+    pub blocks_downloaded: u64,
+    pub bytes_downloaded: u64,
+    pub blocks_scanned: u64,
+    /// The average number of blocks scanned per second since the sync started.
+    pub blocks_per_second: f64,
+    /// The estimated number of seconds until the scan is complete, once enough blocks have been scanned to tell.
+    pub eta_seconds: Option<u64>,
+    /// The block range being downloaded and scanned.
+    pub current_range: Option<HeightRange>,
+    pub current_range_priority: Option<ScanRangePriority>,
+    pub summary: SyncSummary,
+    /// The server that the sync is currently using.
+    pub server: Option<String>,
+}
+
+/// Timings and counts for the work done so far in a sync.
+///
+/// Downloading and scanning blocks overlap, so their timings may add up to more than the elapsed time.
+#[derive(Debug, Clone)]
+pub struct SyncSummary {
+    pub elapsed_ms: u64,
+    pub subtree_roots_ms: u64,
+    pub transparent_ms: u64,
+    pub download_ms: u64,
+    pub scan_ms: u64,
+    /// Time spent downloading full transactions and satisfying the wallet's other transaction data requests.
+    pub enhancement_ms: u64,
+    /// The number of shielded transactions downloaded to fill in their memos.
+    pub transactions_enhanced: u64,
+    /// The number of transaction data requests from the wallet that were satisfied.
+    pub data_requests: u64,
+    pub reorgs: u64,
+}
+
+/// The stage of work that a sync is in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SyncPhase {
+    /// Downloading note commitment tree roots.
+    SubtreeRoots,
+    /// Downloading transactions for the wallet's transparent addresses.
+    Transparent,
+    /// Verifying that the wallet's view of the chain tip is still valid.
+    Verify,
+    /// Downloading and scanning compact blocks.
+    Scan,
+    /// Downloading full transactions to fill in memos, statuses and transparent spends.
+    Enhancement,
+    /// Watching the mempool for new transactions while waiting for the next block.
+    Mempool,
+}
+
+/// The priority of a range of blocks in the scan queue, from lowest to highest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScanRangePriority {
+    Ignored,
+    Scanned,
+    Historic,
+    OpenAdjacent,
+    FoundNote,
+    ChainTip,
+    Verify,
+}
+
+impl From<ScanPriority> for ScanRangePriority {
+    fn from(value: ScanPriority) -> Self {
+        match value {
+            ScanPriority::Ignored => ScanRangePriority::Ignored,
+            ScanPriority::Scanned => ScanRangePriority::Scanned,
+            ScanPriority::Historic => ScanRangePriority::Historic,
+            ScanPriority::OpenAdjacent => ScanRangePriority::OpenAdjacent,
+            ScanPriority::FoundNote => ScanRangePriority::FoundNote,
+            ScanPriority::ChainTip => ScanRangePriority::ChainTip,
+            ScanPriority::Verify => ScanRangePriority::Verify,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -67,7 +157,7 @@ pub enum ChainType {
     Testnet,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Pool {
     Transparent,
     Sapling,
@@ -96,6 +186,18 @@ pub struct AccountInfo {
     pub id: u32,
     pub uvk: Option<String>,
     pub birthday_heights: BirthdayHeights,
+    /// The ZIP-32 account index, for accounts derived from a seed held by the wallet.
+    pub zip32_account_index: Option<u32>,
+    /// The ZIP-32 fingerprint of the seed the account was derived from.
+    pub seed_fingerprint: Option<Vec<u8>>,
+    /// Whether the account was derived from a seed or imported with spending authority.
+    /// For a derived account this doesn't mean the wallet holds the seed;
+    /// spending still requires it.
+    pub spending_key_available: bool,
+    /// The pools the account's viewing key covers.
+    pub pools: Vec<Pool>,
+    pub name: Option<String>,
+    pub metadata: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone)]
@@ -107,6 +209,8 @@ pub struct Transaction {
     pub expired_unmined: bool,
     pub account_balance_delta: i64,
     pub fee: Option<u64>,
+    /// The label the user assigned to this transaction, if any.
+    pub label: Option<String>,
     /// Notes that are sent by this transaction (and do not appear in `change`).
     pub outgoing: Vec<TransactionNote>,
     /// Notes that are received by this transaction (and do not appear in `change`).
@@ -124,6 +228,8 @@ pub struct TransparentNote {
 #[derive(Debug, Clone)]
 pub struct TransactionNote {
     pub recipient: String,
+    /// The label for the recipient, from the address book or the wallet's own address labels.
+    pub recipient_label: Option<String>,
     pub pool: Pool,
     pub value: u64,
     pub memo: Option<Vec<u8>>,
@@ -136,6 +242,23 @@ pub struct TransactionSendDetail {
     pub memo: Option<Vec<u8>>,
 }
 
+/// Identifies a note or UTXO by the transaction output that created it.
+#[derive(Debug, Clone)]
+pub struct NoteId {
+    pub txid: Vec<u8>,
+    pub pool: Pool,
+    pub output_index: u32,
+}
+
+/// User-directed selection of the notes that may fund a send.
+#[derive(Debug, Clone)]
+pub struct CoinSelection {
+    /// When set, exactly these notes are spent, and no others.
+    pub spend_only: Option<Vec<NoteId>>,
+    /// Notes that must not be spent. Ignored when `spend_only` is set.
+    pub exclude: Vec<NoteId>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum LightWalletError {
     #[error("Invalid argument: {message}")]
@@ -198,6 +321,9 @@ impl From<Error> for LightWalletError {
             }
             Error::Canceled => LightWalletError::Canceled,
             Error::InvalidArgument(msg) => LightWalletError::InvalidArgument { message: msg },
+            Error::AddressGeneration(_) => LightWalletError::InvalidArgument {
+                message: e.to_string(),
+            },
             Error::Internal(msg) => LightWalletError::Other { message: msg },
             Error::InsufficientFunds {
                 required,
@@ -218,8 +344,47 @@ pub struct DbInit {
     pub data_file: String,
     pub network: ChainType,
     pub min_confirmations: u32,
+    /// The passphrase the database is encrypted with, if any.
+    pub passphrase: Option<String>,
+}
+
+impl DbInit {
+    /// Makes every connection to the database, including those opened by path alone,
+    /// use this configuration's passphrase.
+    ///
+    /// Each entry point that takes a [`DbInit`] calls this first, so the passphrase applies
+    /// even when the database was not initialized earlier in this session.
+    pub(crate) fn apply_passphrase(&self) {
+        set_passphrase(&self.data_file, self.passphrase.clone());
+    }
+}
+
+/// Options that tune how `sync` behaves.
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// The number of blocks to rewind when a chain reorg is first detected at a given height,
+    /// or `None` for [`DEFAULT_REORG_REWIND_DEPTH`].
+    pub reorg_rewind_depth: Option<u32>,
+    /// The most blocks to rewind when reorgs are repeatedly detected at the same height,
+    /// or `None` for [`DEFAULT_MAX_REORG_REWIND_DEPTH`].
+    /// The rewind depth doubles with each repeat, up to this limit.
+    pub max_reorg_rewind_depth: Option<u32>,
+    /// How failed requests to the server are retried, or `None` for the default policy.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Other servers to fail over to when requests to the server fail.
+    /// The servers are ranked by health and latency before the sync starts.
+    pub fallback_servers: Vec<String>,
 }
 
+/// The number of blocks to rewind when a chain reorg is first detected at a given height,
+/// unless [`SyncOptions::reorg_rewind_depth`] says otherwise.
+pub(crate) const DEFAULT_REORG_REWIND_DEPTH: u32 = 10;
+
+/// The most blocks to rewind for repeated reorgs at one height,
+/// unless [`SyncOptions::max_reorg_rewind_depth`] says otherwise.
+/// zcash_client_sqlite only retains enough checkpoints to rewind this far.
+pub(crate) const DEFAULT_MAX_REORG_REWIND_DEPTH: u32 = 100;
+
 lazy_static! {
     static ref CANCELLATION_TOKENS: Mutex<HashMap<u32, CancellationToken>> =
         Mutex::new(HashMap::new());
@@ -269,12 +434,65 @@ pub fn cancel(id: u32) -> Result<(), LightWalletError> {
 }
 
 pub fn init(config: DbInit) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
     RT.block_on(async move {
         Db::init(config.data_file, config.network.into())?;
         Ok(())
     })
 }
 
+pub fn rekey_database(
+    config: DbInit,
+    new_passphrase: Option<String>,
+) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(rekey(&config.data_file, new_passphrase)?)
+}
+
+pub fn maintain_database(
+    config: DbInit,
+    options: MaintenanceOptions,
+) -> Result<MaintenanceReport, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::maintenance::maintain_database(&config, &options)?)
+}
+
+pub fn check_wallet(config: DbInit) -> Result<WalletCheckReport, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::integrity::check_wallet(&config)?)
+}
+
+pub fn repair_wallet(
+    config: DbInit,
+    repairs: Vec<WalletRepair>,
+) -> Result<WalletCheckReport, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::integrity::repair_wallet(&config, &repairs)?)
+}
+
+pub fn export_backup(
+    config: DbInit,
+    path: String,
+    passphrase: String,
+) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::backup::export_backup(&config, path, &passphrase)?)
+}
+
+pub fn restore_backup(
+    path: String,
+    passphrase: String,
+    new_data_file: String,
+    new_passphrase: Option<String>,
+) -> Result<(), LightWalletError> {
+    Ok(crate::backup::restore_backup(
+        path,
+        &passphrase,
+        new_data_file,
+        new_passphrase,
+    )?)
+}
+
 pub fn add_account(
     config: DbInit,
     uri: String,
@@ -284,6 +502,7 @@ pub fn add_account(
     cancellation: Option<Box<dyn CancellationSource>>,
 ) -> Result<u32, LightWalletError> {
     use crate::lightclient::get_block_height;
+    config.apply_passphrase();
     let cancellation_token = get_cancellation_token(cancellation)?;
     let account_index = zip32::AccountId::try_from(account_index).map_err(|_| {
         LightWalletError::InvalidArgument {
@@ -293,7 +512,7 @@ pub fn add_account(
 
     RT.block_on(async move {
         let mut db = Db::load(config.data_file, config.network.into())?;
-        let mut client = get_client(uri.parse()?).await.map_err(Error::from)?;
+        let mut client = get_client(uri.parse()?).await?;
         let birthday_height = match birthday_height {
             Some(v) => v,
             None => get_block_height(uri.parse()?, cancellation_token.0.clone()).await?,
@@ -306,6 +525,29 @@ pub fn add_account(
     })
 }
 
+pub fn discover_accounts(
+    config: DbInit,
+    uri: String,
+    seed: Vec<u8>,
+    max_gap: u32,
+    birthday_height: Option<u32>,
+    cancellation: Option<Box<dyn CancellationSource>>,
+) -> Result<Vec<DiscoveredAccount>, LightWalletError> {
+    config.apply_passphrase();
+    let cancellation_token = get_cancellation_token(cancellation)?;
+    RT.block_on(async move {
+        Ok(crate::discovery::discover_accounts(
+            &config,
+            uri.parse()?,
+            SecretVec::new(seed),
+            max_gap,
+            birthday_height,
+            cancellation_token.0.clone(),
+        )
+        .await?)
+    })
+}
+
 pub fn import_account_ufvk(
     config: DbInit,
     uri: String,
@@ -315,11 +557,12 @@ pub fn import_account_ufvk(
     cancellation: Option<Box<dyn CancellationSource>>,
 ) -> Result<u32, LightWalletError> {
     use crate::lightclient::get_block_height;
+    config.apply_passphrase();
     let cancellation_token = get_cancellation_token(cancellation)?;
     let network: Network = config.network.into();
     RT.block_on(async move {
         let mut db = Db::load(config.data_file, config.network.into())?;
-        let mut client = get_client(uri.parse()?).await.map_err(Error::from)?;
+        let mut client = get_client(uri.parse()?).await?;
         let birthday_height = match birthday_height {
             Some(v) => v,
             None => get_block_height(uri.parse()?, cancellation_token.0.clone()).await?,
@@ -347,43 +590,235 @@ pub fn import_account_ufvk(
 
 pub fn get_accounts(config: DbInit) -> Result<Vec<AccountInfo>, LightWalletError> {
     use crate::analysis::get_birthday_heights;
+    config.apply_passphrase();
 
     let db = Db::load(config.data_file.clone(), config.network.into())?;
     let network: Network = config.network.into();
+    let conn = open_connection(&config.data_file)?;
     let mut result = Vec::new();
-    for account_info in db.data.get_unified_full_viewing_keys()?.iter() {
+    for (account_id, ufvk) in db.data.get_unified_full_viewing_keys()?.iter() {
+        let account = db
+            .data
+            .get_account(*account_id)?
+            .ok_or_else(|| Error::Internal("Account disappeared.".to_string()))?;
+        let (zip32_account_index, seed_fingerprint, spending_key_available) = match account.source()
+        {
+            AccountSource::Derived {
+                seed_fingerprint,
+                account_index,
+            } => (
+                Some(account_index.into()),
+                Some(seed_fingerprint.to_bytes().to_vec()),
+                true,
+            ),
+            AccountSource::Imported { purpose } => {
+                (None, None, matches!(purpose, AccountPurpose::Spending))
+            }
+        };
+
+        let mut pools = Vec::new();
+        if ufvk.transparent().is_some() {
+            pools.push(Pool::Transparent);
+        }
+        if ufvk.sapling().is_some() {
+            pools.push(Pool::Sapling);
+        }
+        if ufvk.orchard().is_some() {
+            pools.push(Pool::Orchard);
+        }
+
+        let (name, metadata) = get_account_metadata(&conn, *account_id)?;
         result.push(AccountInfo {
-            id: account_info.0.to_owned().into(),
-            uvk: Some(account_info.1.encode(&network)),
-            birthday_heights: get_birthday_heights(config.clone(), account_info.0.to_owned())?,
+            id: (*account_id).into(),
+            uvk: Some(ufvk.encode(&network)),
+            birthday_heights: get_birthday_heights(config.clone(), *account_id)?,
+            zip32_account_index,
+            seed_fingerprint,
+            spending_key_available,
+            pools,
+            name,
+            metadata,
         });
     }
 
     Ok(result)
 }
 
+pub fn find_account_by_seed(
+    config: DbInit,
+    seed: Vec<u8>,
+    account_index: u32,
+) -> Result<Option<u32>, LightWalletError> {
+    config.apply_passphrase();
+    let account_index = zip32::AccountId::try_from(account_index).map_err(|_| {
+        LightWalletError::InvalidArgument {
+            message: "Invalid account index".to_string(),
+        }
+    })?;
+    Ok(
+        crate::accounts::find_account_by_seed(&config, &SecretVec::new(seed), account_index)?
+            .map(|id| id.into()),
+    )
+}
+
+pub fn verify_spending_key(
+    config: DbInit,
+    account_id: u32,
+    usk: Vec<u8>,
+) -> Result<bool, LightWalletError> {
+    config.apply_passphrase();
+    let usk = UnifiedSpendingKey::from_bytes(Era::Orchard, &usk).map_err(|_| {
+        LightWalletError::InvalidArgument {
+            message: "Failure when parsing USK.".to_string(),
+        }
+    })?;
+    Ok(crate::accounts::verify_spending_key(
+        &config,
+        account_id.into(),
+        &usk,
+    )?)
+}
+
+pub fn rescan_from(config: DbInit, height: u32) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::rescan::rescan_from(&config, height)?)
+}
+
+pub fn rescan_account(config: DbInit, account_id: u32) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::rescan::rescan_account(&config, account_id.into())?)
+}
+
+pub fn remove_account(config: DbInit, account_id: u32) -> Result<bool, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::accounts::remove_account(&config, account_id.into())?)
+}
+
+pub fn set_account_name(
+    config: DbInit,
+    account_id: u32,
+    name: Option<String>,
+) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::accounts::set_account_name(
+        &config,
+        account_id.into(),
+        name,
+    )?)
+}
+
+pub fn set_account_metadata(
+    config: DbInit,
+    account_id: u32,
+    key: String,
+    value: Option<String>,
+) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::accounts::set_account_metadata(
+        &config,
+        account_id.into(),
+        key,
+        value,
+    )?)
+}
+
 pub fn add_diversifier(
     config: DbInit,
     account: u32,
     diversifier_index: Vec<u8>,
+    receiver_types: Option<Vec<Pool>>,
 ) -> Result<String, LightWalletError> {
-    RT.block_on(async move {
-        let network = config.network.into();
-        let mut db = Db::load(config.data_file, network)?;
-        let diversified_index: [u8; 11] =
-            diversifier_index
-                .try_into()
-                .map_err(|_| LightWalletError::InvalidArgument {
-                    message: "Bad diversifier".to_string(),
-                })?;
-        let diversifier_index = DiversifierIndex::from(diversified_index);
-        Ok(db
-            .add_diversifier(account.into(), diversifier_index)?
-            .encode(&network))
-    })
+    config.apply_passphrase();
+    let diversified_index: [u8; 11] =
+        diversifier_index
+            .try_into()
+            .map_err(|_| LightWalletError::InvalidArgument {
+                message: "Bad diversifier".to_string(),
+            })?;
+    Ok(crate::addresses::add_diversifier(
+        &config,
+        account.into(),
+        DiversifierIndex::from(diversified_index),
+        receiver_types.as_deref(),
+    )?)
+}
+
+pub fn list_addresses(
+    config: DbInit,
+    account_id: u32,
+) -> Result<Vec<AccountAddress>, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::addresses::list_addresses(
+        &config,
+        account_id.into(),
+    )?)
+}
+
+pub fn next_unused_address(
+    config: DbInit,
+    account_id: u32,
+    receiver_types: Vec<Pool>,
+) -> Result<String, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::addresses::next_unused_address(
+        &config,
+        account_id.into(),
+        &receiver_types,
+    )?)
+}
+
+pub fn set_address_label(
+    config: DbInit,
+    account_id: u32,
+    diversifier_index: Vec<u8>,
+    label: Option<String>,
+) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    let diversifier_index: [u8; 11] =
+        diversifier_index
+            .try_into()
+            .map_err(|_| LightWalletError::InvalidArgument {
+                message: "Bad diversifier".to_string(),
+            })?;
+    Ok(crate::address_book::set_address_label(
+        &config,
+        account_id,
+        DiversifierIndex::from(diversifier_index),
+        label,
+    )?)
+}
+
+pub fn set_transaction_label(
+    config: DbInit,
+    txid: Vec<u8>,
+    label: Option<String>,
+) -> Result<(), LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::address_book::set_transaction_label(
+        &config, txid, label,
+    )?)
+}
+
+pub fn set_address_book_entry(
+    config: DbInit,
+    entry: AddressBookEntry,
+) -> Result<u32, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::address_book::set_address_book_entry(&config, entry)?)
+}
+
+pub fn get_address_book(config: DbInit) -> Result<Vec<AddressBookEntry>, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::address_book::get_address_book(&config)?)
+}
+
+pub fn remove_address_book_entry(config: DbInit, id: u32) -> Result<bool, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::address_book::remove_address_book_entry(&config, id)?)
 }
 
 pub fn get_birthday_height(config: DbInit) -> Result<Option<u32>, LightWalletError> {
+    config.apply_passphrase();
     RT.block_on(async move {
         let db = Db::load(config.data_file, config.network.into())?;
         Ok(db.data.get_wallet_birthday()?.map(|h| h.into()))
@@ -401,20 +836,41 @@ pub fn get_block_height(
 }
 
 pub fn get_sync_height(config: DbInit) -> Result<Option<u32>, LightWalletError> {
+    config.apply_passphrase();
     RT.block_on(async move {
         let db = Db::load(config.data_file, config.network.into())?;
         Ok(db.data.get_max_height_hash()?.map(|h| h.0.into()))
     })
 }
 
+pub fn set_parameter_paths(paths: Option<ParameterPaths>) -> Result<(), LightWalletError> {
+    Ok(crate::prover::set_parameter_paths(paths)?)
+}
+
+pub fn prefetch_parameters(
+    progress: Option<Box<dyn ParameterDownloadProgress>>,
+) -> Result<(), LightWalletError> {
+    Ok(crate::prover::prefetch_parameters(progress.as_deref())?)
+}
+
+pub fn release_prover() {
+    crate::prover::release_prover()
+}
+
+pub fn set_log_sink(sink: Option<Box<dyn LogSink>>, level: LogLevel) {
+    crate::diagnostics::set_log_sink(sink, level)
+}
+
 pub fn sync(
     config: DbInit,
     uri: String,
     progress: Option<Box<dyn SyncUpdate>>,
     continually: bool,
     cancellation: Option<Box<dyn CancellationSource>>,
+    options: Option<SyncOptions>,
 ) -> Result<SyncUpdateData, LightWalletError> {
     use crate::sync::sync;
+    config.apply_passphrase();
     let uri: Uri = uri.parse()?;
     let cancellation_token = get_cancellation_token(cancellation)?;
     RT.block_on(async move {
@@ -422,8 +878,8 @@ pub fn sync(
             uri,
             config.data_file,
             progress,
-            config.min_confirmations,
             continually,
+            options.unwrap_or_default(),
             cancellation_token.0.clone(),
         )
         .await?)
@@ -435,9 +891,10 @@ pub fn get_transactions(
     account_id: u32,
     starting_block: u32,
 ) -> Result<Vec<Transaction>, LightWalletError> {
+    config.apply_passphrase();
     let network: Network = config.network.into();
     let mut db = Db::load(config.data_file.clone(), network)?;
-    let mut conn = Connection::open(config.data_file)?;
+    let mut conn = open_connection(config.data_file)?;
     Ok(crate::sync::get_transactions(
         &mut db,
         &mut conn,
@@ -453,6 +910,7 @@ pub fn get_birthday_heights(
     account_id: u32,
 ) -> Result<BirthdayHeights, LightWalletError> {
     use crate::analysis::get_birthday_heights;
+    config.apply_passphrase();
 
     Ok(get_birthday_heights(config, account_id.into())?)
 }
@@ -462,6 +920,7 @@ pub fn get_user_balances(
     account_id: u32,
 ) -> Result<UserBalances, LightWalletError> {
     use crate::analysis::get_user_balances;
+    config.apply_passphrase();
     Ok(get_user_balances(
         &config,
         account_id.into(),
@@ -470,11 +929,38 @@ pub fn get_user_balances(
     )?)
 }
 
+pub fn list_notes(config: DbInit, account_id: u32) -> Result<Vec<UnspentNote>, LightWalletError> {
+    use crate::analysis::get_unspent_notes;
+    config.apply_passphrase();
+    Ok(get_unspent_notes(
+        &config,
+        account_id.into(),
+        NonZeroU32::try_from(config.min_confirmations)
+            .map_err(|_| Error::InvalidArgument("A positive integer is required.".to_string()))?,
+    )?)
+}
+
 pub fn disconnect_server(uri: String) -> Result<bool, LightWalletError> {
     let uri: Uri = uri.parse()?;
     RT.block_on(async move { Ok(destroy_channel(uri)) })
 }
 
+pub fn set_connection_options(
+    uri: String,
+    options: Option<ConnectionOptions>,
+) -> Result<(), LightWalletError> {
+    crate::grpc::set_connection_options(uri.parse()?, options);
+    Ok(())
+}
+
+pub fn rank_servers(uris: Vec<String>) -> Result<Vec<ServerStatus>, LightWalletError> {
+    let uris = uris
+        .iter()
+        .map(|uri| uri.parse())
+        .collect::<Result<Vec<Uri>, _>>()?;
+    RT.block_on(async move { Ok(crate::grpc::rank_servers(uris).await) })
+}
+
 pub struct SendTransactionResult {
     pub txid: Vec<u8>,
 }
@@ -487,7 +973,9 @@ pub fn simulate_send(
     config: DbInit,
     ufvk: String,
     send_details: Vec<TransactionSendDetail>,
+    coin_selection: Option<CoinSelection>,
 ) -> Result<SendDetails, LightWalletError> {
+    config.apply_passphrase();
     let network = config.network.into();
     let mut db = Db::init(config.data_file, network)?;
     let ufvk = UnifiedFullViewingKey::decode(&network, &ufvk)
@@ -497,7 +985,14 @@ pub fn simulate_send(
             message: "A positive integer is required.".to_string(),
         }
     })?;
-    let proposal = create_send_proposal(&mut db, network, &ufvk, min_confirmations, send_details)?;
+    let proposal = create_send_proposal(
+        &mut db,
+        network,
+        &ufvk,
+        min_confirmations,
+        send_details,
+        coin_selection.as_ref(),
+    )?;
 
     Ok(SendDetails {
         fee: proposal
@@ -513,7 +1008,9 @@ pub fn send(
     uri: String,
     usk: Vec<u8>,
     send_details: Vec<TransactionSendDetail>,
+    coin_selection: Option<CoinSelection>,
 ) -> Result<Vec<SendTransactionResult>, LightWalletError> {
+    config.apply_passphrase();
     let uri: Uri = uri.parse()?;
     let usk = UnifiedSpendingKey::from_bytes(Era::Orchard, &usk).map_err(|_| {
         LightWalletError::InvalidArgument {
@@ -530,6 +1027,7 @@ pub fn send(
                 Error::InvalidArgument("A positive integer is required.".to_string())
             })?,
             send_details,
+            coin_selection.as_ref(),
         )
         .await?;
         Ok(result
@@ -541,11 +1039,48 @@ pub fn send(
     })
 }
 
+pub fn send_batch(
+    config: DbInit,
+    uri: String,
+    usk: Vec<u8>,
+    batch_id: String,
+    send_details: Vec<TransactionSendDetail>,
+    options: Option<BatchOptions>,
+) -> Result<BatchResult, LightWalletError> {
+    config.apply_passphrase();
+    let uri: Uri = uri.parse()?;
+    let usk = UnifiedSpendingKey::from_bytes(Era::Orchard, &usk).map_err(|_| {
+        LightWalletError::InvalidArgument {
+            message: "Failure when parsing USK.".to_string(),
+        }
+    })?;
+    RT.block_on(async move {
+        Ok(crate::batch::send_batch(
+            &config,
+            uri,
+            &usk,
+            &batch_id,
+            send_details,
+            &options.unwrap_or_default(),
+        )
+        .await?)
+    })
+}
+
+pub fn get_batch(
+    config: DbInit,
+    batch_id: String,
+) -> Result<Option<BatchResult>, LightWalletError> {
+    config.apply_passphrase();
+    Ok(crate::batch::get_batch(&config, &batch_id)?)
+}
+
 pub fn get_unshielded_utxos(
     config: DbInit,
     account_id: u32,
 ) -> Result<Vec<TransparentNote>, LightWalletError> {
     use crate::shield::get_unshielded_utxos;
+    config.apply_passphrase();
     Ok(get_unshielded_utxos(config, account_id.into())?)
 }
 
@@ -555,6 +1090,7 @@ pub fn shield(
     usk: Vec<u8>,
     address: String,
 ) -> Result<Vec<SendTransactionResult>, LightWalletError> {
+    config.apply_passphrase();
     let uri: Uri = uri.parse()?;
     let usk = UnifiedSpendingKey::from_bytes(Era::Orchard, &usk).map_err(|_| {
         LightWalletError::InvalidArgument {
@@ -594,4 +1130,24 @@ mod tests {
 
         assert!(transactions.is_empty());
     }
+
+    #[test]
+    fn test_get_accounts_reports_key_source() {
+        let (setup, account_id) = RT.block_on(async move {
+            let mut setup = setup_test().await;
+            let (_, _, account_id, _) = setup.create_account().await.unwrap();
+            (setup, account_id)
+        });
+        let accounts = get_accounts(setup.db_init).unwrap();
+        let account = accounts
+            .iter()
+            .find(|a| a.id == u32::from(account_id))
+            .unwrap();
+
+        assert_eq!(account.zip32_account_index, Some(0));
+        assert_eq!(account.seed_fingerprint.as_ref().map(|f| f.len()), Some(32));
+        assert!(account.spending_key_available);
+        assert!(account.pools.contains(&Pool::Orchard));
+        assert!(account.pools.contains(&Pool::Sapling));
+    }
 }
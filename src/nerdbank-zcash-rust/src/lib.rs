@@ -3,32 +3,62 @@ uniffi::include_scaffolding!("ffi");
 #[macro_use]
 extern crate lazy_static;
 
+mod accounts;
+mod address_book;
+mod addresses;
 mod analysis;
 mod backing_store;
+mod backup;
+mod batch;
 mod block_source;
 mod blockrange;
+mod diagnostics;
+mod discovery;
 mod error;
 mod grpc;
+mod integrity;
 mod interop;
 mod lightclient;
+mod maintenance;
+mod migrations;
 mod orchard;
 mod prover;
+mod rescan;
 mod resilience;
 mod sapling;
 mod send;
 mod shield;
 mod sql_statements;
 mod sync;
+mod tor;
 
 #[cfg(test)]
 mod test_constants;
 
-use analysis::{BirthdayHeights, UserBalances};
+use address_book::AddressBookEntry;
+use addresses::AccountAddress;
+use analysis::{BirthdayHeights, NoteSpendability, UnspentNote, UserBalances};
+use batch::{BatchOptions, BatchPayment, BatchPaymentStatus, BatchResult};
+use diagnostics::{LogLevel, LogSink};
+use discovery::DiscoveredAccount;
+use grpc::{ConnectionOptions, ServerStatus};
+use integrity::{HeightRange, WalletCheckReport, WalletRepair};
 use interop::{
-    add_account, add_diversifier, cancel, disconnect_server, get_accounts, get_birthday_height,
-    get_birthday_heights, get_block_height, get_sync_height, get_transactions,
-    get_unshielded_utxos, get_user_balances, import_account_ufvk, init, send, shield,
-    simulate_send, sync, AccountInfo, CancellationSource, ChainType, DbInit, LightWalletError,
-    Pool, SendDetails, SendTransactionResult, SyncUpdate, SyncUpdateData, Transaction,
-    TransactionNote, TransactionSendDetail, TransparentNote,
+    add_account, add_diversifier, cancel, check_wallet, disconnect_server, discover_accounts,
+    export_backup, find_account_by_seed, get_accounts, get_address_book, get_batch,
+    get_birthday_height, get_birthday_heights, get_block_height, get_sync_height, get_transactions,
+    get_unshielded_utxos, get_user_balances, import_account_ufvk, init, list_addresses, list_notes,
+    maintain_database, next_unused_address, prefetch_parameters, rank_servers, rekey_database,
+    release_prover, remove_account, remove_address_book_entry, repair_wallet, rescan_account,
+    rescan_from, restore_backup, send, send_batch, set_account_metadata, set_account_name,
+    set_address_book_entry, set_address_label, set_connection_options, set_log_sink,
+    set_parameter_paths, set_transaction_label, shield, simulate_send, sync, verify_spending_key,
+    AccountInfo, CancellationSource, ChainType, CoinSelection, DbInit, LightWalletError, NoteId,
+    Pool, ScanRangePriority, SendDetails, SendTransactionResult, SyncOptions, SyncPhase,
+    SyncSummary, SyncUpdate, SyncUpdateData, Transaction, TransactionNote, TransactionSendDetail,
+    TransparentNote,
 };
+use maintenance::{MaintenanceOptions, MaintenanceReport};
+use prover::{ParameterDownloadProgress, ParameterPaths};
+use resilience::RetryPolicy;
+use tor::{set_tor, TorOptions};
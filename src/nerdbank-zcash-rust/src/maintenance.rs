@@ -0,0 +1,270 @@
+use rusqlite::{named_params, Connection};
+
+use crate::{
+    backing_store::open_connection,
+    error::Error,
+    interop::DbInit,
+    sql_statements::{GET_FULLY_SCANNED_HEIGHT, PRUNE_NULLIFIER_MAP, PRUNE_TX_LOCATOR_MAP},
+};
+
+/// How far below the fully scanned height nullifiers are retained,
+/// so that a reorg doesn't require data that has been pruned.
+/// This matches the pruning depth that zcash_client_sqlite uses for its note commitment trees.
+const NULLIFIER_RETENTION_DEPTH: u32 = 100;
+
+/// Selects the maintenance work that `maintain_database` does.
+#[derive(Debug, Clone)]
+pub struct MaintenanceOptions {
+    /// Removes nullifiers observed in blocks that every account has already scanned past.
+    /// These are only needed to detect spends while blocks are scanned out of order.
+    pub prune_nullifier_map: bool,
+    /// Merges adjacent scan ranges that share a priority into one range.
+    pub compact_scan_queue: bool,
+    /// Rebuilds every index.
+    pub reindex: bool,
+    /// Rewrites the database file to release free pages.
+    pub vacuum: bool,
+}
+
+/// The outcome of `maintain_database`.
+#[derive(Debug, Clone)]
+pub struct MaintenanceReport {
+    /// The size of the database before maintenance, in bytes.
+    pub size_before: u64,
+    /// The size of the database after maintenance, in bytes.
+    pub size_after: u64,
+    pub bytes_reclaimed: u64,
+    pub nullifiers_pruned: u64,
+    /// The number of scan queue rows that were eliminated by merging.
+    pub scan_ranges_merged: u64,
+}
+
+/// Prunes data the wallet no longer needs and compacts the database file.
+///
+/// This should not run concurrently with a sync, but is safe to call between syncs.
+/// The wallet's spendable notes and transaction history are never pruned.
+pub fn maintain_database(
+    config: &DbInit,
+    options: &MaintenanceOptions,
+) -> Result<MaintenanceReport, Error> {
+    let mut conn = open_connection(&config.data_file)?;
+    let size_before = database_size(&conn)?;
+
+    let tx = conn.transaction()?;
+    let nullifiers_pruned = if options.prune_nullifier_map {
+        let fully_scanned: Option<u32> =
+            tx.query_row(GET_FULLY_SCANNED_HEIGHT, [], |row| row.get(0))?;
+        match fully_scanned {
+            Some(height) if height > NULLIFIER_RETENTION_DEPTH => {
+                let prune_below = height - NULLIFIER_RETENTION_DEPTH;
+                let pruned = tx.execute(
+                    PRUNE_NULLIFIER_MAP,
+                    named_params! { ":height": prune_below },
+                )?;
+                tx.execute(
+                    PRUNE_TX_LOCATOR_MAP,
+                    named_params! { ":height": prune_below },
+                )?;
+                pruned as u64
+            }
+            _ => 0,
+        }
+    } else {
+        0
+    };
+
+    let scan_ranges_merged = if options.compact_scan_queue {
+        compact_scan_queue(&tx)?
+    } else {
+        0
+    };
+    tx.commit()?;
+
+    if options.reindex {
+        conn.execute_batch("REINDEX;")?;
+    }
+
+    if options.vacuum {
+        conn.execute_batch("VACUUM;")?;
+    }
+
+    let size_after = database_size(&conn)?;
+    Ok(MaintenanceReport {
+        size_before,
+        size_after,
+        bytes_reclaimed: size_before.saturating_sub(size_after),
+        nullifiers_pruned,
+        scan_ranges_merged,
+    })
+}
+
+/// Merges adjacent scan ranges with the same priority.
+///
+/// Returns the number of rows eliminated.
+fn compact_scan_queue(conn: &Connection) -> Result<u64, Error> {
+    let mut stmt = conn.prepare(
+        "SELECT block_range_start, block_range_end, priority FROM scan_queue ORDER BY block_range_start",
+    )?;
+    let ranges = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, u32>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut merged: Vec<(u32, u32, i64)> = Vec::with_capacity(ranges.len());
+    for (start, end, priority) in ranges.iter().copied() {
+        match merged.last_mut() {
+            Some(last) if last.1 == start && last.2 == priority => last.1 = end,
+            _ => merged.push((start, end, priority)),
+        }
+    }
+
+    let eliminated = (ranges.len() - merged.len()) as u64;
+    if eliminated > 0 {
+        conn.execute("DELETE FROM scan_queue", [])?;
+        let mut insert = conn.prepare(
+            "INSERT INTO scan_queue (block_range_start, block_range_end, priority) VALUES (:start, :end, :priority)",
+        )?;
+        for (start, end, priority) in merged {
+            insert.execute(named_params! {
+                ":start": start,
+                ":end": end,
+                ":priority": priority,
+            })?;
+        }
+    }
+
+    Ok(eliminated)
+}
+
+fn database_size(conn: &Connection) -> Result<u64, Error> {
+    let page_count: u64 = conn.pragma_query_value(None, "page_count", |row| row.get(0))?;
+    let page_size: u64 = conn.pragma_query_value(None, "page_size", |row| row.get(0))?;
+    Ok(page_count * page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_constants::setup_test;
+
+    use super::*;
+
+    fn scan_queue(conn: &Connection) -> Vec<(u32, u32, i64)> {
+        let mut stmt = conn
+            .prepare("SELECT block_range_start, block_range_end, priority FROM scan_queue ORDER BY block_range_start")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    fn insert_nullifier(conn: &Connection, height: u32, nf: u8) {
+        conn.execute(
+            "INSERT INTO tx_locator_map (block_height, tx_index, txid) VALUES (:height, 0, :txid)",
+            named_params! { ":height": height, ":txid": vec![nf; 32] },
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO nullifier_map (spend_pool, nf, block_height, tx_index) VALUES (2, :nf, :height, 0)",
+            named_params! { ":height": height, ":nf": vec![nf; 32] },
+        )
+        .unwrap();
+    }
+
+    fn count_at(conn: &Connection, table: &str, height: u32) -> u32 {
+        conn.query_row(
+            &format!(
+                "SELECT count(*) FROM {} WHERE block_height = :height",
+                table
+            ),
+            named_params! { ":height": height },
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_maintain_database() {
+        let mut setup = setup_test().await;
+        setup.create_account().await.unwrap();
+        setup.sync().await;
+
+        let conn = open_connection(&setup.data_file).unwrap();
+        let fully_scanned: u32 = conn
+            .query_row(GET_FULLY_SCANNED_HEIGHT, [], |row| row.get(0))
+            .unwrap();
+        let stale_height = fully_scanned - NULLIFIER_RETENTION_DEPTH - 1;
+        insert_nullifier(&conn, stale_height, 1);
+        insert_nullifier(&conn, fully_scanned, 2);
+
+        // Split the last scan range in two, so there is something to merge.
+        let queue_before = scan_queue(&conn);
+        let (start, end, priority) = *queue_before.last().unwrap();
+        assert!(end - start >= 2);
+        let middle = start + (end - start) / 2;
+        conn.execute(
+            "UPDATE scan_queue SET block_range_end = :middle WHERE block_range_start = :start",
+            named_params! { ":middle": middle, ":start": start },
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO scan_queue (block_range_start, block_range_end, priority) VALUES (:middle, :end, :priority)",
+            named_params! { ":middle": middle, ":end": end, ":priority": priority },
+        )
+        .unwrap();
+
+        let report = maintain_database(
+            &setup.db_init,
+            &MaintenanceOptions {
+                prune_nullifier_map: true,
+                compact_scan_queue: true,
+                reindex: true,
+                vacuum: true,
+            },
+        )
+        .unwrap();
+        assert!(report.size_after <= report.size_before);
+
+        // Only the nullifiers that are deep enough are pruned.
+        assert!(report.nullifiers_pruned >= 1);
+        assert_eq!(count_at(&conn, "nullifier_map", stale_height), 0);
+        assert_eq!(count_at(&conn, "tx_locator_map", stale_height), 0);
+        assert_eq!(count_at(&conn, "nullifier_map", fully_scanned), 1);
+        assert_eq!(count_at(&conn, "tx_locator_map", fully_scanned), 1);
+
+        // The scan queue covers the same blocks with the same priorities, in fewer rows.
+        assert!(report.scan_ranges_merged >= 1);
+        let queue_after = scan_queue(&conn);
+        assert!(queue_after.len() <= queue_before.len());
+        assert_eq!(
+            queue_after.first().unwrap().0,
+            queue_before.first().unwrap().0
+        );
+        assert_eq!(
+            queue_after.last().unwrap().1,
+            queue_before.last().unwrap().1
+        );
+        for range in &queue_after {
+            assert!(range.0 < range.1);
+        }
+        for pair in queue_after.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0, "Scan ranges must be contiguous.");
+            assert_ne!(
+                pair[0].2, pair[1].2,
+                "Adjacent ranges should have been merged."
+            );
+        }
+        for height in [start, middle, end - 1] {
+            let range = queue_after
+                .iter()
+                .find(|r| r.0 <= height && height < r.1)
+                .unwrap();
+            assert_eq!(range.2, priority);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use rusqlite::{Connection, Transaction};
+use schemer::{migration, Migrator};
+use schemer_rusqlite::{RusqliteAdapter, RusqliteMigration};
+
+use crate::error::Error;
+
+/// The table in which we track which of *our* migrations have been applied.
+/// This must be distinct from the table that zcash_client_sqlite uses for its own migrations.
+const MIGRATIONS_TABLE: &str = "nerdbank_migrations";
+
+/// Creates or upgrades the tables that this library adds to the wallet database,
+/// alongside the tables that zcash_client_sqlite owns.
+pub(crate) fn init_app_tables(conn: &mut Connection) -> Result<(), Error> {
+    let adapter = RusqliteAdapter::new(conn, Some(MIGRATIONS_TABLE.to_string()));
+    adapter.init()?;
+
+    let mut migrator = Migrator::new(adapter);
+    migrator.register_multiple(all_migrations())?;
+    migrator.up(None)?;
+
+    Ok(())
+}
+
+fn all_migrations() -> Vec<Box<dyn RusqliteMigration<Error = rusqlite::Error>>> {
+    vec![
+        Box::new(AddressBook),
+        Box::new(AddressMetadata),
+        Box::new(AccountMetadata),
+        Box::new(BatchPayments),
+    ]
+}
+
+/// Adds the address book, and labels for the wallet's own addresses and its transactions.
+struct AddressBook;
+
+migration!(
+    AddressBook,
+    "3b1c0f4e-7d2a-4c55-9a0e-2f6a8e51b9d3",
+    [],
+    "Add the address book and labels for addresses and transactions."
+);
+
+impl RusqliteMigration for AddressBook {
+    type Error = rusqlite::Error;
+
+    fn up(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			CREATE TABLE address_book (
+				id INTEGER PRIMARY KEY,
+				address TEXT NOT NULL UNIQUE,
+				label TEXT NOT NULL
+			);
+			CREATE TABLE address_labels (
+				account_id INTEGER NOT NULL,
+				diversifier_index_be BLOB NOT NULL,
+				label TEXT NOT NULL,
+				PRIMARY KEY (account_id, diversifier_index_be)
+			);
+			CREATE TABLE transaction_labels (
+				txid BLOB NOT NULL PRIMARY KEY,
+				label TEXT NOT NULL
+			);
+			"#,
+        )
+    }
+
+    fn down(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			DROP TABLE transaction_labels;
+			DROP TABLE address_labels;
+			DROP TABLE address_book;
+			"#,
+        )
+    }
+}
+
+/// Tracks when each of the wallet's diversified addresses was first handed out.
+struct AddressMetadata;
+
+migration!(
+    AddressMetadata,
+    "9e5d2a71-48c3-4f0b-b6e2-5d1c7a3f8e04",
+    ["3b1c0f4e-7d2a-4c55-9a0e-2f6a8e51b9d3"],
+    "Record the creation time of diversified addresses."
+);
+
+impl RusqliteMigration for AddressMetadata {
+    type Error = rusqlite::Error;
+
+    fn up(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			CREATE TABLE address_metadata (
+				account_id INTEGER NOT NULL,
+				diversifier_index_be BLOB NOT NULL,
+				created_at INTEGER NOT NULL,
+				PRIMARY KEY (account_id, diversifier_index_be)
+			);
+			"#,
+        )
+    }
+
+    fn down(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch("DROP TABLE address_metadata;")
+    }
+}
+
+/// Adds a name and arbitrary key-value metadata to each account.
+struct AccountMetadata;
+
+migration!(
+    AccountMetadata,
+    "c47a9b13-2e6f-4d8a-8f51-0b3e6d92a7c5",
+    ["9e5d2a71-48c3-4f0b-b6e2-5d1c7a3f8e04"],
+    "Add names and metadata for accounts."
+);
+
+impl RusqliteMigration for AccountMetadata {
+    type Error = rusqlite::Error;
+
+    fn up(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			CREATE TABLE account_info (
+				account_id INTEGER NOT NULL PRIMARY KEY,
+				name TEXT
+			);
+			CREATE TABLE account_metadata (
+				account_id INTEGER NOT NULL,
+				key TEXT NOT NULL,
+				value TEXT NOT NULL,
+				PRIMARY KEY (account_id, key)
+			);
+			"#,
+        )
+    }
+
+    fn down(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			DROP TABLE account_metadata;
+			DROP TABLE account_info;
+			"#,
+        )
+    }
+}
+
+/// Persists the progress of batch payouts, so that an interrupted batch can be resumed.
+struct BatchPayments;
+
+migration!(
+    BatchPayments,
+    "5f0e8c2d-9b7a-4e13-a6d4-71c2b8e93f60",
+    ["c47a9b13-2e6f-4d8a-8f51-0b3e6d92a7c5"],
+    "Add batch payouts and the transactions that pay them."
+);
+
+impl RusqliteMigration for BatchPayments {
+    type Error = rusqlite::Error;
+
+    fn up(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			CREATE TABLE batches (
+				id TEXT NOT NULL PRIMARY KEY,
+				account_id INTEGER NOT NULL
+			);
+			CREATE TABLE batch_transactions (
+				id INTEGER PRIMARY KEY,
+				batch_id TEXT NOT NULL REFERENCES batches(id) ON DELETE CASCADE,
+				txid BLOB NOT NULL,
+				broadcast INTEGER NOT NULL DEFAULT 0,
+				error TEXT
+			);
+			CREATE TABLE batch_payments (
+				batch_id TEXT NOT NULL REFERENCES batches(id) ON DELETE CASCADE,
+				payment_index INTEGER NOT NULL,
+				recipient TEXT NOT NULL,
+				value INTEGER NOT NULL,
+				memo BLOB,
+				transaction_id INTEGER REFERENCES batch_transactions(id),
+				error TEXT,
+				PRIMARY KEY (batch_id, payment_index)
+			);
+			"#,
+        )
+    }
+
+    fn down(&self, transaction: &Transaction<'_>) -> Result<(), Self::Error> {
+        transaction.execute_batch(
+            r#"
+			DROP TABLE batch_payments;
+			DROP TABLE batch_transactions;
+			DROP TABLE batches;
+			"#,
+        )
+    }
+}
@@ -1,11 +1,292 @@
-use zcash_proofs::{download_sapling_parameters, prover::LocalTxProver};
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-use crate::error::Error;
+use zcash_proofs::{
+    default_params_folder, prover::LocalTxProver, SaplingParameterPaths, SAPLING_OUTPUT_NAME,
+    SAPLING_SPEND_NAME,
+};
 
-pub(crate) fn get_prover() -> Result<LocalTxProver, Error> {
-    let parameter_paths = download_sapling_parameters(None)?;
+use crate::{error::Error, tor::tor_options};
+
+/// Where `zcash_proofs` downloads the Sapling parameters from, each file in two parts.
+const DOWNLOAD_URL: &str = "https://download.z.cash/downloads";
+
+/// The BLAKE2b-512 hash and size of the Sapling spend parameters.
+const SAPLING_SPEND_HASH: &str = "8270785a1a0d0bc77196f000ee6d221c9c9894f55307bd9357c3f0105d31ca63991ab91324160d8f53e2bbd3c2633a6eb8bdf5205d822e7f3f73edac51b2b70c";
+const SAPLING_SPEND_BYTES: u64 = 47_958_396;
+
+/// The BLAKE2b-512 hash and size of the Sapling output parameters.
+const SAPLING_OUTPUT_HASH: &str = "657e3d38dbb5cb5e7dd2970e8b03d69b4787dd907285b5a7f0790dcc8072f60bf593b32cc2d1c030e00ff5ae64bf84c5c3beb84ddc841d48264b4a171744d028";
+const SAPLING_OUTPUT_BYTES: u64 = 3_592_860;
+
+/// Explicit paths to the Sapling parameter files, to use instead of the default folder.
+#[derive(Debug, Clone)]
+pub struct ParameterPaths {
+    pub spend: String,
+    pub output: String,
+}
+
+/// Receives progress while the Sapling parameters download.
+pub trait ParameterDownloadProgress: Send + Sync + std::fmt::Debug {
+    fn report(&self, bytes_downloaded: u64, total_bytes: u64);
+}
+
+lazy_static! {
+    /// The parameter files to prove with, once they have been found to have the expected hashes.
+    static ref VERIFIED_PATHS: Mutex<Option<SaplingParameterPaths>> = Mutex::new(None);
+
+    /// The prover shared by every send, so the parameters are only loaded once.
+    static ref PROVER: Mutex<Option<Arc<LocalTxProver>>> = Mutex::new(None);
+
+    /// Held while parameter files in the default folder are checked or downloaded,
+    /// so that concurrent callers don't download the same file or remove one another's.
+    static ref DOWNLOADING: Mutex<()> = Mutex::new(());
+}
+
+/// Uses the given parameter files instead of those in the default folder,
+/// or goes back to the default folder when `None`.
+///
+/// The files are checked against the known hashes before they are accepted.
+pub fn set_parameter_paths(paths: Option<ParameterPaths>) -> Result<(), Error> {
+    let paths = paths
+        .map(|paths| {
+            let paths = SaplingParameterPaths {
+                spend: PathBuf::from(paths.spend),
+                output: PathBuf::from(paths.output),
+            };
+            verify(&paths.spend, SAPLING_SPEND_HASH)?;
+            verify(&paths.output, SAPLING_OUTPUT_HASH)?;
+            Ok::<_, Error>(paths)
+        })
+        .transpose()?;
+
+    *VERIFIED_PATHS.lock().unwrap() = paths;
+    release_prover();
+    Ok(())
+}
+
+/// Downloads and checks the Sapling parameters ahead of time, unless they are already present,
+/// so that sending works offline and the first send doesn't wait for the download.
+pub fn prefetch_parameters(progress: Option<&dyn ParameterDownloadProgress>) -> Result<(), Error> {
+    get_parameter_paths(progress)?;
+    Ok(())
+}
+
+/// Gets the shared prover, loading the parameters the first time.
+pub(crate) fn get_prover() -> Result<Arc<LocalTxProver>, Error> {
+    // The lock is held while loading, so that concurrent sends don't each load the parameters.
+    let mut prover = PROVER.lock().unwrap();
+    if let Some(prover) = prover.as_ref() {
+        return Ok(prover.clone());
+    }
+
+    let loaded = Arc::new(load_prover()?);
+    *prover = Some(loaded.clone());
+    Ok(loaded)
+}
+
+/// Frees the memory held by the shared prover. It is loaded again by the next send.
+///
+/// Sends that are in progress keep using the prover until they finish.
+pub fn release_prover() {
+    *PROVER.lock().unwrap() = None;
+}
+
+fn load_prover() -> Result<LocalTxProver, Error> {
+    #[cfg(feature = "bundled-prover")]
+    if VERIFIED_PATHS.lock().unwrap().is_none() {
+        return Ok(LocalTxProver::bundled());
+    }
+
+    let parameter_paths = get_parameter_paths(None)?;
     Ok(LocalTxProver::new(
         &parameter_paths.spend,
         &parameter_paths.output,
     ))
 }
+
+/// Gets the paths to parameter files that have the expected hashes,
+/// downloading any that are missing from the default folder.
+fn get_parameter_paths(
+    progress: Option<&dyn ParameterDownloadProgress>,
+) -> Result<SaplingParameterPaths, Error> {
+    if let Some(paths) = VERIFIED_PATHS.lock().unwrap().clone() {
+        return Ok(paths);
+    }
+
+    let _downloading = DOWNLOADING.lock().unwrap();
+
+    // Another caller may have finished while we waited.
+    if let Some(paths) = VERIFIED_PATHS.lock().unwrap().clone() {
+        return Ok(paths);
+    }
+
+    let folder = default_params_folder().ok_or_else(|| {
+        Error::Internal("Unable to find the folder for the Sapling parameters.".to_string())
+    })?;
+    let paths = SaplingParameterPaths {
+        spend: folder.join(SAPLING_SPEND_NAME),
+        output: folder.join(SAPLING_OUTPUT_NAME),
+    };
+
+    let total_bytes = SAPLING_SPEND_BYTES + SAPLING_OUTPUT_BYTES;
+    let mut downloaded = 0;
+    for (path, hash, bytes) in [
+        (&paths.spend, SAPLING_SPEND_HASH, SAPLING_SPEND_BYTES),
+        (&paths.output, SAPLING_OUTPUT_HASH, SAPLING_OUTPUT_BYTES),
+    ] {
+        if !path.exists() {
+            // While Tor is in use, parameters are only downloaded through its HTTP tunnel port.
+            let proxy = match tor_options() {
+                Some(tor) => Some(tor.http_tunnel_address.ok_or_else(|| {
+                    Error::Internal(
+                        "Sapling parameters are only downloaded through Tor's HTTP tunnel port while Tor is in use."
+                            .to_string(),
+                    )
+                })?),
+                None => None,
+            };
+            download(path, hash, proxy.as_deref(), &mut |chunk| {
+                downloaded += chunk;
+                if let Some(progress) = progress {
+                    progress.report(downloaded, total_bytes);
+                }
+            })?;
+        } else {
+            downloaded += bytes;
+            if let Err(e) = verify(path, hash) {
+                // Remove a corrupt file so that it is downloaded again next time.
+                let _ = fs::remove_file(path);
+                return Err(e);
+            }
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress.report(total_bytes, total_bytes);
+    }
+
+    *VERIFIED_PATHS.lock().unwrap() = Some(paths.clone());
+    Ok(paths)
+}
+
+/// Downloads a parameter file, optionally through an HTTP CONNECT proxy (e.g. Tor's `HTTPTunnelPort`).
+///
+/// The file is only moved into place once it has the expected hash.
+fn download(
+    path: &Path,
+    expected_hash: &str,
+    proxy: Option<&str>,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), Error> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| Error::Internal(format!("Invalid parameter path: {}", path.display())))?;
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+
+    // The name is unique to this process, so that another process downloading the same file can't interleave writes.
+    let partial = path.with_extension(format!("{}.partial", std::process::id()));
+    let result = download_to(&partial, name, proxy, on_progress)
+        .and_then(|()| verify(&partial, expected_hash))
+        .and_then(|()| fs::rename(&partial, path).map_err(Error::from));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+
+    result
+}
+
+fn download_to(
+    partial: &Path,
+    name: &str,
+    proxy: Option<&str>,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), Error> {
+    let mut file = File::create(partial)?;
+    for part in 1..=2 {
+        let mut request = minreq::get(format!("{}/{}.part.{}", DOWNLOAD_URL, name, part));
+        if let Some(proxy) = proxy {
+            request = request.with_proxy(minreq::Proxy::new(proxy)?);
+        }
+        let mut response = request.send_lazy()?;
+        if response.status_code != 200 {
+            return Err(Error::Internal(format!(
+                "Downloading {} failed: {} {}",
+                name, response.status_code, response.reason_phrase
+            )));
+        }
+
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let count = response.read(&mut buffer)?;
+            if count == 0 {
+                break;
+            }
+            file.write_all(&buffer[..count])?;
+            on_progress(count as u64);
+        }
+    }
+    file.sync_all()?;
+
+    Ok(())
+}
+
+/// Checks that a parameter file has the expected BLAKE2b-512 hash.
+fn verify(path: &Path, expected_hash: &str) -> Result<(), Error> {
+    let mut file = File::open(path)?;
+    let mut state = blake2b_simd::Params::new().hash_length(64).to_state();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let count = file.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        state.update(&buffer[..count]);
+    }
+
+    if state.finalize().to_hex().as_str() != expected_hash {
+        return Err(Error::Internal(format!(
+            "{} does not have the expected hash.",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use testdir::testdir;
+
+    use super::*;
+
+    #[test]
+    #[ignore = "Downloads or loads about 50 MB of Sapling parameters."]
+    fn test_prover_is_shared() {
+        let first = get_prover().unwrap();
+        let second = get_prover().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_set_parameter_paths_rejects_wrong_hash() {
+        let dir = testdir!();
+        let spend = dir.join(SAPLING_SPEND_NAME);
+        let output = dir.join(SAPLING_OUTPUT_NAME);
+        fs::write(&spend, b"not the spend parameters").unwrap();
+        fs::write(&output, b"not the output parameters").unwrap();
+
+        let result = set_parameter_paths(Some(ParameterPaths {
+            spend: spend.to_string_lossy().into_owned(),
+            output: output.to_string_lossy().into_owned(),
+        }));
+        assert!(result.is_err());
+    }
+}
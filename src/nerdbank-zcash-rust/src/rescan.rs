@@ -0,0 +1,172 @@
+use rusqlite::{named_params, OptionalExtension};
+use zcash_client_backend::data_api::{WalletRead, WalletWrite};
+use zcash_client_sqlite::{error::SqliteClientError, AccountId};
+use zcash_primitives::consensus::BlockHeight;
+
+use crate::{
+    backing_store::{open_connection, Db},
+    error::Error,
+    integrity::SCAN_PRIORITY_HISTORIC,
+    interop::DbInit,
+    sql_statements::{
+        GET_MIN_BIRTHDAY_HEIGHT, GET_SCANNED_RANGE_SPANNING_HEIGHT, INSERT_SCAN_RANGE,
+        RESCHEDULE_SCANNED_RANGES, SET_SCAN_RANGE_END,
+    },
+};
+
+/// Schedules every block from `height` onward to be scanned again by the next `sync`.
+///
+/// Where the wallet still has note commitment tree checkpoints reaching back to `height`,
+/// the wallet is truncated to `height`, which discards the notes and spends it found in later blocks.
+/// Otherwise the scanned blocks from `height` onward are put back in the scan queue,
+/// and the next `sync` finds their notes and spends again alongside the ones the wallet already has.
+/// An error is returned if `height` is before the wallet's birthday.
+///
+/// Address book entries, labels and account metadata are preserved.
+pub fn rescan_from(config: &DbInit, height: u32) -> Result<(), Error> {
+    let conn = open_connection(&config.data_file)?;
+    let Some(birthday) = conn.query_row(GET_MIN_BIRTHDAY_HEIGHT, [], |row| {
+        row.get::<_, Option<u32>>(0)
+    })?
+    else {
+        // A wallet without accounts has nothing to rescan.
+        return Ok(());
+    };
+    if height < birthday {
+        return Err(Error::InvalidArgument(format!(
+            "The wallet can only rescan from its birthday, block {}, onward.",
+            birthday
+        )));
+    }
+
+    let mut db = Db::load(&config.data_file, config.network.into())?;
+    match db.data.truncate_to_height(BlockHeight::from_u32(height)) {
+        Ok(()) => Ok(()),
+        Err(SqliteClientError::RequestedRewindInvalid(..)) => {
+            drop(db);
+            reschedule_scanned_ranges(config, height)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Schedules the blocks from an account's birthday onward to be scanned again by the next `sync`.
+///
+/// Since every account is scanned together, the rescan also covers the other accounts in the wallet.
+pub fn rescan_account(config: &DbInit, account_id: AccountId) -> Result<(), Error> {
+    let birthday = {
+        let db = Db::load(&config.data_file, config.network.into())?;
+        if db.data.get_account(account_id)?.is_none() {
+            return Err(Error::InvalidArgument("Unknown account.".to_string()));
+        }
+        db.data.get_account_birthday(account_id)?
+    };
+
+    rescan_from(config, birthday.into())
+}
+
+/// Marks the scanned ranges of the scan queue from `height` onward as needing to be scanned,
+/// splitting the range that spans `height`.
+fn reschedule_scanned_ranges(config: &DbInit, height: u32) -> Result<(), Error> {
+    let mut conn = open_connection(&config.data_file)?;
+    let tx = conn.transaction()?;
+    let spanning = tx
+        .query_row(
+            GET_SCANNED_RANGE_SPANNING_HEIGHT,
+            named_params! { ":height": height },
+            |row| Ok((row.get::<_, u32>(0)?, row.get::<_, u32>(1)?)),
+        )
+        .optional()?;
+    if let Some((start, end)) = spanning {
+        tx.execute(
+            SET_SCAN_RANGE_END,
+            named_params! { ":start": start, ":end": height },
+        )?;
+        tx.execute(
+            INSERT_SCAN_RANGE,
+            named_params! {
+                ":start": height,
+                ":end": end,
+                ":priority": SCAN_PRIORITY_HISTORIC,
+            },
+        )?;
+    }
+    tx.execute(
+        RESCHEDULE_SCANNED_RANGES,
+        named_params! {
+            ":height": height,
+            ":priority": SCAN_PRIORITY_HISTORIC,
+        },
+    )?;
+    tx.commit()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bip0039::{Count, English, Mnemonic};
+    use matches::assert_matches;
+    use secrecy::SecretVec;
+
+    use crate::test_constants::setup_test;
+
+    use super::*;
+
+    #[tokio_shared_rt::test]
+    async fn test_rescan_from() {
+        let mut setup = setup_test().await;
+        let (_, birthday, _, _) = setup.create_account().await.unwrap();
+        let status = setup.sync().await;
+
+        rescan_from(&setup.db_init, status.tip_height - 10).unwrap();
+        assert!(!setup.db.data.suggest_scan_ranges().unwrap().is_empty());
+
+        // The next sync scans the truncated blocks again.
+        setup.sync().await;
+        assert!(setup.db.data.suggest_scan_ranges().unwrap().is_empty());
+
+        // Blocks before the wallet's birthday can't be rescanned.
+        assert_matches!(
+            rescan_from(&setup.db_init, birthday as u32 - 1),
+            Err(Error::InvalidArgument(_))
+        );
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_rescan_from_before_earliest_checkpoint() {
+        let mut setup = setup_test().await;
+        let seed = SecretVec::new(
+            Mnemonic::<English>::generate(Count::Words24)
+                .to_seed("")
+                .to_vec(),
+        );
+        let birthday = setup.server_info.block_height - 300;
+        setup
+            .db
+            .add_account(&seed, zip32::AccountId::ZERO, birthday, &mut setup.client)
+            .await
+            .unwrap();
+        let status = setup.sync().await;
+
+        // The wallet only keeps checkpoints for about the last 100 blocks.
+        let height = status.tip_height - 250;
+        rescan_from(&setup.db_init, height).unwrap();
+        let ranges = setup.db.data.suggest_scan_ranges().unwrap();
+        assert!(ranges
+            .iter()
+            .any(|r| u32::from(r.block_range().start) == height));
+
+        setup.sync().await;
+        assert!(setup.db.data.suggest_scan_ranges().unwrap().is_empty());
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_rescan_unknown_account() {
+        let setup = setup_test().await;
+        assert_matches!(
+            rescan_account(&setup.db_init, 99u32.into()),
+            Err(Error::InvalidArgument(_))
+        );
+    }
+}
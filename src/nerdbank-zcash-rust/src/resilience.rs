@@ -1,55 +1,125 @@
-use std::time::Duration;
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use futures_util::Future;
+use futures_util::{stream, Future, Stream, StreamExt, TryStreamExt};
+use rand::Rng;
 use tokio::select;
 use tokio_util::sync::CancellationToken;
-use tonic::Status;
+use tonic::{transport::Channel, Code, Status, Streaming};
 use tracing::warn;
+use zcash_client_backend::proto::{
+    compact_formats::CompactBlock,
+    service::{
+        self, compact_tx_streamer_client::CompactTxStreamerClient, LightdInfo, RawTransaction,
+        SendResponse, SubtreeRoot, TransparentAddressBlockFilter, TreeState, TxFilter,
+    },
+};
 
-const ATTEMPT_LIMIT: u32 = 3;
-const DELAY_BETWEEN_RETRIES: Duration = Duration::from_secs(2);
+use crate::grpc::ServerPool;
 
-pub(crate) async fn _webrequest_with_logged_retry<
+/// Controls how failed requests to the light wallet server are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The most times a failed request is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry, in milliseconds.
+    pub initial_delay_ms: u64,
+    /// The longest delay between retries, in milliseconds.
+    pub max_delay_ms: u64,
+    /// The factor the delay grows by after each retry.
+    pub backoff_multiplier: f64,
+    /// The fraction (from 0 to 1) of each delay that is randomized,
+    /// so that many clients don't retry in lockstep.
+    pub jitter: f64,
+    /// The time after which a failing request is no longer retried, in milliseconds.
+    pub max_elapsed_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_delay_ms: 2_000,
+            max_delay_ms: 30_000,
+            backoff_multiplier: 2.0,
+            jitter: 0.2,
+            max_elapsed_ms: 120_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Gets the delay before the given retry (starting from 1).
+    fn delay(&self, retry: u32) -> Duration {
+        let base = self.initial_delay_ms as f64
+            * self
+                .backoff_multiplier
+                .max(1.0)
+                .powi(retry.saturating_sub(1) as i32);
+        let base = base.min(self.max_delay_ms as f64);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+        } else {
+            1.0
+        };
+        Duration::from_millis((base * factor) as u64)
+    }
+}
+
+/// Checks whether a request that failed with the given status might succeed if retried.
+///
+/// Errors that describe the request itself (e.g. an invalid argument) will fail the same way again.
+pub(crate) fn is_retryable(status: &Status) -> bool {
+    matches!(
+        status.code(),
+        Code::Unknown
+            | Code::DeadlineExceeded
+            | Code::ResourceExhausted
+            | Code::Aborted
+            | Code::Internal
+            | Code::Unavailable
+    )
+}
+
+/// Sends a request with the default [`RetryPolicy`].
+pub(crate) async fn webrequest_with_retry<
     TResult,
     FRequest: FnMut() -> FResult,
     FResult: Future<Output = Result<TResult, Status>>,
-    FRetry: Fn(Status, Duration, u32),
 >(
-    mut delegate: FRequest,
-    on_retry: FRetry,
+    delegate: FRequest,
     cancellation_token: CancellationToken,
 ) -> Result<TResult, Status> {
-    let mut failure_count = 0;
-    loop {
-        let result = select! {
-            r = delegate() => Ok(r),
-            _ = cancellation_token.cancelled() => Err(Status::cancelled("Request cancelled")),
-        }?;
-        match result {
-            Ok(result) => return Ok(result),
-            Err(status) => {
-                if failure_count == ATTEMPT_LIMIT {
-                    warn!("Web request failed. No more retries. {:?}", status);
-                    return Err(status);
-                } else {
-                    failure_count += 1;
-                    on_retry(status, DELAY_BETWEEN_RETRIES, failure_count);
-                    tokio::time::sleep(DELAY_BETWEEN_RETRIES).await;
-                }
-            }
-        }
-    }
+    webrequest_with_logged_retry(
+        delegate,
+        &RetryPolicy::default(),
+        |_, _, _| {},
+        cancellation_token,
+    )
+    .await
 }
 
-pub(crate) async fn webrequest_with_retry<
+/// Sends a request, retrying transient failures as the given policy allows.
+///
+/// `on_retry` is called with the failure, the delay before the next attempt and the retry number
+/// (starting from 1) before each retry.
+pub(crate) async fn webrequest_with_logged_retry<
     TResult,
     FRequest: FnMut() -> FResult,
     FResult: Future<Output = Result<TResult, Status>>,
+    FRetry: Fn(&Status, Duration, u32),
 >(
     mut delegate: FRequest,
+    policy: &RetryPolicy,
+    on_retry: FRetry,
     cancellation_token: CancellationToken,
 ) -> Result<TResult, Status> {
-    let mut failure_count = 0;
+    let mut backoff = Backoff::new(policy.clone());
     loop {
         let result = select! {
             r = delegate() => Ok(r),
@@ -57,23 +127,555 @@ pub(crate) async fn webrequest_with_retry<
         }?;
         match result {
             Ok(result) => return Ok(result),
-            Err(status) => {
-                if status.code() == tonic::Code::Cancelled {
-                    return Err(Status::cancelled(status.message()));
+            Err(status) => backoff.wait(status, &on_retry, &cancellation_token).await?,
+        }
+    }
+}
+
+/// Tracks the failures of one request against a [`RetryPolicy`].
+struct Backoff {
+    policy: RetryPolicy,
+    started: Instant,
+    failure_count: u32,
+}
+
+impl Backoff {
+    fn new(policy: RetryPolicy) -> Self {
+        Backoff {
+            policy,
+            started: Instant::now(),
+            failure_count: 0,
+        }
+    }
+
+    /// Waits until the request should be retried after the given failure,
+    /// or returns the failure if it should not be retried.
+    async fn wait<FRetry: Fn(&Status, Duration, u32) + ?Sized>(
+        &mut self,
+        status: Status,
+        on_retry: &FRetry,
+        cancellation_token: &CancellationToken,
+    ) -> Result<(), Status> {
+        if status.code() == Code::Cancelled {
+            return Err(Status::cancelled(status.message()));
+        }
+        if !is_retryable(&status) {
+            return Err(status);
+        }
+
+        self.failure_count += 1;
+        let delay = self.policy.delay(self.failure_count);
+        let out_of_time =
+            self.started.elapsed() + delay > Duration::from_millis(self.policy.max_elapsed_ms);
+        if self.failure_count > self.policy.max_retries || out_of_time {
+            warn!("Web request failed. No more retries. {:?}", status);
+            return Err(status);
+        }
+
+        on_retry(&status, delay, self.failure_count);
+        select! {
+            _ = tokio::time::sleep(delay) => Ok(()),
+            _ = cancellation_token.cancelled() => Err(Status::cancelled("Request cancelled")),
+        }
+    }
+
+    /// Starts counting failures over, after a streaming request has made progress.
+    fn reset(&mut self) {
+        self.started = Instant::now();
+        self.failure_count = 0;
+    }
+}
+
+type RetryCallback = Arc<dyn Fn(&Status, Duration, u32) + Send + Sync>;
+
+/// A stream of messages from the light wallet server.
+type MessageStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Identifies a raw transaction, which carries no txid of its own, by its height and a digest of its encoding.
+fn raw_transaction_key(tx: &RawTransaction) -> (u64, [u8; 32]) {
+    let hash = blake2b_simd::Params::new().hash_length(32).hash(&tx.data);
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(hash.as_bytes());
+    (tx.height, digest)
+}
+
+/// A client for the light wallet server that retries failed requests,
+/// and resumes interrupted streams from the last item received.
+#[derive(Clone)]
+pub(crate) struct ResilientClient {
+    servers: Arc<ServerPool>,
+    policy: RetryPolicy,
+    on_retry: RetryCallback,
+    cancellation_token: CancellationToken,
+}
+
+impl From<CompactTxStreamerClient<Channel>> for ResilientClient {
+    fn from(client: CompactTxStreamerClient<Channel>) -> Self {
+        ResilientClient::new(
+            client.into(),
+            RetryPolicy::default(),
+            CancellationToken::new(),
+        )
+    }
+}
+
+impl ResilientClient {
+    pub(crate) fn new(
+        servers: ServerPool,
+        policy: RetryPolicy,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        ResilientClient {
+            servers: Arc::new(servers),
+            policy,
+            on_retry: Arc::new(|_, _, _| {}),
+            cancellation_token,
+        }
+    }
+
+    /// Sets a callback that is invoked with the failure, the delay and the retry number before each retry.
+    pub(crate) fn with_retry_callback(
+        mut self,
+        on_retry: impl Fn(&Status, Duration, u32) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_retry = Arc::new(on_retry);
+        self
+    }
+
+    /// Gets the servers this client may send requests to.
+    pub(crate) fn servers(&self) -> &Arc<ServerPool> {
+        &self.servers
+    }
+
+    /// Moves on to the next server, so that the retry after this failure goes elsewhere.
+    fn on_failure(&self, status: &Status, delay: Duration, retry: u32) {
+        self.servers.fail_over();
+        (self.on_retry)(status, delay, retry);
+    }
+
+    async fn retry<TResult, FRequest, FResult>(
+        &self,
+        mut request: FRequest,
+    ) -> Result<TResult, Status>
+    where
+        FRequest: FnMut(CompactTxStreamerClient<Channel>) -> FResult,
+        FResult: Future<Output = Result<TResult, Status>>,
+    {
+        webrequest_with_logged_retry(
+            || request(self.servers.client()),
+            &self.policy,
+            |status, delay, retry| self.on_failure(status, delay, retry),
+            self.cancellation_token.clone(),
+        )
+        .await
+    }
+
+    pub(crate) async fn get_lightd_info(&self) -> Result<LightdInfo, Status> {
+        self.retry(|mut client| async move {
+            Ok(client
+                .get_lightd_info(service::Empty {})
+                .await?
+                .into_inner())
+        })
+        .await
+    }
+
+    pub(crate) async fn get_latest_block(&self) -> Result<service::BlockId, Status> {
+        self.retry(|mut client| async move {
+            Ok(client
+                .get_latest_block(service::ChainSpec::default())
+                .await?
+                .into_inner())
+        })
+        .await
+    }
+
+    pub(crate) async fn get_tree_state(
+        &self,
+        request: service::BlockId,
+    ) -> Result<TreeState, Status> {
+        self.retry(|mut client| {
+            let request = request.clone();
+            async move { Ok(client.get_tree_state(request).await?.into_inner()) }
+        })
+        .await
+    }
+
+    pub(crate) async fn get_subtree_roots(
+        &self,
+        request: service::GetSubtreeRootsArg,
+    ) -> Result<Vec<SubtreeRoot>, Status> {
+        self.retry(|mut client| {
+            let request = request.clone();
+            async move {
+                client
+                    .get_subtree_roots(request)
+                    .await?
+                    .into_inner()
+                    .try_collect()
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Gets a transaction by its ID, or `None` if the server does not know of it.
+    pub(crate) async fn get_transaction(
+        &self,
+        request: TxFilter,
+    ) -> Result<Option<RawTransaction>, Status> {
+        self.retry(|mut client| {
+            let request = request.clone();
+            async move {
+                match client.get_transaction(request).await {
+                    Ok(response) => Ok(Some(response.into_inner())),
+                    Err(status) if status.code() == Code::NotFound => Ok(None),
+                    // Workaround for https://github.com/ZcashFoundation/zebra/issues/8786 and https://github.com/zcash/lightwalletd/issues/497
+                    Err(status) if status.code() == Code::Unknown && (status.message() == "-5: No such mempool or blockchain transaction. Use gettransaction for wallet transactions." || status.message() == "0: Transaction not found") => Ok(None),
+                    Err(status) => Err(status),
+                }
+            }
+        })
+        .await
+    }
+
+    pub(crate) async fn send_transaction(
+        &self,
+        request: RawTransaction,
+    ) -> Result<SendResponse, Status> {
+        self.retry(|mut client| {
+            let request = request.clone();
+            async move { Ok(client.send_transaction(request).await?.into_inner()) }
+        })
+        .await
+    }
+
+    /// Opens the stream of transactions entering the mempool.
+    ///
+    /// The stream is expected to end when the next block is mined, so only opening it is retried.
+    pub(crate) async fn get_mempool_stream(&self) -> Result<Streaming<RawTransaction>, Status> {
+        self.retry(|mut client| async move {
+            Ok(client
+                .get_mempool_stream(service::Empty {})
+                .await?
+                .into_inner())
+        })
+        .await
+    }
+
+    /// Gets the transactions involving a transparent address.
+    ///
+    /// An interrupted stream is resumed from the height of the last transaction received.
+    pub(crate) async fn get_taddress_txids(
+        &self,
+        request: TransparentAddressBlockFilter,
+    ) -> Result<Vec<RawTransaction>, Status> {
+        self.collect_taddress_txids(request, |request| {
+            let mut client = self.servers.client();
+            async move {
+                Ok(client
+                    .get_taddress_txids(request)
+                    .await?
+                    .into_inner()
+                    .boxed())
+            }
+        })
+        .await
+    }
+
+    async fn collect_taddress_txids<FOpen, FResult>(
+        &self,
+        mut request: TransparentAddressBlockFilter,
+        mut open: FOpen,
+    ) -> Result<Vec<RawTransaction>, Status>
+    where
+        FOpen: FnMut(TransparentAddressBlockFilter) -> FResult,
+        FResult: Future<Output = Result<MessageStream<RawTransaction>, Status>>,
+    {
+        let mut backoff = Backoff::new(self.policy.clone());
+        let mut transactions: Vec<RawTransaction> = Vec::new();
+        let mut received = HashSet::new();
+        loop {
+            let result = async {
+                let mut stream = open(request.clone()).await?;
+                while let Some(tx) = stream.try_next().await? {
+                    // A resumed stream repeats the transactions at the height it resumed from.
+                    if received.insert(raw_transaction_key(&tx)) {
+                        transactions.push(tx);
+                        backoff.reset();
+                    }
                 }
-                if failure_count == ATTEMPT_LIMIT {
-                    warn!("Web request failed. No more retries. {:?}", status);
-                    return Err(status);
-                } else {
-                    failure_count += 1;
-                    select! {
-                        _ = tokio::time::sleep(DELAY_BETWEEN_RETRIES) => {},
-                        _ = cancellation_token.cancelled() => {
-                            return Err(Status::cancelled("Request cancelled"));
-                        },
+                Ok::<_, Status>(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => return Ok(transactions),
+                Err(status) => {
+                    backoff
+                        .wait(
+                            status,
+                            &|status, delay, retry| self.on_failure(status, delay, retry),
+                            &self.cancellation_token,
+                        )
+                        .await?;
+                    if let (Some(last), Some(range)) = (transactions.last(), request.range.as_mut())
+                    {
+                        range.start = Some(service::BlockId {
+                            height: last.height,
+                            ..Default::default()
+                        });
                     }
                 }
             }
         }
     }
+
+    /// Streams the compact blocks in a range.
+    ///
+    /// An interrupted stream is resumed from the block after the last one received.
+    pub(crate) fn get_block_range(
+        &self,
+        range: service::BlockRange,
+    ) -> impl Stream<Item = Result<CompactBlock, Status>> + Send + 'static {
+        let servers = self.servers.clone();
+        self.resume_block_range(range, move |range| {
+            let mut client = servers.client();
+            async move { Ok(client.get_block_range(range).await?.into_inner().boxed()) }
+        })
+    }
+
+    fn resume_block_range<FOpen, FResult>(
+        &self,
+        range: service::BlockRange,
+        open: FOpen,
+    ) -> impl Stream<Item = Result<CompactBlock, Status>> + Send + 'static
+    where
+        FOpen: Fn(service::BlockRange) -> FResult + Send + 'static,
+        FResult: Future<Output = Result<MessageStream<CompactBlock>, Status>> + Send,
+    {
+        struct State<FOpen> {
+            client: ResilientClient,
+            open: FOpen,
+            range: service::BlockRange,
+            stream: Option<MessageStream<CompactBlock>>,
+            backoff: Backoff,
+            done: bool,
+        }
+
+        let state = State {
+            client: self.clone(),
+            open,
+            range,
+            stream: None,
+            backoff: Backoff::new(self.policy.clone()),
+            done: false,
+        };
+
+        fn past_end(range: &service::BlockRange) -> bool {
+            matches!((range.start.as_ref(), range.end.as_ref()), (Some(start), Some(end)) if start.height > end.height)
+        }
+
+        stream::unfold(state, |mut state| async move {
+            while !state.done {
+                let failure = match state.stream.as_mut() {
+                    None if past_end(&state.range) => return None,
+                    None => match (state.open)(state.range.clone()).await {
+                        Ok(stream) => {
+                            state.stream = Some(stream);
+                            continue;
+                        }
+                        Err(status) => status,
+                    },
+                    Some(stream) => match stream.try_next().await {
+                        Ok(Some(block)) => {
+                            if let Some(start) = state.range.start.as_mut() {
+                                start.height = block.height + 1;
+                            }
+                            state.backoff.reset();
+                            return Some((Ok(block), state));
+                        }
+                        Ok(None) => return None,
+                        Err(status) => status,
+                    },
+                };
+
+                state.stream = None;
+                if let Err(status) = state
+                    .backoff
+                    .wait(
+                        failure,
+                        &|status, delay, retry| state.client.on_failure(status, delay, retry),
+                        &state.client.cancellation_token,
+                    )
+                    .await
+                {
+                    state.done = true;
+                    return Some((Err(status), state));
+                }
+            }
+
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tonic::transport::Endpoint;
+
+    use super::*;
+
+    fn quick_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            initial_delay_ms: 1,
+            max_delay_ms: 5,
+            ..Default::default()
+        }
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_retry_classification() {
+        let attempts = AtomicU32::new(0);
+        let retries = AtomicU32::new(0);
+        let result: Result<(), Status> = webrequest_with_logged_retry(
+            || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err(Status::unavailable("down"))
+            },
+            &quick_policy(),
+            |_, _, _| {
+                retries.fetch_add(1, Ordering::Relaxed);
+            },
+            CancellationToken::new(),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::Unavailable);
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+        assert_eq!(retries.load(Ordering::Relaxed), 2);
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), Status> = webrequest_with_logged_retry(
+            || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err(Status::invalid_argument("bad"))
+            },
+            &quick_policy(),
+            |_, _, _| {},
+            CancellationToken::new(),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+    }
+
+    /// Creates a client whose server is never contacted, for tests that stub out the streams.
+    fn stub_client() -> ResilientClient {
+        let channel = Endpoint::from_static("http://127.0.0.1:9").connect_lazy();
+        ResilientClient::new(
+            CompactTxStreamerClient::new(channel).into(),
+            quick_policy(),
+            CancellationToken::new(),
+        )
+    }
+
+    /// Serves the given items, dropping the stream after `drop_after` of them on the first `flaky_opens` requests.
+    fn flaky_stream<T: Send + 'static>(
+        items: Vec<T>,
+        opens: &AtomicU32,
+        flaky_opens: u32,
+        drop_after: usize,
+    ) -> MessageStream<T> {
+        if opens.fetch_add(1, Ordering::Relaxed) < flaky_opens {
+            stream::iter(items.into_iter().take(drop_after).map(Ok))
+                .chain(stream::once(async {
+                    Err(Status::unavailable("connection reset"))
+                }))
+                .boxed()
+        } else {
+            stream::iter(items.into_iter().map(Ok)).boxed()
+        }
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_block_range_resumes_after_drop() {
+        let opens = Arc::new(AtomicU32::new(0));
+        let range = service::BlockRange {
+            start: Some(service::BlockId {
+                height: 100,
+                ..Default::default()
+            }),
+            end: Some(service::BlockId {
+                height: 109,
+                ..Default::default()
+            }),
+        };
+
+        let stub_opens = opens.clone();
+        let blocks: Vec<CompactBlock> = stub_client()
+            .resume_block_range(range, move |range| {
+                let start = range.start.unwrap().height;
+                let end = range.end.unwrap().height;
+                let blocks = (start..=end)
+                    .map(|height| CompactBlock {
+                        height,
+                        ..Default::default()
+                    })
+                    .collect();
+                let stream = flaky_stream(blocks, &stub_opens, 2, 3);
+                async move { Ok(stream) }
+            })
+            .try_collect()
+            .await
+            .unwrap();
+
+        let heights: Vec<u64> = blocks.iter().map(|b| b.height).collect();
+        assert_eq!(heights, (100..=109).collect::<Vec<_>>());
+        assert_eq!(opens.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_taddress_txids_resumes_after_drop() {
+        // Two transactions at each height, so that a resumed stream repeats some.
+        let all: Vec<RawTransaction> = (0..10u8)
+            .map(|i| RawTransaction {
+                data: vec![i],
+                height: 100 + u64::from(i / 2),
+            })
+            .collect();
+        let request = TransparentAddressBlockFilter {
+            address: "t1stub".to_string(),
+            range: Some(service::BlockRange {
+                start: Some(service::BlockId {
+                    height: 100,
+                    ..Default::default()
+                }),
+                end: Some(service::BlockId {
+                    height: 104,
+                    ..Default::default()
+                }),
+            }),
+        };
+
+        let opens = AtomicU32::new(0);
+        let transactions = stub_client()
+            .collect_taddress_txids(request, |request| {
+                let start = request.range.unwrap().start.unwrap().height;
+                let matching = all
+                    .iter()
+                    .filter(|tx| tx.height >= start)
+                    .cloned()
+                    .collect();
+                let stream = flaky_stream(matching, &opens, 2, 3);
+                async move { Ok(stream) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(transactions, all);
+        assert_eq!(opens.load(Ordering::Relaxed), 3);
+    }
 }
@@ -1,4 +1,4 @@
-use std::{num::NonZeroU32, path::Path};
+use std::{convert::Infallible, num::NonZeroU32, path::Path};
 
 use http::Uri;
 use nonempty::NonEmpty;
@@ -6,32 +6,38 @@ use rusqlite::Connection;
 use zcash_address::ZcashAddress;
 use zcash_client_backend::{
     data_api::{
+        error::Error as BackendError,
         wallet::{
             create_proposed_transactions,
-            input_selection::{GreedyInputSelector, GreedyInputSelectorError},
+            input_selection::{GreedyInputSelector, GreedyInputSelectorError, InputSelector},
             propose_transfer,
         },
-        Account, WalletRead,
+        Account, InputSource, SpendableNotes, WalletRead,
     },
     fees::{zip317::SingleOutputChangeStrategy, ChangeStrategy},
     keys::UnifiedSpendingKey,
     proposal::Proposal,
     proto::service,
-    wallet::OvkPolicy,
+    wallet::{Note, OvkPolicy, ReceivedNote},
     zip321::{Payment, TransactionRequest},
     ShieldedProtocol,
 };
-use zcash_client_sqlite::{ReceivedNoteId, WalletDb};
+use zcash_client_sqlite::{error::SqliteClientError, AccountId, ReceivedNoteId, WalletDb};
 use zcash_keys::keys::UnifiedFullViewingKey;
 use zcash_primitives::{
-    consensus::Network,
+    consensus::{BlockHeight, Network},
     memo::MemoBytes,
     transaction::{components::amount::NonNegativeAmount, fees::zip317::FeeRule, TxId},
 };
 
 use crate::{
-    backing_store::Db, error::Error, grpc::get_client, interop::TransactionSendDetail,
+    backing_store::Db,
+    error::Error,
+    grpc::get_isolated_client,
+    interop::{CoinSelection, NoteId, Pool, TransactionSendDetail},
     prover::get_prover,
+    resilience::ResilientClient,
+    tor::account_isolation,
 };
 
 #[derive(Debug)]
@@ -45,6 +51,7 @@ pub(crate) fn create_send_proposal(
     account_ufvk: &UnifiedFullViewingKey,
     min_confirmations: NonZeroU32,
     details: Vec<TransactionSendDetail>,
+    coin_selection: Option<&CoinSelection>,
 ) -> Result<Proposal<FeeRule, ReceivedNoteId>, Error> {
     // TODO: revise this to a smarter change strategy that avoids unnecessarily crossing the turnstile.
     let input_selector = GreedyInputSelector::new(
@@ -78,6 +85,17 @@ pub(crate) fn create_send_proposal(
         .get_account_for_ufvk(account_ufvk)?
         .ok_or(Error::KeyNotRecognized)?;
 
+    if let Some(coin_selection) = coin_selection {
+        return propose_transfer_from_selected_notes(
+            &db.data,
+            network,
+            account.id(),
+            request,
+            min_confirmations,
+            coin_selection,
+        );
+    }
+
     Ok(propose_transfer::<_, _, _, Error>(
         &mut db.data,
         &network,
@@ -88,6 +106,148 @@ pub(crate) fn create_send_proposal(
     )?)
 }
 
+/// Offers input selection only the notes that the user's coin selection allows,
+/// so that the wallet's own input selector can fund a transfer from them.
+struct SelectedNotes<'a> {
+    db: &'a WalletDb<Connection, Network>,
+    coin_selection: &'a CoinSelection,
+}
+
+impl SelectedNotes<'_> {
+    fn allows(&self, protocol: ShieldedProtocol, txid: &TxId, output_index: u32) -> bool {
+        let pool = match protocol {
+            ShieldedProtocol::Sapling => Pool::Sapling,
+            ShieldedProtocol::Orchard => Pool::Orchard,
+        };
+        let is_match = |n: &NoteId| {
+            n.pool == pool && n.txid == txid.as_ref() && n.output_index == output_index
+        };
+        match &self.coin_selection.spend_only {
+            Some(spend_only) => spend_only.iter().any(is_match),
+            None => !self.coin_selection.exclude.iter().any(is_match),
+        }
+    }
+}
+
+impl InputSource for SelectedNotes<'_> {
+    type Error = SqliteClientError;
+    type AccountId = AccountId;
+    type NoteRef = ReceivedNoteId;
+
+    fn get_spendable_note(
+        &self,
+        txid: &TxId,
+        protocol: ShieldedProtocol,
+        index: u32,
+    ) -> Result<Option<ReceivedNote<ReceivedNoteId, Note>>, SqliteClientError> {
+        Ok(self
+            .db
+            .get_spendable_note(txid, protocol, index)?
+            .filter(|_| self.allows(protocol, txid, index)))
+    }
+
+    fn select_spendable_notes(
+        &self,
+        account: AccountId,
+        target_value: NonNegativeAmount,
+        sources: &[ShieldedProtocol],
+        anchor_height: BlockHeight,
+        exclude: &[ReceivedNoteId],
+    ) -> Result<SpendableNotes<ReceivedNoteId>, SqliteClientError> {
+        // Exclude the notes the wallet offers that aren't allowed and ask again,
+        // until it offers only allowed notes.
+        let mut exclude = exclude.to_vec();
+        loop {
+            let notes = self.db.select_spendable_notes(
+                account,
+                target_value,
+                sources,
+                anchor_height,
+                &exclude,
+            )?;
+            let disallowed = notes
+                .sapling()
+                .iter()
+                .filter(|n| {
+                    !self.allows(ShieldedProtocol::Sapling, n.txid(), n.output_index().into())
+                })
+                .map(|n| *n.internal_note_id())
+                .chain(
+                    notes
+                        .orchard()
+                        .iter()
+                        .filter(|n| {
+                            !self.allows(
+                                ShieldedProtocol::Orchard,
+                                n.txid(),
+                                n.output_index().into(),
+                            )
+                        })
+                        .map(|n| *n.internal_note_id()),
+                )
+                .collect::<Vec<_>>();
+            if disallowed.is_empty() {
+                return Ok(notes);
+            }
+            exclude.extend(disallowed);
+        }
+    }
+}
+
+/// Proposes a transfer that is funded only from the notes allowed by the user's coin selection.
+fn propose_transfer_from_selected_notes(
+    db: &WalletDb<Connection, Network>,
+    network: Network,
+    account_id: AccountId,
+    request: TransactionRequest,
+    min_confirmations: NonZeroU32,
+    coin_selection: &CoinSelection,
+) -> Result<Proposal<FeeRule, ReceivedNoteId>, Error> {
+    if let Some(spend_only) = &coin_selection.spend_only {
+        for note in spend_only {
+            let protocol = match note.pool {
+                Pool::Transparent => return Err(Error::InvalidArgument(
+                    "Transparent funds must be shielded before they can be selected for a send."
+                        .to_string(),
+                )),
+                Pool::Sapling => ShieldedProtocol::Sapling,
+                Pool::Orchard => ShieldedProtocol::Orchard,
+            };
+            let txid = TxId::from_bytes(note.txid.clone().try_into().map_err(|_| {
+                Error::InvalidArgument("A transaction ID must be 32 bytes.".to_string())
+            })?);
+            if db
+                .get_spendable_note(&txid, protocol, note.output_index)?
+                .is_none()
+            {
+                return Err(Error::InvalidArgument(format!(
+                    "The {:?} note at output {} of transaction {} is not spendable. It may already be spent or not belong to this wallet.",
+                    note.pool, note.output_index, txid,
+                )));
+            }
+        }
+    }
+
+    let (target_height, anchor_height) = db
+        .get_target_and_anchor_heights(min_confirmations)?
+        .ok_or(Error::SyncFirst)?;
+    let selected_notes = SelectedNotes { db, coin_selection };
+    let input_selector = GreedyInputSelector::new(
+        SingleOutputChangeStrategy::new(FeeRule::standard(), None, ShieldedProtocol::Sapling),
+        Default::default(),
+    );
+    Ok(input_selector
+        .propose_transaction(
+            &network,
+            &selected_notes,
+            target_height,
+            anchor_height,
+            account_id,
+            request,
+        )
+        .map_err(BackendError::<_, Infallible, _, Infallible>::from)?)
+}
+
 pub async fn send_transaction<P: AsRef<Path>>(
     data_file: P,
     server_uri: Uri,
@@ -95,6 +255,7 @@ pub async fn send_transaction<P: AsRef<Path>>(
     usk: &UnifiedSpendingKey,
     min_confirmations: NonZeroU32,
     details: Vec<TransactionSendDetail>,
+    coin_selection: Option<&CoinSelection>,
 ) -> Result<NonEmpty<SendTransactionResult>, Error> {
     let mut db = Db::init(data_file, network)?;
     let proposal = create_send_proposal(
@@ -103,6 +264,7 @@ pub async fn send_transaction<P: AsRef<Path>>(
         &usk.to_unified_full_viewing_key(),
         min_confirmations,
         details,
+        coin_selection,
     )?;
 
     let prover = get_prover()?;
@@ -118,27 +280,42 @@ pub async fn send_transaction<P: AsRef<Path>>(
     >(
         &mut db.data,
         &network,
-        &prover,
-        &prover,
+        &*prover,
+        &*prover,
         usk,
         OvkPolicy::Sender,
         &proposal,
     )?;
 
+    let isolation = account_isolation(&network, &usk.to_unified_full_viewing_key());
     let mut result = Vec::new();
     for txid in txids {
-        result.push(transmit_transaction(txid, server_uri.clone(), &mut db.data).await?);
+        result.push(
+            transmit_transaction(txid, server_uri.clone(), isolation.clone(), &mut db.data).await?,
+        );
     }
 
     Ok(NonEmpty::from_vec(result).unwrap())
 }
 
+/// The error code with which zcashd rejects a transaction that is already in the block chain (`RPC_VERIFY_ALREADY_IN_CHAIN`).
+const RPC_VERIFY_ALREADY_IN_CHAIN: i32 = -27;
+
+/// The error code with which zcashd rejects a transaction whose inputs are missing or already spent (`RPC_VERIFY_ERROR`),
+/// as they are once an earlier attempt to send the same transaction has been mined.
+const RPC_VERIFY_ERROR: i32 = -25;
+
+/// Sends a transaction to the server, over a connection isolated by the given key (see `tor::account_isolation`).
+///
+/// A transaction that the server rejects as a duplicate counts as sent,
+/// so that retrying after an interrupted attempt succeeds.
 pub(crate) async fn transmit_transaction(
     txid: TxId,
     server_uri: Uri,
+    isolation: Option<String>,
     db: &mut WalletDb<Connection, Network>,
 ) -> Result<SendTransactionResult, Error> {
-    let mut client = get_client(server_uri).await?;
+    let client = ResilientClient::from(get_isolated_client(server_uri, isolation).await?);
     let raw_tx = db
         .get_transaction(txid)?
         .ok_or(Error::Internal("Transaction not found".to_string()))
@@ -147,14 +324,13 @@ pub(crate) async fn transmit_transaction(
             tx.write(&mut raw_tx.data).unwrap();
             raw_tx
         })?;
-    let response = client.send_transaction(raw_tx).await?.into_inner();
-    if response.error_code != 0 {
-        Err(Error::SendFailed {
-            code: response.error_code,
+    let response = client.send_transaction(raw_tx).await?;
+    match response.error_code {
+        0 | RPC_VERIFY_ALREADY_IN_CHAIN | RPC_VERIFY_ERROR => Ok(SendTransactionResult { txid }),
+        code => Err(Error::SendFailed {
+            code,
             reason: response.error_message,
-        })
-    } else {
-        Ok(SendTransactionResult { txid })
+        }),
     }
 }
 
@@ -164,6 +340,8 @@ mod tests {
     use tokio_util::sync::CancellationToken;
 
     use crate::{
+        analysis::{get_unspent_notes, NoteSpendability, UnspentNote},
+        interop::SyncOptions,
         sync::sync,
         test_constants::{setup_test, MIN_CONFIRMATIONS, VALID_SAPLING_TESTNET},
     };
@@ -178,8 +356,8 @@ mod tests {
             setup.server_uri.clone(),
             setup.data_file.clone(),
             None,
-            setup.db_init.min_confirmations,
             false,
+            SyncOptions::default(),
             CancellationToken::new(),
         )
         .await
@@ -195,9 +373,119 @@ mod tests {
                 memo: None,
                 recipient: VALID_SAPLING_TESTNET.to_string(),
             }],
+            None,
         )
         .await
         .unwrap_err();
         assert_matches!(result, Error::InsufficientFunds { .. });
     }
+
+    #[tokio_shared_rt::test]
+    async fn test_send_with_unknown_selected_note() {
+        let mut setup = setup_test().await;
+        let account = setup.create_account().await.unwrap();
+        setup.sync().await;
+        let result = send_transaction(
+            setup.data_file,
+            setup.server_uri,
+            setup.network,
+            &account.3,
+            NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap(),
+            vec![TransactionSendDetail {
+                value: 1000,
+                memo: None,
+                recipient: VALID_SAPLING_TESTNET.to_string(),
+            }],
+            Some(&CoinSelection {
+                spend_only: Some(vec![NoteId {
+                    txid: vec![0; 32],
+                    pool: Pool::Sapling,
+                    output_index: 0,
+                }]),
+                exclude: Vec::new(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_matches!(result, Error::InvalidArgument(..));
+    }
+
+    /// Asserts that every note the proposal spends is one of `allowed`.
+    fn assert_spends_only(proposal: &Proposal<FeeRule, ReceivedNoteId>, allowed: &[&UnspentNote]) {
+        for step in proposal.steps() {
+            assert!(step.transparent_inputs().is_empty());
+            for note in step.shielded_inputs().iter().flat_map(|i| i.notes()) {
+                assert!(
+                    allowed.iter().any(|a| a.txid == note.txid().as_ref()
+                        && a.output_index == u32::from(note.output_index())),
+                    "The proposal spends a note that was not selected."
+                );
+            }
+        }
+    }
+
+    #[tokio_shared_rt::test]
+    #[ignore = "requires NERDBANK_ZCASH_FUNDED_TESTNET_SEED"]
+    async fn test_send_spends_only_selected_notes() {
+        let mut setup = setup_test().await;
+        let (account_id, usk) = setup.create_funded_account().await;
+        let min_confirmations = NonZeroU32::try_from(MIN_CONFIRMATIONS).unwrap();
+        let notes = get_unspent_notes(&setup.db_init, account_id, min_confirmations).unwrap();
+        let mut spendable = notes
+            .iter()
+            .filter(|n| {
+                n.spendability == NoteSpendability::Spendable && n.pool != Pool::Transparent
+            })
+            .collect::<Vec<_>>();
+        spendable.sort_by_key(|n| n.value);
+        assert!(
+            spendable.len() >= 2 && spendable[0].value >= 30_000,
+            "The funded wallet needs at least two spendable shielded notes of at least 30,000 ZATs each."
+        );
+        let note_id = |n: &UnspentNote| NoteId {
+            txid: n.txid.clone(),
+            pool: n.pool,
+            output_index: n.output_index,
+        };
+        let payment = || {
+            vec![TransactionSendDetail {
+                value: 10_000,
+                memo: None,
+                recipient: VALID_SAPLING_TESTNET.to_string(),
+            }]
+        };
+        let ufvk = usk.to_unified_full_viewing_key();
+
+        // Spending only the smallest note funds the payment from that note alone.
+        let smallest = spendable[0];
+        let proposal = create_send_proposal(
+            &mut setup.db,
+            setup.network,
+            &ufvk,
+            min_confirmations,
+            payment(),
+            Some(&CoinSelection {
+                spend_only: Some(vec![note_id(smallest)]),
+                exclude: Vec::new(),
+            }),
+        )
+        .unwrap();
+        assert_spends_only(&proposal, &[smallest]);
+
+        // Excluding the largest note funds the payment from the others.
+        let (largest, others) = spendable.split_last().unwrap();
+        let proposal = create_send_proposal(
+            &mut setup.db,
+            setup.network,
+            &ufvk,
+            min_confirmations,
+            payment(),
+            Some(&CoinSelection {
+                spend_only: None,
+                exclude: vec![note_id(*largest)],
+            }),
+        )
+        .unwrap();
+        assert_spends_only(&proposal, others);
+    }
 }
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use http::Uri;
 use nonempty::NonEmpty;
-use rusqlite::{named_params, Connection};
+use rusqlite::named_params;
 use zcash_client_backend::{
     data_api::wallet::{
         create_proposed_transactions,
@@ -22,12 +22,13 @@ use zcash_primitives::{
 };
 
 use crate::{
-    backing_store::Db,
+    backing_store::{open_connection, Db},
     error::Error,
     interop::{DbInit, TransparentNote},
     prover::get_prover,
     send::{transmit_transaction, SendTransactionResult},
     sql_statements::GET_UNSPENT_TRANSPARENT_NOTES,
+    tor::account_isolation,
 };
 
 pub async fn shield_funds_at_address<P: AsRef<Path>>(
@@ -67,16 +68,19 @@ pub async fn shield_funds_at_address<P: AsRef<Path>>(
     >(
         &mut db.data,
         &network,
-        &prover,
-        &prover,
+        &*prover,
+        &*prover,
         usk,
         OvkPolicy::Sender,
         &proposal,
     )?;
 
+    let isolation = account_isolation(&network, &usk.to_unified_full_viewing_key());
     let mut result = Vec::new();
     for txid in txids {
-        result.push(transmit_transaction(txid, server_uri.clone(), &mut db.data).await?);
+        result.push(
+            transmit_transaction(txid, server_uri.clone(), isolation.clone(), &mut db.data).await?,
+        );
     }
 
     Ok(NonEmpty::from_vec(result).unwrap())
@@ -88,7 +92,7 @@ pub fn get_unshielded_utxos(
     config: DbInit,
     account_id: AccountId,
 ) -> Result<Vec<TransparentNote>, Error> {
-    let conn = Connection::open(config.data_file)?;
+    let conn = open_connection(config.data_file)?;
     let mut balances_query = conn.prepare(GET_UNSPENT_TRANSPARENT_NOTES)?;
     let mut rows = balances_query.query(named_params! {
         ":account_id": u32::from(account_id),
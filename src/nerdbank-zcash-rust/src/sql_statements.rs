@@ -35,10 +35,12 @@ pub(crate) const GET_TRANSACTIONS_SQL: &str = r#"
 		) AS to_address,
 		coalesce(s.diversifier, o.diversifier) AS diversifier,
 		txo.value,
-		txo.memo
+		txo.memo,
+		tl.label
 	FROM v_transactions t
 	LEFT OUTER JOIN v_tx_outputs txo ON t.txid = txo.txid
 	LEFT OUTER JOIN transactions tx ON tx.txid = t.txid
+	LEFT OUTER JOIN transaction_labels tl ON tl.txid = t.txid
 	LEFT OUTER JOIN sapling_received_notes s ON txo.output_pool = 2 AND s.tx = tx.id_tx AND s.output_index = txo.output_index
 	LEFT OUTER JOIN orchard_received_notes o ON txo.output_pool = 3 AND o.tx = tx.id_tx AND o.action_index = txo.output_index
 	WHERE (:account_id IS NULL OR t.account_id = :account_id)
@@ -50,6 +52,10 @@ pub(crate) const GET_TRANSACTIONS_SQL: &str = r#"
 	ORDER BY t.account_id, t.mined_height, t.tx_index, txo.output_pool, txo.output_index -- ensure rows that get squashed together are next to each other
 "#;
 
+pub(crate) const GET_FIRST_TRANSACTION_HEIGHT: &str = r#"
+	SELECT MIN(mined_height) FROM v_transactions WHERE account_id = :account_id
+"#;
+
 // TODO: update this to consider UTXOs in "Block with first unspent note" column.
 // Note that WalletDb::get_min_unspent_height provides the rebirth height at the wallet level (instead of the account level).
 pub(crate) const GET_BIRTHDAY_HEIGHTS: &str = r#"
@@ -94,6 +100,46 @@ pub(crate) const GET_UNSPENT_NOTES: &str = r#"
 	WHERE account_id = :account_id AND j.transaction_id IS NULL
 "#;
 
+// Like GET_UNSPENT_NOTES, but with enough detail to identify and describe each individual note or UTXO.
+pub(crate) const GET_UNSPENT_NOTE_INVENTORY: &str = r#"
+	SELECT
+		tx.txid,
+		tx.block,
+		txo.output_pool,
+		txo.output_index,
+		txo.value,
+		COALESCE(txo.from_account_id = txo.to_account_id, 0) AS is_change,
+		coalesce(s.diversifier, o.diversifier) AS diversifier,
+		NULL AS address,
+		txo.memo
+	FROM v_tx_outputs txo
+	INNER JOIN transactions tx ON tx.txid = txo.txid
+	LEFT OUTER JOIN sapling_received_notes s ON txo.output_pool = 2 AND s.tx = tx.id_tx AND s.output_index = txo.output_index
+	LEFT OUTER JOIN sapling_received_note_spends ss ON ss.sapling_received_note_id = s.id
+	LEFT OUTER JOIN orchard_received_notes o ON txo.output_pool = 3 AND o.tx = tx.id_tx AND o.action_index = txo.output_index
+	LEFT OUTER JOIN orchard_received_note_spends os ON os.orchard_received_note_id = o.id
+	WHERE txo.to_account_id = :account_id AND ss.transaction_id IS NULL AND os.transaction_id IS NULL AND txo.output_pool > 0
+
+	UNION
+
+	SELECT
+		t.txid,
+		t.block,
+		0, -- output_pool
+		txo.output_index,
+		value_zat,
+		0, -- is_change
+		NULL, -- diversifier
+		address,
+		NULL -- memo
+	FROM transparent_received_outputs txo
+	INNER JOIN transactions t ON t.id_tx = txo.transaction_id
+	LEFT OUTER JOIN transparent_received_output_spends j ON txo.id = j.transparent_received_output_id
+	WHERE account_id = :account_id AND j.transaction_id IS NULL
+
+	ORDER BY 2, 3, 1, 4
+"#;
+
 pub(crate) const GET_UNSPENT_TRANSPARENT_NOTES: &str = r#"
 	SELECT
 		t.block AS height,
@@ -112,3 +158,369 @@ pub(crate) const GET_OUTPOINT_VALUE: &str = r#"
 	INNER JOIN transactions t ON txo.transaction_id = t.id_tx
 	WHERE t.txid = :txid AND output_index = :idx
 "#;
+
+pub(crate) const GET_ADDRESS_BOOK: &str = r#"
+	SELECT id, address, label
+	FROM address_book
+	ORDER BY label, id
+"#;
+
+pub(crate) const UPDATE_ADDRESS_BOOK_ENTRY: &str = r#"
+	UPDATE address_book SET address = :address, label = :label WHERE id = :id
+"#;
+
+pub(crate) const INSERT_ADDRESS_BOOK_ENTRY: &str = r#"
+	INSERT INTO address_book (address, label) VALUES (:address, :label)
+	ON CONFLICT (address) DO UPDATE SET label = excluded.label
+	RETURNING id
+"#;
+
+pub(crate) const REMOVE_ADDRESS_BOOK_ENTRY: &str = r#"
+	DELETE FROM address_book WHERE id = :id
+"#;
+
+pub(crate) const HAS_ACCOUNT_ADDRESS: &str = r#"
+	SELECT 1 FROM addresses WHERE account_id = :account_id AND diversifier_index_be = :diversifier_index_be
+"#;
+
+pub(crate) const SET_ADDRESS_LABEL: &str = r#"
+	INSERT INTO address_labels (account_id, diversifier_index_be, label)
+	VALUES (:account_id, :diversifier_index_be, :label)
+	ON CONFLICT (account_id, diversifier_index_be) DO UPDATE SET label = excluded.label
+"#;
+
+pub(crate) const REMOVE_ADDRESS_LABEL: &str = r#"
+	DELETE FROM address_labels WHERE account_id = :account_id AND diversifier_index_be = :diversifier_index_be
+"#;
+
+pub(crate) const SET_TRANSACTION_LABEL: &str = r#"
+	INSERT INTO transaction_labels (txid, label) VALUES (:txid, :label)
+	ON CONFLICT (txid) DO UPDATE SET label = excluded.label
+"#;
+
+pub(crate) const REMOVE_TRANSACTION_LABEL: &str = r#"
+	DELETE FROM transaction_labels WHERE txid = :txid
+"#;
+
+pub(crate) const GET_OWN_ADDRESS_LABELS: &str = r#"
+	SELECT a.address, l.label
+	FROM address_labels l
+	INNER JOIN addresses a ON a.account_id = l.account_id AND a.diversifier_index_be = l.diversifier_index_be
+"#;
+
+pub(crate) const GET_ACCOUNT_ADDRESSES: &str = r#"
+	SELECT a.diversifier_index_be, a.address, m.created_at, l.label
+	FROM addresses a
+	LEFT OUTER JOIN address_metadata m ON m.account_id = a.account_id AND m.diversifier_index_be = a.diversifier_index_be
+	LEFT OUTER JOIN address_labels l ON l.account_id = a.account_id AND l.diversifier_index_be = a.diversifier_index_be
+	WHERE a.account_id = :account_id
+	ORDER BY a.diversifier_index_be
+"#;
+
+// Records an address with custom receivers, unless the account already has one at that diversifier index.
+pub(crate) const INSERT_ADDRESS_WITH_RECEIVERS: &str = r#"
+	INSERT INTO addresses (account_id, diversifier_index_be, address, cached_transparent_receiver_address)
+	VALUES (:account_id, :diversifier_index_be, :address, :transparent_address)
+	ON CONFLICT (account_id, diversifier_index_be) DO NOTHING
+"#;
+
+pub(crate) const GET_ADDRESS_AT_DIVERSIFIER_INDEX: &str = r#"
+	SELECT address
+	FROM addresses
+	WHERE account_id = :account_id AND diversifier_index_be = :diversifier_index_be
+"#;
+
+// Sums the value received to each external receiver of an account, keyed by the shielded diversifier
+// or (for transparent outputs) the encoded address.
+// Change and other internally-scoped notes are excluded.
+pub(crate) const GET_RECEIVED_BY_RECEIVER: &str = r#"
+	SELECT 2 AS output_pool, n.diversifier, NULL AS address, SUM(n.value) AS value
+	FROM sapling_received_notes n
+	INNER JOIN transactions t ON t.id_tx = n.tx
+	WHERE n.account_id = :account_id AND n.recipient_key_scope = 0 AND t.block IS NOT NULL
+	GROUP BY n.diversifier
+
+	UNION ALL
+
+	SELECT 3, n.diversifier, NULL, SUM(n.value)
+	FROM orchard_received_notes n
+	INNER JOIN transactions t ON t.id_tx = n.tx
+	WHERE n.account_id = :account_id AND n.recipient_key_scope = 0 AND t.block IS NOT NULL
+	GROUP BY n.diversifier
+
+	UNION ALL
+
+	SELECT 0, NULL, txo.address, SUM(txo.value_zat)
+	FROM transparent_received_outputs txo
+	INNER JOIN transactions t ON t.id_tx = txo.transaction_id
+	WHERE txo.account_id = :account_id AND t.block IS NOT NULL
+	GROUP BY txo.address
+"#;
+
+// Removes an account and everything the wallet has recorded that only pertains to it.
+// Transactions that also involve other accounts are kept, along with those accounts' notes.
+// The caller must first populate the `removed_account` temp table with the ID of the account to remove.
+pub(crate) const REMOVE_ACCOUNT: &str = r#"
+	CREATE TEMP TABLE removed_account_txs AS
+		SELECT tx AS id_tx FROM sapling_received_notes WHERE account_id IN (SELECT id FROM removed_account)
+		UNION SELECT tx FROM orchard_received_notes WHERE account_id IN (SELECT id FROM removed_account)
+		UNION SELECT transaction_id FROM transparent_received_outputs WHERE account_id IN (SELECT id FROM removed_account)
+		UNION SELECT tx FROM sent_notes WHERE from_account_id IN (SELECT id FROM removed_account);
+
+	DELETE FROM sapling_received_note_spends
+		WHERE sapling_received_note_id IN (SELECT id FROM sapling_received_notes WHERE account_id IN (SELECT id FROM removed_account));
+	DELETE FROM orchard_received_note_spends
+		WHERE orchard_received_note_id IN (SELECT id FROM orchard_received_notes WHERE account_id IN (SELECT id FROM removed_account));
+	DELETE FROM transparent_received_output_spends
+		WHERE transparent_received_output_id IN (SELECT id FROM transparent_received_outputs WHERE account_id IN (SELECT id FROM removed_account));
+
+	DELETE FROM sapling_received_notes WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM orchard_received_notes WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM transparent_received_outputs WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM sent_notes WHERE from_account_id IN (SELECT id FROM removed_account);
+
+	-- A sent note must keep either a recipient account or a recipient address.
+	-- Transfers to the removed account that recorded only the account are attributed to its default address.
+	UPDATE sent_notes
+		SET to_address = (
+			SELECT address FROM addresses a
+			WHERE a.account_id = sent_notes.to_account_id
+			ORDER BY a.diversifier_index_be
+			LIMIT 1
+		)
+		WHERE to_account_id IN (SELECT id FROM removed_account) AND to_address IS NULL;
+	DELETE FROM sent_notes WHERE to_account_id IN (SELECT id FROM removed_account) AND to_address IS NULL;
+	UPDATE sent_notes SET to_account_id = NULL WHERE to_account_id IN (SELECT id FROM removed_account);
+
+	DELETE FROM removed_account_txs
+		WHERE id_tx IN (SELECT tx FROM sapling_received_notes)
+		OR id_tx IN (SELECT tx FROM orchard_received_notes)
+		OR id_tx IN (SELECT transaction_id FROM transparent_received_outputs)
+		OR id_tx IN (SELECT tx FROM sent_notes)
+		OR id_tx IN (SELECT transaction_id FROM sapling_received_note_spends)
+		OR id_tx IN (SELECT transaction_id FROM orchard_received_note_spends)
+		OR id_tx IN (SELECT transaction_id FROM transparent_received_output_spends);
+	DELETE FROM tx_retrieval_queue WHERE dependent_transaction_id IN (SELECT id_tx FROM removed_account_txs);
+	DELETE FROM transaction_labels WHERE txid IN (SELECT txid FROM transactions WHERE id_tx IN (SELECT id_tx FROM removed_account_txs));
+	DELETE FROM transactions WHERE id_tx IN (SELECT id_tx FROM removed_account_txs);
+	DROP TABLE removed_account_txs;
+
+	DELETE FROM address_labels WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM address_metadata WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM addresses WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM ephemeral_addresses WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM account_metadata WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM account_info WHERE account_id IN (SELECT id FROM removed_account);
+	DELETE FROM accounts WHERE id IN (SELECT id FROM removed_account);
+	DROP TABLE removed_account;
+"#;
+
+// Gets the earliest birthday of the remaining accounts, or NULL if none remain.
+pub(crate) const GET_MIN_BIRTHDAY_HEIGHT: &str = r#"
+	SELECT MIN(birthday_height) FROM accounts
+"#;
+
+// Forgets everything the wallet learned from the chain, for when no accounts remain to need it.
+pub(crate) const CLEAR_CHAIN_STATE: &str = r#"
+	DELETE FROM tx_retrieval_queue;
+	DELETE FROM transactions;
+	DELETE FROM nullifier_map;
+	DELETE FROM tx_locator_map;
+	DELETE FROM scan_queue;
+	DELETE FROM sapling_tree_checkpoint_marks_removed;
+	DELETE FROM sapling_tree_checkpoints;
+	DELETE FROM sapling_tree_cap;
+	DELETE FROM sapling_tree_shards;
+	DELETE FROM orchard_tree_checkpoint_marks_removed;
+	DELETE FROM orchard_tree_checkpoints;
+	DELETE FROM orchard_tree_cap;
+	DELETE FROM orchard_tree_shards;
+	DELETE FROM blocks;
+"#;
+
+// Drops or trims scan ranges that fall below the birthday of every remaining account.
+pub(crate) const TRIM_SCAN_QUEUE_TO_BIRTHDAYS: &str = r#"
+	DELETE FROM scan_queue
+		WHERE block_range_end <= (SELECT MIN(birthday_height) FROM accounts);
+	UPDATE scan_queue
+		SET block_range_start = (SELECT MIN(birthday_height) FROM accounts)
+		WHERE block_range_start < (SELECT MIN(birthday_height) FROM accounts);
+"#;
+
+// Gets the height below which every block has been scanned, or the wallet's tip if no unscanned ranges remain.
+// Scan ranges with a priority above `Scanned` (10) remain to be scanned.
+pub(crate) const GET_FULLY_SCANNED_HEIGHT: &str = r#"
+	SELECT coalesce(
+		(SELECT MIN(block_range_start) FROM scan_queue WHERE priority > 10),
+		(SELECT MAX(height) FROM blocks)
+	)
+"#;
+
+// Finds the scanned range (priority 10) that starts before and ends after the given height, if any.
+pub(crate) const GET_SCANNED_RANGE_SPANNING_HEIGHT: &str = r#"
+	SELECT block_range_start, block_range_end FROM scan_queue
+	WHERE block_range_start < :height AND block_range_end > :height AND priority = 10
+"#;
+
+// Ends the scan range that starts at the given height early.
+pub(crate) const SET_SCAN_RANGE_END: &str = r#"
+	UPDATE scan_queue SET block_range_end = :end WHERE block_range_start = :start
+"#;
+
+// Adds a range to the scan queue.
+pub(crate) const INSERT_SCAN_RANGE: &str = r#"
+	INSERT INTO scan_queue (block_range_start, block_range_end, priority) VALUES (:start, :end, :priority)
+"#;
+
+// Schedules the scanned ranges (priority 10) that start at or after the given height to be scanned again.
+pub(crate) const RESCHEDULE_SCANNED_RANGES: &str = r#"
+	UPDATE scan_queue SET priority = :priority WHERE block_range_start >= :height AND priority = 10
+"#;
+
+// Removes nullifiers observed in blocks below the given height.
+pub(crate) const PRUNE_NULLIFIER_MAP: &str = r#"
+	DELETE FROM nullifier_map WHERE block_height < :height
+"#;
+
+// Removes the transaction locators that nullifiers below the given height referred to.
+pub(crate) const PRUNE_TX_LOCATOR_MAP: &str = r#"
+	DELETE FROM tx_locator_map WHERE block_height < :height
+"#;
+
+// Finds blocks whose note commitment tree sizes don't follow from the preceding block and the block's own outputs.
+pub(crate) const FIND_TREE_INCONSISTENCIES: &str = r#"
+	SELECT b.height
+	FROM blocks b
+	INNER JOIN blocks p ON p.height = b.height - 1
+	WHERE (b.sapling_commitment_tree_size != p.sapling_commitment_tree_size + b.sapling_output_count)
+		OR (b.orchard_commitment_tree_size != p.orchard_commitment_tree_size + b.orchard_action_count)
+	ORDER BY b.height
+"#;
+
+// Finds the block ranges between the wallet birthday and the end of the scan queue that no scan range covers.
+pub(crate) const FIND_SCAN_QUEUE_GAPS: &str = r#"
+	SELECT prev_end AS gap_start, block_range_start AS gap_end
+	FROM (
+		SELECT
+			block_range_start,
+			coalesce(
+				MAX(block_range_end) OVER (ORDER BY block_range_start ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING),
+				(SELECT MIN(birthday_height) FROM accounts)
+			) AS prev_end
+		FROM scan_queue
+	)
+	WHERE prev_end < block_range_start
+	ORDER BY gap_start
+"#;
+
+// Counts received notes and outputs that belong to a missing transaction or account.
+pub(crate) const COUNT_ORPHANED_NOTES: &str = r#"
+	SELECT
+		(SELECT count(*) FROM sapling_received_notes
+			WHERE tx NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts))
+		+ (SELECT count(*) FROM orchard_received_notes
+			WHERE tx NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts))
+		+ (SELECT count(*) FROM transparent_received_outputs
+			WHERE transaction_id NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts))
+"#;
+
+pub(crate) const REMOVE_ORPHANED_NOTES: &str = r#"
+	DELETE FROM sapling_received_note_spends WHERE sapling_received_note_id IN (
+		SELECT id FROM sapling_received_notes
+		WHERE tx NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts));
+	DELETE FROM sapling_received_notes
+		WHERE tx NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts);
+	DELETE FROM orchard_received_note_spends WHERE orchard_received_note_id IN (
+		SELECT id FROM orchard_received_notes
+		WHERE tx NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts));
+	DELETE FROM orchard_received_notes
+		WHERE tx NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts);
+	DELETE FROM transparent_received_output_spends WHERE transparent_received_output_id IN (
+		SELECT id FROM transparent_received_outputs
+		WHERE transaction_id NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts));
+	DELETE FROM transparent_received_outputs
+		WHERE transaction_id NOT IN (SELECT id_tx FROM transactions) OR account_id NOT IN (SELECT id FROM accounts);
+"#;
+
+// Finds transactions whose raw data is missing and that no enhancement request is pending for.
+pub(crate) const FIND_MISSING_RAW_TRANSACTIONS: &str = r#"
+	SELECT txid FROM transactions
+	WHERE raw IS NULL AND txid NOT IN (SELECT txid FROM tx_retrieval_queue)
+"#;
+
+// Finds mined transactions funded by the wallet whose fee has not been determined.
+// The fees of incoming transactions are paid by someone else, and are often unknowable.
+pub(crate) const FIND_MISSING_FEES: &str = r#"
+	SELECT txid FROM transactions
+	WHERE fee IS NULL AND block IS NOT NULL AND (
+		id_tx IN (SELECT transaction_id FROM sapling_received_note_spends)
+		OR id_tx IN (SELECT transaction_id FROM orchard_received_note_spends)
+		OR id_tx IN (SELECT transaction_id FROM transparent_received_output_spends)
+		OR id_tx IN (SELECT tx FROM sent_notes WHERE from_account_id IS NOT NULL)
+	)
+"#;
+
+pub(crate) const COUNT_UNKNOWN_OUTPUT_POOLS: &str = r#"
+	SELECT count(*) FROM v_tx_outputs WHERE output_pool NOT IN (0, 2, 3)
+"#;
+
+// Gets the payments in a batch, with the transaction that pays each one (if any).
+pub(crate) const GET_BATCH_PAYMENTS: &str = r#"
+	SELECT p.payment_index, p.recipient, p.value, p.memo, p.error, t.txid, t.broadcast, t.error AS transaction_error
+	FROM batch_payments p
+	LEFT JOIN batch_transactions t ON t.id = p.transaction_id
+	WHERE p.batch_id = :batch_id
+	ORDER BY p.payment_index
+"#;
+
+pub(crate) const GET_BATCH_ACCOUNT: &str = r#"
+	SELECT account_id FROM batches WHERE id = :id
+"#;
+
+pub(crate) const INSERT_BATCH: &str = r#"
+	INSERT INTO batches (id, account_id) VALUES (:id, :account_id)
+"#;
+
+pub(crate) const INSERT_BATCH_PAYMENT: &str = r#"
+	INSERT INTO batch_payments (batch_id, payment_index, recipient, value, memo)
+	VALUES (:batch_id, :payment_index, :recipient, :value, :memo)
+"#;
+
+pub(crate) const INSERT_BATCH_TRANSACTION: &str = r#"
+	INSERT INTO batch_transactions (batch_id, txid) VALUES (:batch_id, :txid) RETURNING id
+"#;
+
+pub(crate) const SET_BATCH_PAYMENT_TRANSACTION: &str = r#"
+	UPDATE batch_payments SET transaction_id = :transaction_id, error = NULL
+	WHERE batch_id = :batch_id AND payment_index = :payment_index
+"#;
+
+pub(crate) const SET_BATCH_PAYMENT_ERROR: &str = r#"
+	UPDATE batch_payments SET error = :error WHERE batch_id = :batch_id AND payment_index = :payment_index
+"#;
+
+// Gets the batch's transactions that the server has not accepted yet, in the order they were created.
+pub(crate) const GET_UNBROADCAST_BATCH_TRANSACTIONS: &str = r#"
+	SELECT id, txid FROM batch_transactions WHERE batch_id = :batch_id AND broadcast = 0 ORDER BY id
+"#;
+
+pub(crate) const SET_BATCH_TRANSACTION_BROADCAST: &str = r#"
+	UPDATE batch_transactions SET broadcast = :broadcast, error = :error WHERE id = :id
+"#;
+
+// Gets the transaction that pays the payments which the given transaction was created for.
+// It is the given transaction itself, or a later one created from the same proposal.
+pub(crate) const GET_BATCH_PAYING_TRANSACTION: &str = r#"
+	SELECT MIN(transaction_id) FROM batch_payments WHERE batch_id = :batch_id AND transaction_id >= :id
+"#;
+
+// Returns the payments made by a transaction that will never be mined to the pending payments,
+// recording why.
+pub(crate) const ABANDON_BATCH_PAYMENTS: &str = r#"
+	UPDATE batch_payments SET transaction_id = NULL, error = :error
+	WHERE batch_id = :batch_id AND transaction_id = :transaction_id
+"#;
+
+pub(crate) const DELETE_BATCH_TRANSACTIONS: &str = r#"
+	DELETE FROM batch_transactions WHERE batch_id = :batch_id AND id BETWEEN :first AND :last
+"#;
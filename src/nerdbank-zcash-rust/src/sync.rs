@@ -1,25 +1,26 @@
-use futures_util::{StreamExt, TryStreamExt};
+use futures_util::TryStreamExt;
 use http::Uri;
 use orchard::{keys::Scope, tree::MerkleHashOrchard};
-use prost::bytes::Buf;
+use prost::{bytes::Buf, Message};
 use rusqlite::{named_params, Connection};
 use std::{
     borrow::Borrow,
     collections::{BTreeSet, HashMap},
     ops::Range,
     path::Path,
-    sync::Arc,
-};
-use tokio::{
-    select,
-    sync::{mpsc, Mutex},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
+use tokio::{select, sync::mpsc};
 use tokio_util::sync::CancellationToken;
-use tonic::{transport::Channel, Status};
-use tracing::{debug, info};
+use tonic::Status;
+use tracing::{debug, info, Instrument};
 use uniffi::deps::anyhow;
 use zcash_client_sqlite::{error::SqliteClientError, AccountId, WalletDb};
-use zcash_keys::address::UnifiedAddress;
+use zcash_keys::{address::UnifiedAddress, keys::UnifiedFullViewingKey};
 use zcash_primitives::{
     consensus::{BlockHeight, BranchId, Network, Parameters},
     legacy::TransparentAddress,
@@ -42,24 +43,26 @@ use zcash_client_backend::{
     encoding::AddressCodec,
     proto::{
         compact_formats::CompactBlock,
-        service::{
-            self, compact_tx_streamer_client::CompactTxStreamerClient, Empty, RawTransaction,
-            TransparentAddressBlockFilter, TxFilter,
-        },
+        service::{self, RawTransaction, TransparentAddressBlockFilter, TxFilter},
     },
     wallet::WalletTransparentOutput,
     PoolType,
 };
 
 use crate::{
-    backing_store::Db,
+    address_book::get_address_labels,
+    backing_store::{open_connection, Db},
     block_source::BlockCacheError,
     blockrange::BlockRange,
     error::Error,
-    grpc::get_client,
-    interop::{Pool, SyncUpdate, SyncUpdateData, TransactionNote},
+    grpc::ServerPool,
+    integrity::HeightRange,
+    interop::{
+        Pool, SyncOptions, SyncPhase, SyncSummary, SyncUpdate, SyncUpdateData, TransactionNote,
+        DEFAULT_MAX_REORG_REWIND_DEPTH, DEFAULT_REORG_REWIND_DEPTH,
+    },
     lightclient::parse_network,
-    resilience::webrequest_with_retry,
+    resilience::ResilientClient,
     sql_statements::{GET_OUTPOINT_VALUE, GET_TRANSACTIONS_SQL},
 };
 
@@ -83,39 +86,44 @@ const CHUNK_CHANNEL_CAPACITY: usize = 10;
 /// https://discord.com/channels/809218587167293450/1250828701864693761/1250942856198230086
 const BLOCKS_CHUNK_THRESHOLD: usize = BLOCK_ACTIONS_MEMORY_LIMIT / CHUNK_CHANNEL_CAPACITY;
 
+#[tracing::instrument(skip_all)]
 pub async fn sync<P: AsRef<Path>>(
     uri: Uri,
     data_file: P,
     progress: Option<Box<dyn SyncUpdate>>,
-    min_confirmations: u32,
     continually: bool,
+    options: SyncOptions,
     cancellation_token: CancellationToken,
 ) -> Result<SyncUpdateData, Error> {
-    let mut client = get_client(uri.clone()).await?;
-    let info = webrequest_with_retry(
-        || async {
-            Ok(client
-                .clone()
-                .get_lightd_info(service::Empty {})
-                .await?
-                .into_inner())
-        },
+    let progress = Arc::new(progress);
+    let mut uris = vec![uri];
+    for server in &options.fallback_servers {
+        uris.push(server.parse().map_err(|_| {
+            Error::InvalidArgument(format!("Invalid fallback server URI: {}", server))
+        })?);
+    }
+    let client = ResilientClient::new(
+        ServerPool::connect(uris).await?,
+        options.retry_policy.clone().unwrap_or_default(),
         cancellation_token.clone(),
     )
-    .await?;
+    .with_retry_callback({
+        let progress = progress.clone();
+        move |status, delay, retry| report_retry(&progress, status, delay, retry)
+    });
+    let info = client.get_lightd_info().await?;
     let state = SyncState {
         cancellation_token,
-        min_confirmations,
         network: parse_network(&info)?,
-        progress: Arc::new(progress),
+        progress,
+        options,
+        reorgs: Arc::new(Mutex::new(ReorgTracker::default())),
+        metrics: Arc::new(SyncMetrics::new()),
+        servers: client.servers().clone(),
     };
 
     let mut db = Db::load(&data_file, state.network)?;
-    let conn = Connection::open(&data_file)?;
-
-    // 1) Download note commitment tree data from lightwalletd
-    // 2) Pass the commitment tree data to the database.
-    update_subtree_roots(&mut client.clone(), &mut db.data).await?;
+    let conn = open_connection(&data_file)?;
 
     let mut status = SyncUpdateData {
         current_step: 0,
@@ -123,24 +131,36 @@ pub async fn sync<P: AsRef<Path>>(
         last_fully_scanned_block: None,
         tip_height: 0,
         last_error: None,
+        phase: SyncPhase::SubtreeRoots,
+        progress: 0.0,
+        blocks_downloaded: 0,
+        bytes_downloaded: 0,
+        blocks_scanned: 0,
+        blocks_per_second: 0.0,
+        eta_seconds: None,
+        current_range: None,
+        current_range_priority: None,
+        summary: state.metrics.summary(),
+        server: state.servers.uri().map(|uri| uri.to_string()),
     };
+    report_status(&status, &state.progress);
+
+    // 1) Download note commitment tree data from lightwalletd
+    // 2) Pass the commitment tree data to the database.
+    let subtree_roots_started = Instant::now();
+    update_subtree_roots(&client, &mut db.data).await?;
+    state
+        .metrics
+        .record_elapsed(&state.metrics.subtree_roots_ms, subtree_roots_started);
 
     loop {
         // 3) Download chain tip metadata from lightwalletd
-        status.tip_height = webrequest_with_retry(
-            || async {
-                Ok(client
-                    .clone()
-                    .get_latest_block(service::ChainSpec::default())
-                    .await?
-                    .get_ref()
-                    .height)
-            },
-            state.cancellation_token.clone(),
-        )
-        .await?
-        .try_into()
-        .map_err(|e| Error::Internal(format!("Invalid block height: {}", e)))?;
+        status.tip_height = client
+            .get_latest_block()
+            .await?
+            .height
+            .try_into()
+            .map_err(|e| Error::Internal(format!("Invalid block height: {}", e)))?;
 
         // 4) Notify the wallet of the updated chain tip.
         db.data.update_chain_tip(status.tip_height.into())?;
@@ -156,7 +176,7 @@ pub async fn sync<P: AsRef<Path>>(
             if !txids.is_empty() {
                 initialize_transaction_fees(db, conn)?;
                 if let Some(sink) = progress.as_ref() {
-                    let mut conn = Connection::open(data_file)?;
+                    let mut conn = open_connection(data_file)?;
                     let new_transactions =
                         get_transactions(db, &mut conn, &network, None, None, None)?
                             .iter()
@@ -176,8 +196,11 @@ pub async fn sync<P: AsRef<Path>>(
             Ok(())
         }
 
+        set_phase(&mut status, SyncPhase::Transparent, &state);
+
         // Download all the transparent ops related to the wallet first.
         // We don't need batches for this as that would just multiply the number of LWD requests we have to make.
+        let transparent_started = Instant::now();
         let mut taddrs = db.data.get_transparent_addresses_and_sync_heights()?;
         let mut taddrs_to_scan = taddrs.clone();
         while !taddrs_to_scan.is_empty() {
@@ -192,12 +215,11 @@ pub async fn sync<P: AsRef<Path>>(
                     })?;
 
                 let txids = download_transparent_transactions(
-                    &mut client,
+                    &client,
                     &mut db,
                     &state.network,
                     &addr_info.address,
                     &range,
-                    state.cancellation_token.clone(),
                 )
                 .await?;
                 if !txids.is_empty() {
@@ -215,6 +237,9 @@ pub async fn sync<P: AsRef<Path>>(
 
             taddrs_to_scan = fill_in_taddrs_to_gap_limit(&mut taddrs, &mut db.data)?;
         }
+        state
+            .metrics
+            .record_elapsed(&state.metrics.transparent_ms, transparent_started);
 
         // 5) Get the suggested scan ranges from the wallet database
         let mut scan_ranges = db.data.suggest_scan_ranges()?;
@@ -226,10 +251,13 @@ pub async fn sync<P: AsRef<Path>>(
             // the first element of the vector of suggested ranges.
             match scan_ranges.first() {
                 Some(scan_range) if scan_range.priority() == ScanPriority::Verify => {
+                    set_current_range(&mut status, scan_range);
+                    set_phase(&mut status, SyncPhase::Verify, &state);
+
                     // Download and scan the blocks and check for scanning errors that indicate that the wallet's chain tip
                     // is out of sync with blockchain history.
                     let scan_result = download_and_scan_blocks(
-                        &mut client,
+                        &client,
                         Db::load(&data_file, state.network)?,
                         &state,
                         None,
@@ -273,12 +301,14 @@ pub async fn sync<P: AsRef<Path>>(
         status.total_steps =
             status.current_step + scan_ranges.iter().map(|r| r.len()).sum::<usize>() as u64;
 
-        update_and_report_status(&mut status, &db.data, min_confirmations, &state.progress)?;
+        status.phase = SyncPhase::Scan;
+        update_and_report_status(&mut status, &db.data, &state)?;
 
         let mut caught_up = true;
         for scan_range in scan_ranges.into_iter() {
+            set_current_range(&mut status, &scan_range);
             let scan_result = download_and_scan_blocks(
-                &mut client,
+                &client,
                 Db::load(&data_file, state.network)?,
                 &state,
                 Some(&status),
@@ -291,14 +321,17 @@ pub async fn sync<P: AsRef<Path>>(
 
             // Download and decrypt the full transactions we found in the compact blocks
             // so we can save their memos to the database.
-            download_full_shielded_transactions(
-                &mut client,
-                &data_file,
-                &mut db,
-                &state.network,
-                state.cancellation_token.clone(),
-            )
-            .await?;
+            let enhancement_started = Instant::now();
+            let enhanced =
+                download_full_shielded_transactions(&client, &data_file, &mut db, &state.network)
+                    .await?;
+            state
+                .metrics
+                .transactions_enhanced
+                .fetch_add(enhanced.len() as u64, Ordering::Relaxed);
+            state
+                .metrics
+                .record_elapsed(&state.metrics.enhancement_ms, enhancement_started);
 
             // Report all transactions that are in the block range we just scanned,
             // even if we didn't just download them (which would have only included shielded transactions).
@@ -314,7 +347,7 @@ pub async fn sync<P: AsRef<Path>>(
                 &state.network,
             )?;
 
-            update_and_report_status(&mut status, &db.data, min_confirmations, &state.progress)?;
+            update_and_report_status(&mut status, &db.data, &state)?;
 
             if scan_result.priorities_changed {
                 // The suggested scan ranges have been updated (either due to a continuity
@@ -324,126 +357,34 @@ pub async fn sync<P: AsRef<Path>>(
             }
         }
 
-        let mut satisfied_requests = BTreeSet::new();
-        loop {
-            let mut new_request_encountered = false;
-            for data_request in db.data.transaction_data_requests()? {
-                if satisfied_requests.contains(&data_request) {
-                    continue;
-                } else {
-                    new_request_encountered = true;
-                }
-
-                info!("Fetching data for request {:?}", data_request);
-                match data_request {
-                    TransactionDataRequest::GetStatus(txid) => {
-                        let status = fetch_transaction(
-                            &mut client,
-                            &state.network,
-                            status.tip_height.into(),
-                            txid,
-                        )
-                        .await?
-                        .map_or(
-                            TransactionStatus::TxidNotRecognized,
-                            |(_, mined_height)| {
-                                mined_height.map_or(
-                                    TransactionStatus::NotInMainChain,
-                                    TransactionStatus::Mined,
-                                )
-                            },
-                        );
-                        info!("Got status {:?}", status);
-                        db.data.set_transaction_status(txid, status)?;
-                    }
-                    TransactionDataRequest::Enhancement(txid) => {
-                        match fetch_transaction(
-                            &mut client,
-                            &state.network,
-                            status.tip_height.into(),
-                            txid,
-                        )
-                        .await?
-                        {
-                            None => {
-                                info!("Txid not recognized {:?}", txid);
-                                db.data.set_transaction_status(
-                                    txid,
-                                    TransactionStatus::TxidNotRecognized,
-                                )?;
-                            }
-                            Some((tx, mined_height)) => {
-                                info!(
-                                    "Enhancing tx {:?} with mined height {:?}",
-                                    txid, mined_height
-                                );
-                                decrypt_and_store_transaction(
-                                    &state.network,
-                                    &mut db.data,
-                                    &tx,
-                                    mined_height,
-                                )?;
-                            }
-                        }
-                    }
-                    TransactionDataRequest::SpendsFromAddress {
-                        address,
-                        block_range_start,
-                        block_range_end,
-                    } => {
-                        let address = address.encode(&state.network);
-                        let request = service::TransparentAddressBlockFilter {
-                            address: address.clone(),
-                            range: Some(service::BlockRange {
-                                start: Some(service::BlockId {
-                                    height: u64::from(block_range_start),
-                                    ..Default::default()
-                                }),
-                                end: block_range_end.map(|h| service::BlockId {
-                                    height: u64::from(h - 1), // `BlockRange` end is inclusive.
-                                    ..Default::default()
-                                }),
-                            }),
-                        };
+        status.current_range = None;
+        status.current_range_priority = None;
+        set_phase(&mut status, SyncPhase::Enhancement, &state);
 
-                        let mut stream = client.get_taddress_txids(request).await?.into_inner();
-                        while let Some(raw_tx) = stream.next().await {
-                            let (tx, mined_height) = parse_raw_transaction(
-                                &state.network,
-                                status.tip_height.into(),
-                                raw_tx?,
-                            )?;
-                            info!(
-                                "Found tx {:?} for address {} with mined height {:?}",
-                                tx.txid(),
-                                address,
-                                mined_height
-                            );
-                            decrypt_and_store_transaction(
-                                &state.network,
-                                &mut db.data,
-                                &tx,
-                                mined_height,
-                            )?
-                        }
-                    }
-                }
-
-                satisfied_requests.insert(data_request);
-            }
-
-            if !new_request_encountered {
-                break;
-            }
-        }
+        let enhancement_started = Instant::now();
+        let requests_handled = handle_transaction_data_requests(
+            &client,
+            &mut db,
+            &state.network,
+            status.tip_height.into(),
+        )
+        .await?;
+        state
+            .metrics
+            .data_requests
+            .fetch_add(requests_handled, Ordering::Relaxed);
+        state
+            .metrics
+            .record_elapsed(&state.metrics.enhancement_ms, enhancement_started);
 
         if caught_up {
-            update_status(&mut status, &db.data, min_confirmations)?;
+            update_status(&mut status, &db.data, &state)?;
 
             if !continually {
                 return Ok(status);
             }
 
+            status.phase = SyncPhase::Mempool;
             report_status(&status, &state.progress);
 
             // We'll loop around again when the next block is mined.
@@ -452,12 +393,118 @@ pub async fn sync<P: AsRef<Path>>(
             // but not noticed above, we'll end up waiting for yet *another* block to be mined.
             select! {
                 _ = state.cancellation_token.cancelled() => Err(Status::cancelled("Request cancelled")),
-                _ = watch_mempool(&state.network, &mut client, &data_file, &mut db, &state.progress) => Ok(()),
+                _ = watch_mempool(&state.network, &client, &data_file, &mut db, &state.progress) => Ok(()),
             }?;
+
+            // A new block was mined, so the next pass catches up from scratch.
+            // Progress only stays monotonic within a pass.
+            status.current_step = 0;
+            status.total_steps = 0;
+            status.progress = 0.0;
         }
     }
 }
 
+/// Satisfies the wallet's requests for transaction data, until it stops making new ones.
+///
+/// Returns the number of requests that were satisfied.
+#[tracing::instrument(skip_all)]
+async fn handle_transaction_data_requests(
+    client: &ResilientClient,
+    db: &mut Db,
+    network: &Network,
+    tip_height: BlockHeight,
+) -> Result<u64, Error> {
+    let mut satisfied_requests = BTreeSet::new();
+    loop {
+        let mut new_request_encountered = false;
+        for data_request in db.data.transaction_data_requests()? {
+            if satisfied_requests.contains(&data_request) {
+                continue;
+            } else {
+                new_request_encountered = true;
+            }
+
+            info!("Fetching data for request {:?}", data_request);
+            match data_request {
+                TransactionDataRequest::GetStatus(txid) => {
+                    let status = fetch_transaction(client, network, tip_height, txid)
+                        .await?
+                        .map_or(TransactionStatus::TxidNotRecognized, |(_, mined_height)| {
+                            mined_height
+                                .map_or(TransactionStatus::NotInMainChain, TransactionStatus::Mined)
+                        });
+                    info!("Got status {:?}", status);
+                    db.data.set_transaction_status(txid, status)?;
+                }
+                TransactionDataRequest::Enhancement(txid) => {
+                    match fetch_transaction(client, network, tip_height, txid).await? {
+                        None => {
+                            info!("Txid not recognized {:?}", txid);
+                            db.data.set_transaction_status(
+                                txid,
+                                TransactionStatus::TxidNotRecognized,
+                            )?;
+                        }
+                        Some((tx, mined_height)) => {
+                            info!(
+                                "Enhancing tx {:?} with mined height {:?}",
+                                txid, mined_height
+                            );
+                            decrypt_and_store_transaction(
+                                network,
+                                &mut db.data,
+                                &tx,
+                                mined_height,
+                            )?;
+                        }
+                    }
+                }
+                TransactionDataRequest::SpendsFromAddress {
+                    address,
+                    block_range_start,
+                    block_range_end,
+                } => {
+                    let address = address.encode(network);
+                    let request = service::TransparentAddressBlockFilter {
+                        address: address.clone(),
+                        range: Some(service::BlockRange {
+                            start: Some(service::BlockId {
+                                height: u64::from(block_range_start),
+                                ..Default::default()
+                            }),
+                            end: block_range_end.map(|h| service::BlockId {
+                                height: u64::from(h - 1), // `BlockRange` end is inclusive.
+                                ..Default::default()
+                            }),
+                        }),
+                    };
+
+                    for raw_tx in client.get_taddress_txids(request).await? {
+                        let (tx, mined_height) =
+                            parse_raw_transaction(network, tip_height, raw_tx)?;
+                        info!(
+                            "Found tx {:?} for address {} with mined height {:?}",
+                            tx.txid(),
+                            address,
+                            mined_height
+                        );
+                        decrypt_and_store_transaction(network, &mut db.data, &tx, mined_height)?
+                    }
+                }
+            }
+
+            satisfied_requests.insert(data_request);
+        }
+
+        if !new_request_encountered {
+            break;
+        }
+    }
+
+    Ok(satisfied_requests.len() as u64)
+}
+
 fn report_transactions_in_range<P: AsRef<Path>>(
     range: &Range<BlockHeight>,
     only_txid: Option<TxId>,
@@ -471,7 +518,7 @@ fn report_transactions_in_range<P: AsRef<Path>>(
         initialize_transaction_fees(db, conn)?;
     }
     if let Some(sink) = progress.as_ref() {
-        let mut conn = Connection::open(data_file)?;
+        let mut conn = open_connection(data_file)?;
         let transactions = get_transactions(
             db,
             &mut conn,
@@ -503,20 +550,40 @@ fn report_transactions_in_range<P: AsRef<Path>>(
 fn update_status<'a>(
     status: &'a mut SyncUpdateData,
     data: &WalletDb<Connection, Network>,
-    min_confirmations: u32,
+    state: &SyncState,
 ) -> Result<&'a SyncUpdateData, Error> {
     status.last_fully_scanned_block = data.block_fully_scanned()?.map(|b| b.block_height().into());
 
-    // Disabled for now because it's unstable -- it goes backwards, jumps around, etc.
-    if false {
-        if let Some(wallet_progress) = data
-            .get_wallet_summary(min_confirmations)
-            .unwrap_or(None)
-            .and_then(|s| s.scan_progress())
-        {
-            status.current_step = *wallet_progress.numerator();
-            status.total_steps = *wallet_progress.denominator();
-        }
+    let metrics = &state.metrics;
+    status.blocks_downloaded = metrics.blocks_downloaded.load(Ordering::Relaxed);
+    status.bytes_downloaded = metrics.bytes_downloaded.load(Ordering::Relaxed);
+    status.blocks_scanned = metrics.blocks_scanned.load(Ordering::Relaxed);
+    status.summary = metrics.summary();
+    status.server = state.servers.uri().map(|uri| uri.to_string());
+
+    let elapsed = metrics.started.elapsed().as_secs_f64();
+    status.blocks_per_second = if elapsed > 0.0 {
+        status.blocks_scanned as f64 / elapsed
+    } else {
+        0.0
+    };
+    let remaining_steps = status.total_steps.saturating_sub(status.current_step);
+    status.eta_seconds = if status.blocks_per_second > 0.0 {
+        Some((remaining_steps as f64 / status.blocks_per_second).ceil() as u64)
+    } else {
+        None
+    };
+
+    // The wallet summary's scan progress is not used because it goes backwards and jumps around
+    // as scan ranges are re-prioritized, and it was the only reason sync needed min_confirmations.
+    // Our own step counts only move forward, but the total can still grow (e.g. when a reorg
+    // adds ranges), so the reported fraction is clamped so that it never decreases within a pass.
+    if status.total_steps > 0 {
+        let progress = status.current_step as f64 / status.total_steps as f64;
+        status.progress = status.progress.max(progress.min(1.0));
+    }
+    if remaining_steps == 0 && status.phase != SyncPhase::SubtreeRoots {
+        status.progress = 1.0;
     }
 
     Ok(status)
@@ -531,13 +598,28 @@ fn report_status(status: &SyncUpdateData, progress: &Option<Box<dyn SyncUpdate>>
 fn update_and_report_status(
     status: &mut SyncUpdateData,
     data: &WalletDb<Connection, Network>,
-    min_confirmations: u32,
-    progress: &Option<Box<dyn SyncUpdate>>,
+    state: &SyncState,
 ) -> Result<(), Error> {
-    report_status(update_status(status, data, min_confirmations)?, progress);
+    report_status(update_status(status, data, state)?, state.progress.borrow());
     Ok(())
 }
 
+/// Moves the sync to a new phase and reports it.
+fn set_phase(status: &mut SyncUpdateData, phase: SyncPhase, state: &SyncState) {
+    if status.phase != phase {
+        status.phase = phase;
+        report_status(status, state.progress.borrow());
+    }
+}
+
+fn set_current_range(status: &mut SyncUpdateData, scan_range: &ScanRange) {
+    status.current_range = Some(HeightRange {
+        start: scan_range.block_range().start.into(),
+        end: scan_range.block_range().end.into(),
+    });
+    status.current_range_priority = Some(scan_range.priority().into());
+}
+
 struct DownloadAndScanResult {
     priorities_changed: bool,
     status: Option<SyncUpdateData>,
@@ -677,19 +759,56 @@ fn initialize_transaction_fees(db: &mut Db, conn: &Connection) -> Result<(), Err
     Ok(())
 }
 
+/// Downloads and scans just the blocks in `range`, without otherwise syncing the wallet.
+///
+/// This finds the wallet's notes in a bounded stretch of the chain, such as when probing
+/// whether an account was ever used. Memos and transparent history are not retrieved.
+pub(crate) async fn scan_block_range<P: AsRef<Path>>(
+    uri: Uri,
+    data_file: P,
+    range: Range<BlockHeight>,
+    cancellation_token: CancellationToken,
+) -> Result<(), Error> {
+    let client = ResilientClient::new(
+        ServerPool::connect(vec![uri]).await?,
+        Default::default(),
+        cancellation_token.clone(),
+    );
+    let info = client.get_lightd_info().await?;
+    let state = SyncState {
+        cancellation_token,
+        network: parse_network(&info)?,
+        progress: Arc::new(None),
+        options: SyncOptions::default(),
+        reorgs: Arc::new(Mutex::new(ReorgTracker::default())),
+        metrics: Arc::new(SyncMetrics::new()),
+        servers: client.servers().clone(),
+    };
+
+    download_and_scan_blocks(
+        &client,
+        Db::load(&data_file, state.network)?,
+        &state,
+        None,
+        &ScanRange::from_parts(range, ScanPriority::Historic),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
 async fn download_full_shielded_transactions<P: AsRef<Path> + Clone>(
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     data_file: P,
     db: &mut Db,
     network: &Network,
-    cancellation_token: CancellationToken,
 ) -> Result<Vec<TxId>, Error> {
-    let client = Arc::new(Mutex::new(client));
     // Scope the database connection so it's closed before we use the db argument,
     // to avoid 'database is locked' errors.
     let txids;
     {
-        let conn = Connection::open(data_file.clone())?;
+        let conn = open_connection(data_file.clone())?;
         let mut stmt = conn.prepare("SELECT txid FROM transactions WHERE raw IS NULL")?;
         txids = stmt
             .query_map([], |r| r.get::<_, [u8; 32]>(0).map(TxId::from_bytes))?
@@ -697,21 +816,17 @@ async fn download_full_shielded_transactions<P: AsRef<Path> + Clone>(
     }
 
     for txid in txids.iter() {
-        let raw_tx = webrequest_with_retry(
-            || async {
-                Ok(client
-                    .lock()
-                    .await
-                    .get_transaction(TxFilter {
-                        hash: txid.as_ref().to_vec(),
-                        ..Default::default()
-                    })
-                    .await?
-                    .into_inner())
-            },
-            cancellation_token.clone(),
-        )
-        .await?;
+        let Some(raw_tx) = client
+            .get_transaction(TxFilter {
+                hash: txid.as_ref().to_vec(),
+                ..Default::default()
+            })
+            .await?
+        else {
+            // The next sync will try again.
+            info!("Txid not recognized {:?}", txid);
+            continue;
+        };
 
         // The consensus branch ID passed in here does not matter:
         // - v4 and below cache it internally, but all we do with this transaction while
@@ -732,7 +847,7 @@ async fn download_full_shielded_transactions<P: AsRef<Path> + Clone>(
 }
 
 async fn update_subtree_roots<P: Parameters>(
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     db_data: &mut WalletDb<rusqlite::Connection, P>,
 ) -> Result<(), anyhow::Error> {
     // Update sapling subtree roots
@@ -741,16 +856,15 @@ async fn update_subtree_roots<P: Parameters>(
     let roots: Vec<CommitmentTreeRoot<sapling::Node>> = client
         .get_subtree_roots(request)
         .await?
-        .into_inner()
-        .and_then(|root| async move {
+        .into_iter()
+        .map(|root| {
             let root_hash = sapling::Node::read(&root.root_hash[..])?;
-            Ok(CommitmentTreeRoot::from_parts(
+            Ok::<_, std::io::Error>(CommitmentTreeRoot::from_parts(
                 BlockHeight::from_u32(root.completing_block_height as u32),
                 root_hash,
             ))
         })
-        .try_collect()
-        .await?;
+        .collect::<Result<_, _>>()?;
     db_data.put_sapling_subtree_roots(0, &roots)?;
 
     // Update orchard subtree roots
@@ -759,52 +873,38 @@ async fn update_subtree_roots<P: Parameters>(
     let roots: Vec<CommitmentTreeRoot<MerkleHashOrchard>> = client
         .get_subtree_roots(request)
         .await?
-        .into_inner()
-        .and_then(|root| async move {
+        .into_iter()
+        .map(|root| {
             let root_hash = MerkleHashOrchard::read(&root.root_hash[..])?;
-            Ok(CommitmentTreeRoot::from_parts(
+            Ok::<_, std::io::Error>(CommitmentTreeRoot::from_parts(
                 BlockHeight::from_u32(root.completing_block_height as u32),
                 root_hash,
             ))
         })
-        .try_collect()
-        .await?;
+        .collect::<Result<_, _>>()?;
     db_data.put_orchard_subtree_roots(0, roots.as_slice())?;
 
     Ok(())
 }
 
 async fn download_transparent_transactions(
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     db: &mut Db,
     network: &Network,
     address: &TransparentAddress,
     range: &BlockRange,
-    cancellation_token: CancellationToken,
 ) -> Result<Vec<TxId>, Error> {
     if range.block_range().is_empty() {
         return Ok(vec![]);
     }
 
     let mut txids_for_new_transparent_transactions = vec![];
-    let client = Arc::new(Mutex::new(client));
-    let transparent_transactions = webrequest_with_retry(
-        || async {
-            client
-                .lock()
-                .await
-                .get_taddress_txids(TransparentAddressBlockFilter {
-                    address: address.encode(network),
-                    range: Some(range.into()),
-                })
-                .await?
-                .into_inner()
-                .try_collect::<Vec<_>>()
-                .await
-        },
-        cancellation_token,
-    )
-    .await?;
+    let transparent_transactions = client
+        .get_taddress_txids(TransparentAddressBlockFilter {
+            address: address.encode(network),
+            range: Some(range.into()),
+        })
+        .await?;
     for rawtx in transparent_transactions {
         let height = BlockHeight::from_u32(rawtx.height as u32);
         let tx = Transaction::read(&rawtx.data[..], BranchId::for_height(network, height))?;
@@ -840,12 +940,118 @@ async fn download_transparent_transactions(
 struct SyncState {
     network: Network,
     progress: Arc<Option<Box<dyn SyncUpdate>>>,
-    min_confirmations: u32,
+    options: SyncOptions,
+    reorgs: Arc<Mutex<ReorgTracker>>,
+    /// The servers being synced from, to report which one is in use.
+    servers: Arc<ServerPool>,
+    metrics: Arc<SyncMetrics>,
     cancellation_token: CancellationToken,
 }
 
+/// Counters shared by the tasks that download and scan blocks during a sync.
+#[derive(Debug)]
+struct SyncMetrics {
+    started: Instant,
+    blocks_downloaded: AtomicU64,
+    bytes_downloaded: AtomicU64,
+    blocks_scanned: AtomicU64,
+    transactions_enhanced: AtomicU64,
+    data_requests: AtomicU64,
+    reorgs: AtomicU64,
+    subtree_roots_ms: AtomicU64,
+    transparent_ms: AtomicU64,
+    download_ms: AtomicU64,
+    scan_ms: AtomicU64,
+    enhancement_ms: AtomicU64,
+}
+
+impl SyncMetrics {
+    fn new() -> Self {
+        SyncMetrics {
+            started: Instant::now(),
+            blocks_downloaded: AtomicU64::new(0),
+            bytes_downloaded: AtomicU64::new(0),
+            blocks_scanned: AtomicU64::new(0),
+            transactions_enhanced: AtomicU64::new(0),
+            data_requests: AtomicU64::new(0),
+            reorgs: AtomicU64::new(0),
+            subtree_roots_ms: AtomicU64::new(0),
+            transparent_ms: AtomicU64::new(0),
+            download_ms: AtomicU64::new(0),
+            scan_ms: AtomicU64::new(0),
+            enhancement_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Adds the time since `started` to a timer.
+    fn record_elapsed(&self, timer: &AtomicU64, started: Instant) {
+        timer.fetch_add(started.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+
+    fn summary(&self) -> SyncSummary {
+        SyncSummary {
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            subtree_roots_ms: self.subtree_roots_ms.load(Ordering::Relaxed),
+            transparent_ms: self.transparent_ms.load(Ordering::Relaxed),
+            download_ms: self.download_ms.load(Ordering::Relaxed),
+            scan_ms: self.scan_ms.load(Ordering::Relaxed),
+            enhancement_ms: self.enhancement_ms.load(Ordering::Relaxed),
+            transactions_enhanced: self.transactions_enhanced.load(Ordering::Relaxed),
+            data_requests: self.data_requests.load(Ordering::Relaxed),
+            reorgs: self.reorgs.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn report_retry(
+    progress: &Option<Box<dyn SyncUpdate>>,
+    status: &Status,
+    delay: Duration,
+    retry: u32,
+) {
+    info!(
+        "Request failed ({:?}). Retry #{} in {:?}.",
+        status.code(),
+        retry,
+        delay
+    );
+    if let Some(sink) = progress.as_ref() {
+        sink.on_retry(retry, delay.as_millis() as u64, status.to_string());
+    }
+}
+
+/// Tracks repeated chain reorgs detected at the same height, so that each one rewinds further than the last.
+#[derive(Debug, Default)]
+struct ReorgTracker {
+    height: Option<BlockHeight>,
+    repeats: u32,
+}
+
+impl ReorgTracker {
+    /// Records a continuity error at the given height and returns the number of blocks to rewind.
+    fn rewind_depth(&mut self, at_height: BlockHeight, options: &SyncOptions) -> u32 {
+        if self.height == Some(at_height) {
+            self.repeats = self.repeats.saturating_add(1);
+        } else {
+            self.height = Some(at_height);
+            self.repeats = 0;
+        }
+
+        options
+            .reorg_rewind_depth
+            .unwrap_or(DEFAULT_REORG_REWIND_DEPTH)
+            .saturating_mul(2u32.saturating_pow(self.repeats))
+            .min(
+                options
+                    .max_reorg_rewind_depth
+                    .unwrap_or(DEFAULT_MAX_REORG_REWIND_DEPTH),
+            )
+            .max(1)
+    }
+}
+
 async fn download_and_scan_blocks(
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     mut db: Db,
     state: &SyncState,
     status: Option<&SyncUpdateData>,
@@ -858,82 +1064,97 @@ async fn download_and_scan_blocks(
 
     // Download the blocks in `scan_range` into the block source, overwriting any
     // existing blocks in this range.
-    let mut client = client.to_owned();
+    let client = client.clone();
     let downloader_block_range = block_range.clone();
     let downloader_priorities_changed_token = priorities_changed_token.clone();
-    let downloader = tokio::spawn(async move {
-        download_blocks(
-            &mut client,
-            &downloader_block_range,
-            send,
-            downloader_priorities_changed_token,
-        )
-        .await
-    });
+    let downloader_metrics = state.metrics.clone();
+    let downloader = tokio::spawn(
+        async move {
+            let started = Instant::now();
+            let result = download_blocks(
+                &client,
+                &downloader_block_range,
+                send,
+                &downloader_metrics,
+                downloader_priorities_changed_token,
+            )
+            .await;
+            downloader_metrics.record_elapsed(&downloader_metrics.download_ms, started);
+            result
+        }
+        .in_current_span(),
+    );
 
     let state = state.clone();
     let mut status = status.cloned();
     let scanner_block_range = block_range.clone();
-    let scanner = tokio::spawn(async move {
-        let mut priorities_changed = false;
-        while let Some((chunk, chain_state)) = select! {
-            result = receive.recv() => Ok(result),
-            _ = state.cancellation_token.cancelled() => Err(Error::Canceled),
-        }? {
-            let scan_range = ScanRange::from_parts(
-                chunk.first().unwrap().height()..chunk.last().unwrap().height() + 1,
-                scanner_block_range.priority(),
-            );
+    let scanner = tokio::spawn(
+        async move {
+            let mut priorities_changed = false;
+            while let Some((chunk, chain_state)) = select! {
+                result = receive.recv() => Ok(result),
+                _ = state.cancellation_token.cancelled() => Err(Error::Canceled),
+            }? {
+                let scan_range = ScanRange::from_parts(
+                    chunk.first().unwrap().height()..chunk.last().unwrap().height() + 1,
+                    scanner_block_range.priority(),
+                );
 
-            info!("Scanning {} blocks [{}).", chunk.len(), scan_range);
+                info!("Scanning {} blocks [{}).", chunk.len(), scan_range);
+
+                // Insert the blocks into the block cache.
+                db.blocks.insert_range(chunk);
+
+                let scan_started = Instant::now();
+                let scan_result = scan_blocks(&state, &mut db, &scan_range, &chain_state);
+                state
+                    .metrics
+                    .record_elapsed(&state.metrics.scan_ms, scan_started);
+                if scan_result? && !priorities_changed {
+                    // Notify the downloader to break out early because we'll be getting a new range request.
+                    // But we don't abort here. Presumably the original scan range is still interesting
+                    // (just less so), so don't throw away what we've already downloaded.
+                    priorities_changed_token.cancel();
+                    priorities_changed = true;
+                    info!("Resetting...");
+                }
 
-            // Insert the blocks into the block cache.
-            db.blocks.insert_range(chunk);
+                // Now that they've been scanned, we don't need them any more.
+                db.blocks.remove_range(scan_range.block_range());
+                state
+                    .metrics
+                    .blocks_scanned
+                    .fetch_add(scan_range.len() as u64, Ordering::Relaxed);
 
-            if scan_blocks(&state.network, &mut db, &scan_range, &chain_state)?
-                && !priorities_changed
-            {
-                // Notify the downloader to break out early because we'll be getting a new range request.
-                // But we don't abort here. Presumably the original scan range is still interesting
-                // (just less so), so don't throw away what we've already downloaded.
-                priorities_changed_token.cancel();
-                priorities_changed = true;
-                info!("Resetting...");
-            }
-
-            // Now that they've been scanned, we don't need them any more.
-            db.blocks.remove_range(scan_range.block_range());
+                if let Some(s) = status.as_mut() {
+                    s.current_step += scan_range.len() as u64;
+                    update_and_report_status(s, &db.data, &state)?;
+                }
 
-            if let Some(s) = status.as_mut() {
-                s.current_step += scan_range.len() as u64;
-                update_and_report_status(
-                    s,
-                    &db.data,
-                    state.min_confirmations,
-                    state.progress.borrow(),
-                )?;
+                if state.cancellation_token.is_cancelled() {
+                    return Err(Error::Canceled);
+                }
             }
 
-            if state.cancellation_token.is_cancelled() {
-                return Err(Error::Canceled);
-            }
+            Ok::<_, Error>(DownloadAndScanResult {
+                priorities_changed,
+                status,
+            })
         }
-
-        Ok::<_, Error>(DownloadAndScanResult {
-            priorities_changed,
-            status,
-        })
-    });
+        .in_current_span(),
+    );
 
     let (_, scan_result) = tokio::try_join!(downloader, scanner)?;
 
     scan_result
 }
 
+#[tracing::instrument(skip_all, fields(range = %scan_range))]
 async fn download_blocks(
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     scan_range: &ScanRange,
     sender: mpsc::Sender<(Vec<CompactBlock>, ChainState)>,
+    metrics: &SyncMetrics,
     cancellation_token: CancellationToken,
 ) -> Result<(), Status> {
     info!("Fetching {}", scan_range);
@@ -947,9 +1168,14 @@ async fn download_blocks(
     };
 
     let mut blocks = Vec::new();
-    let mut stream = client.get_block_range(range).await?.into_inner();
+    let mut stream = Box::pin(client.get_block_range(range));
     let mut accumulated_size = 0;
     while let Some(block) = stream.try_next().await? {
+        metrics.blocks_downloaded.fetch_add(1, Ordering::Relaxed);
+        metrics
+            .bytes_downloaded
+            .fetch_add(block.encoded_len() as u64, Ordering::Relaxed);
+
         // Process each block here
         accumulated_size += block.vtx.iter().fold(0, |acc, tx| {
             acc + tx.actions.len() + tx.outputs.len() + tx.spends.len()
@@ -978,7 +1204,7 @@ async fn download_blocks(
     }
 
     async fn send_blocks_and_chainstate(
-        client: &mut CompactTxStreamerClient<Channel>,
+        client: &ResilientClient,
         blocks: Vec<CompactBlock>,
         sender: &mpsc::Sender<(Vec<CompactBlock>, ChainState)>,
     ) -> Result<(), Status> {
@@ -988,8 +1214,7 @@ async fn download_blocks(
                 height: base_height,
                 ..Default::default()
             })
-            .await?
-            .into_inner();
+            .await?;
         let chain_state = tree_state.to_chain_state()?;
 
         sender.send((blocks, chain_state)).await.unwrap();
@@ -1004,14 +1229,15 @@ async fn download_blocks(
 /// chain tip is out of sync with blockchain history.
 ///
 /// Returns `true` if scanning these blocks materially changed the suggested scan ranges.
+#[tracing::instrument(skip_all, fields(range = %scan_range))]
 fn scan_blocks(
-    network: &Network,
+    state: &SyncState,
     db: &mut Db,
     scan_range: &ScanRange,
     chain_state: &ChainState,
 ) -> Result<bool, Error> {
     let scan_result = scan_cached_blocks(
-        network,
+        &state.network,
         &db.blocks,
         &mut db.data,
         scan_range.block_range().start,
@@ -1035,18 +1261,34 @@ fn scan_blocks(
         }
         Err(ChainError::Scan(err)) if err.is_continuity_error() => {
             // Pick a height to rewind to, which must be at least one block before
-            // the height at which the error occurred, but may be an earlier height
-            // determined based on heuristics such as the platform, available bandwidth,
-            // size of recent CompactBlocks, etc.
-            let rewind_height = err.at_height().saturating_sub(10);
+            // the height at which the error occurred. Repeated errors at the same height
+            // suggest a deeper reorg, so each one rewinds further than the last.
+            let rewind_depth = state
+                .reorgs
+                .lock()
+                .unwrap()
+                .rewind_depth(err.at_height(), &state.options);
+            let mut rewind_height = err.at_height().saturating_sub(rewind_depth);
             info!(
                 "Chain reorg detected at {}, rewinding to {}",
                 err.at_height(),
                 rewind_height,
             );
 
-            // Rewind to the chosen height.
-            db.data.truncate_to_height(rewind_height)?;
+            // Rewind to the chosen height, or to the earliest height the wallet can rewind to
+            // if the chosen one is too deep.
+            match db.data.truncate_to_height(rewind_height) {
+                Ok(()) => {}
+                Err(SqliteClientError::RequestedRewindInvalid(safe_height, _)) => {
+                    info!(
+                        "Unable to rewind to {}, rewinding to {} instead",
+                        rewind_height, safe_height,
+                    );
+                    rewind_height = safe_height;
+                    db.data.truncate_to_height(rewind_height)?;
+                }
+                Err(e) => return Err(e.into()),
+            }
 
             // Delete cached blocks from rewind_height onwards.
             //
@@ -1055,6 +1297,11 @@ fn scan_blocks(
             // occurred that orphaned some of those blocks.
             db.blocks.truncate_to_height(rewind_height);
 
+            state.metrics.reorgs.fetch_add(1, Ordering::Relaxed);
+            if let Some(sink) = state.progress.as_ref() {
+                sink.reorg_detected(err.at_height().into(), rewind_height.into());
+            }
+
             Ok(true)
         }
         Err(other) => Err(other.into()),
@@ -1063,12 +1310,12 @@ fn scan_blocks(
 
 async fn watch_mempool<P: AsRef<Path>>(
     network: &Network,
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     data_file: &P,
     db: &mut Db,
     progress: &Option<Box<dyn SyncUpdate>>,
 ) -> Result<(), Error> {
-    let mut response = client.get_mempool_stream(Empty {}).await?.into_inner();
+    let mut response = client.get_mempool_stream().await?;
 
     let mempool_range = db.data.chain_height()?.map(|h| (h + 1)..(h + 1));
 
@@ -1103,7 +1350,7 @@ async fn watch_mempool<P: AsRef<Path>>(
 }
 
 async fn fetch_transaction(
-    client: &mut CompactTxStreamerClient<Channel>,
+    client: &ResilientClient,
     params: &Network,
     chain_tip: BlockHeight,
     txid: TxId,
@@ -1113,13 +1360,7 @@ async fn fetch_transaction(
         ..Default::default()
     };
 
-    let raw_tx = match client.get_transaction(request).await {
-        Ok(response) => Ok(Some(response.into_inner())),
-        Err(status) if status.code() == tonic::Code::NotFound => Ok(None),
-        // Workaround for https://github.com/ZcashFoundation/zebra/issues/8786 and https://github.com/zcash/lightwalletd/issues/497
-        Err(status) if status.code() == tonic::Code::Unknown && (status.message() == "-5: No such mempool or blockchain transaction. Use gettransaction for wallet transactions." || status.message() == "0: Transaction not found") => Ok(None),
-        Err(status) => Err(status),
-    }?;
+    let raw_tx = client.get_transaction(request).await?;
 
     raw_tx
         .map(|raw_tx| parse_raw_transaction(params, chain_tip, raw_tx))
@@ -1143,6 +1384,40 @@ fn parse_raw_transaction(
     Ok((tx, mined_height))
 }
 
+/// Regenerates the address that received a shielded note from the note's diversifier.
+///
+/// Returns `None` if the pool is not shielded or the key for that pool is not available.
+pub(crate) fn address_from_diversifier(
+    ufvk: Option<&UnifiedFullViewingKey>,
+    output_pool: PoolType,
+    diversifier: Vec<u8>,
+    network: &Network,
+) -> Option<String> {
+    match output_pool {
+        PoolType::SAPLING => ufvk.and_then(|k| {
+            k.sapling().and_then(|s| {
+                s.diversified_address(sapling::keys::Diversifier(diversifier.try_into().unwrap()))
+                    .map(|a| a.encode(network))
+            })
+        }),
+        PoolType::ORCHARD => ufvk.and_then(|k| {
+            k.orchard().map(|o| {
+                UnifiedAddress::from_receivers(
+                    Some(o.address(
+                        orchard::keys::Diversifier::from_bytes(diversifier.try_into().unwrap()),
+                        Scope::External,
+                    )),
+                    None,
+                    None,
+                )
+                .unwrap()
+                .encode(network)
+            })
+        }),
+        _ => None,
+    }
+}
+
 /// Returns the transactions that match the given filters.
 ///
 /// `starting_block_filter` and `ending_block_filter` are inclusive.
@@ -1158,6 +1433,8 @@ pub(crate) fn get_transactions(
 
     rusqlite::vtab::array::load_module(conn)?;
 
+    let address_labels = get_address_labels(conn, network)?;
+
     let mut stmt_txs = conn.prepare(GET_TRANSACTIONS_SQL)?;
 
     let rows = stmt_txs.query_and_then(
@@ -1194,33 +1471,7 @@ pub(crate) fn get_transactions(
             if recipient.is_none() {
                 let diversifier: Option<Vec<u8>> = row.get("diversifier")?;
                 if let Some(diversifier) = diversifier {
-                    recipient = match output_pool {
-                        PoolType::SAPLING => ufvk.and_then(|k| {
-                            k.sapling().and_then(|s| {
-                                s.diversified_address(sapling::keys::Diversifier(
-                                    diversifier.try_into().unwrap(),
-                                ))
-                                .map(|a| a.encode(network))
-                            })
-                        }),
-                        PoolType::ORCHARD => ufvk.and_then(|k| {
-                            k.orchard().map(|o| {
-                                UnifiedAddress::from_receivers(
-                                    Some(o.address(
-                                        orchard::keys::Diversifier::from_bytes(
-                                            diversifier.try_into().unwrap(),
-                                        ),
-                                        Scope::External,
-                                    )),
-                                    None,
-                                    None,
-                                )
-                                .unwrap()
-                                .encode(network)
-                            })
-                        }),
-                        _ => None,
-                    }
+                    recipient = address_from_diversifier(ufvk, output_pool, diversifier, network);
                 }
             }
 
@@ -1245,6 +1496,7 @@ pub(crate) fn get_transactions(
                     None => None,
                 },
                 fee: row.get::<_, Option<u64>>("fee_paid")?,
+                label: row.get("label")?,
                 account_balance_delta: row.get("account_balance_delta")?,
                 incoming: Vec::new(),
                 outgoing: Vec::new(),
@@ -1253,6 +1505,9 @@ pub(crate) fn get_transactions(
 
             let note = TransactionNote {
                 value,
+                recipient_label: recipient
+                    .as_ref()
+                    .and_then(|r| address_labels.get(r).cloned()),
                 recipient: recipient.clone().unwrap(),
                 pool: match output_pool {
                     PoolType::Transparent => Pool::Transparent,
@@ -1322,6 +1577,34 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_reorg_tracker_doubles_up_to_cap() {
+        let options = SyncOptions::default();
+        let mut tracker = ReorgTracker::default();
+        let height = BlockHeight::from_u32(1000);
+
+        let depths = (0..6)
+            .map(|_| tracker.rewind_depth(height, &options))
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![10, 20, 40, 80, 100, 100]);
+
+        // A reorg at another height starts over.
+        assert_eq!(tracker.rewind_depth(height + 1, &options), 10);
+
+        let options = SyncOptions {
+            reorg_rewind_depth: Some(0),
+            max_reorg_rewind_depth: Some(3),
+            ..Default::default()
+        };
+        let mut tracker = ReorgTracker::default();
+        assert_eq!(tracker.rewind_depth(height, &options), 1);
+        let options = SyncOptions {
+            reorg_rewind_depth: Some(2),
+            ..options
+        };
+        assert_eq!(tracker.rewind_depth(height, &options), 3);
+    }
+
     #[tokio_shared_rt::test(flavor = "multi_thread")]
     async fn test_sync() {
         let mut setup = setup_test().await;
@@ -1349,14 +1632,18 @@ mod tests {
             setup.server_uri.clone(),
             &setup.data_file,
             None,
-            setup.db_init.min_confirmations,
             false,
+            SyncOptions::default(),
             CancellationToken::new(),
         )
         .await
         .unwrap();
 
         info!("Tip: {:?}", result.last_fully_scanned_block);
+        assert_eq!(result.progress, 1.0);
+        assert!(result.blocks_scanned > 0);
+        assert!(result.blocks_downloaded >= result.blocks_scanned);
+        assert_eq!(result.server, Some(setup.server_uri.to_string()));
 
         if let Some(summary) = setup
             .db
@@ -1381,7 +1668,7 @@ mod tests {
             info!("No summary found");
         }
 
-        let mut conn = Connection::open(setup.db_init.data_file).unwrap();
+        let mut conn = open_connection(setup.db_init.data_file).unwrap();
         let txs =
             get_transactions(&mut setup.db, &mut conn, &setup.network, None, None, None).unwrap();
         assert_eq!(txs.len(), 0);
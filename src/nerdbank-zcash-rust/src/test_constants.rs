@@ -1,5 +1,7 @@
 use bip0039::{Count, English, Mnemonic};
 use http::Uri;
+use rand::RngCore;
+use rusqlite::named_params;
 use secrecy::{Secret, SecretVec};
 use testdir::testdir;
 use tokio_util::sync::CancellationToken;
@@ -10,10 +12,11 @@ use zcash_client_backend::proto::service::{
     self, compact_tx_streamer_client::CompactTxStreamerClient, LightdInfo,
 };
 use zcash_client_sqlite::AccountId;
-use zcash_primitives::consensus::Network;
+use zcash_primitives::consensus::{Network, NetworkUpgrade, Parameters};
 
+use crate::backing_store::open_connection;
 use crate::error::Error;
-use crate::interop::SyncUpdateData;
+use crate::interop::{SyncOptions, SyncUpdateData};
 use crate::resilience::webrequest_with_retry;
 use crate::sync::sync;
 use crate::{backing_store::Db, grpc::get_client, interop::DbInit, lightclient::parse_network};
@@ -25,7 +28,25 @@ lazy_static! {
         Uri::from_static("https://zcash.mysideoftheweb.com:9067/");
 }
 
+lazy_static! {
+    /// Held by tests that change process-wide state, such as connection options or Tor,
+    /// so that they don't run at the same time.
+    static ref GLOBAL_STATE: tokio::sync::Mutex<()> = tokio::sync::Mutex::new(());
+}
+
+/// Waits until no other test is changing process-wide state, and keeps them from doing so until the guard is dropped.
+pub(crate) async fn lock_global_state() -> tokio::sync::MutexGuard<'static, ()> {
+    GLOBAL_STATE.lock().await
+}
+
 pub(crate) const MIN_CONFIRMATIONS: u32 = 3;
+
+/// The environment variable that may hold the mnemonic of a funded testnet wallet
+/// for tests that need notes to spend.
+const FUNDED_SEED_VARIABLE: &str = "NERDBANK_ZCASH_FUNDED_TESTNET_SEED";
+
+/// The environment variable that may hold the birthday height of the funded testnet wallet.
+const FUNDED_BIRTHDAY_VARIABLE: &str = "NERDBANK_ZCASH_FUNDED_TESTNET_BIRTHDAY";
 pub(crate) const VALID_SAPLING_TESTNET: &str =
     "ztestsapling15740genxvp99m3vut5q7dqm0da9l8nst2njae3kpu6e406peeypk0n78zue0hgxt5gmasaznnm0";
 
@@ -70,6 +91,7 @@ pub(crate) async fn setup_test() -> TestSetup {
             data_file: data_file.into_os_string().into_string().unwrap(),
             network: network.into(),
             min_confirmations: 3,
+            passphrase: None,
         },
         db,
         server_info,
@@ -95,16 +117,86 @@ impl TestSetup {
         Ok((seed, birthday, account.0.id(), account.1))
     }
 
+    /// Imports and syncs the funded testnet wallet whose mnemonic is in the
+    /// `NERDBANK_ZCASH_FUNDED_TESTNET_SEED` environment variable.
+    /// Tests that call this are ignored by default, and fail when run without the variable set.
+    pub async fn create_funded_account(&mut self) -> (AccountId, UnifiedSpendingKey) {
+        let mnemonic = std::env::var(FUNDED_SEED_VARIABLE)
+            .unwrap_or_else(|_| panic!("{} is not set.", FUNDED_SEED_VARIABLE));
+        let seed = SecretVec::new(
+            Mnemonic::<English>::from_phrase(mnemonic.trim())
+                .unwrap()
+                .to_seed("")
+                .to_vec(),
+        );
+        let birthday = std::env::var(FUNDED_BIRTHDAY_VARIABLE)
+            .map(|h| h.parse().unwrap())
+            .unwrap_or_else(|_| {
+                u32::from(
+                    self.network
+                        .activation_height(NetworkUpgrade::Sapling)
+                        .unwrap(),
+                )
+                .into()
+            });
+        let account = self
+            .db
+            .add_account(&seed, zip32::AccountId::ZERO, birthday, &mut self.client)
+            .await
+            .unwrap();
+        self.sync().await;
+        (account.0.id(), account.1)
+    }
+
     pub async fn sync(&mut self) -> SyncUpdateData {
         sync(
             self.server_uri.clone(),
             &self.data_file,
             None,
-            self.db_init.min_confirmations,
             false,
+            SyncOptions::default(),
             CancellationToken::new(),
         )
         .await
         .unwrap()
     }
 }
+
+/// Records a Sapling note received by the account directly in the wallet database,
+/// so that tests can observe how notes in states that are hard to arrange on the chain are reported.
+/// The note is unconfirmed when `height` is `None`, and cannot be spent.
+/// Returns the ID of the transaction that carries the note.
+pub(crate) fn insert_received_note(
+    db_init: &DbInit,
+    account_id: AccountId,
+    height: Option<u32>,
+    value: u64,
+    memo: Option<Vec<u8>>,
+) -> Vec<u8> {
+    let mut txid = vec![0u8; 32];
+    rand::thread_rng().fill_bytes(&mut txid);
+    let mut rcm = vec![0u8; 32];
+    rand::thread_rng().fill_bytes(&mut rcm);
+
+    let conn = open_connection(&db_init.data_file).unwrap();
+    conn.execute(
+        "INSERT INTO transactions (txid, block) VALUES (:txid, :block)",
+        named_params! { ":txid": txid, ":block": height },
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO sapling_received_notes (tx, output_index, account_id, diversifier, value, rcm, is_change, memo)
+		VALUES (:tx, 0, :account_id, :diversifier, :value, :rcm, 0, :memo)",
+        named_params! {
+            ":tx": conn.last_insert_rowid(),
+            ":account_id": u32::from(account_id),
+            ":diversifier": vec![0u8; 11],
+            ":value": value,
+            ":rcm": rcm,
+            ":memo": memo,
+        },
+    )
+    .unwrap();
+
+    txid
+}
@@ -0,0 +1,171 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::RwLock,
+};
+
+use zcash_keys::keys::UnifiedFullViewingKey;
+use zcash_primitives::consensus::Network;
+
+use crate::grpc::destroy_all_channels;
+
+/// Routes the traffic to light wallet servers and parameter downloads through Tor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorOptions {
+    /// The address (`host:port`) of Tor's SOCKS port, e.g. `127.0.0.1:9050`.
+    pub socks_address: String,
+    /// The address (`host:port`) of Tor's HTTP CONNECT tunnel port (`HTTPTunnelPort`),
+    /// through which missing Sapling parameters are downloaded.
+    /// When `None`, missing parameters are not downloaded while Tor is in use.
+    pub http_tunnel_address: Option<String>,
+    /// Whether requests made on behalf of different accounts use separate Tor circuits,
+    /// so that the server cannot link the accounts by the circuit they arrive on.
+    pub isolate_accounts: bool,
+}
+
+lazy_static! {
+    static ref TOR: RwLock<Option<TorOptions>> = RwLock::new(None);
+}
+
+/// Routes all future server traffic through Tor, or stops doing so when `None`.
+///
+/// Cached channels to servers are released so that the next request to each reconnects.
+pub fn set_tor(options: Option<TorOptions>) {
+    *TOR.write().unwrap() = options;
+    destroy_all_channels();
+}
+
+/// Gets the Tor settings, if Tor is in use.
+pub(crate) fn tor_options() -> Option<TorOptions> {
+    TOR.read().unwrap().clone()
+}
+
+/// Gets the key that isolates the Tor circuit used for requests on behalf of an account,
+/// or `None` if Tor is not in use or accounts are not isolated.
+///
+/// Tor puts streams with different SOCKS credentials on different circuits,
+/// so the key is used as both the SOCKS username and password.
+pub(crate) fn account_isolation(network: &Network, ufvk: &UnifiedFullViewingKey) -> Option<String> {
+    if !tor_options().is_some_and(|tor| tor.isolate_accounts) {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    ufvk.encode(network).hash(&mut hasher);
+    Some(format!("account-{:016x}", hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use tokio::{
+        io::{copy_bidirectional, AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+    use zcash_client_backend::proto::service;
+
+    use crate::{
+        grpc::get_isolated_client,
+        test_constants::{lock_global_state, LIGHTSERVER_URI},
+    };
+
+    use super::*;
+
+    /// Stops using Tor when dropped, even if the test fails.
+    struct TorReset;
+
+    impl Drop for TorReset {
+        fn drop(&mut self) {
+            set_tor(None);
+        }
+    }
+
+    /// Starts a SOCKS5 proxy that stands in for Tor, recording the username each connection authenticates with.
+    async fn start_socks_stand_in(usernames: Arc<Mutex<Vec<Option<String>>>>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut client, _)) = listener.accept().await {
+                let usernames = usernames.clone();
+                tokio::spawn(async move {
+                    // Greeting: version, then the authentication methods offered.
+                    let mut header = [0u8; 2];
+                    client.read_exact(&mut header).await?;
+                    let mut methods = vec![0u8; header[1] as usize];
+                    client.read_exact(&mut methods).await?;
+                    let username = if methods.contains(&2) {
+                        client.write_all(&[5, 2]).await?;
+                        let mut length = [0u8; 2];
+                        client.read_exact(&mut length).await?;
+                        let mut username = vec![0u8; length[1] as usize];
+                        client.read_exact(&mut username).await?;
+                        let password_length = client.read_u8().await?;
+                        let mut password = vec![0u8; password_length as usize];
+                        client.read_exact(&mut password).await?;
+                        client.write_all(&[1, 0]).await?;
+                        Some(String::from_utf8_lossy(&username).into_owned())
+                    } else {
+                        client.write_all(&[5, 0]).await?;
+                        None
+                    };
+                    usernames.lock().unwrap().push(username);
+
+                    // Connect request: version, command, reserved, then the target address.
+                    let mut request = [0u8; 4];
+                    client.read_exact(&mut request).await?;
+                    let host = match request[3] {
+                        1 => {
+                            let mut ip = [0u8; 4];
+                            client.read_exact(&mut ip).await?;
+                            std::net::Ipv4Addr::from(ip).to_string()
+                        }
+                        3 => {
+                            let length = client.read_u8().await?;
+                            let mut name = vec![0u8; length as usize];
+                            client.read_exact(&mut name).await?;
+                            String::from_utf8_lossy(&name).into_owned()
+                        }
+                        _ => return Ok(()),
+                    };
+                    let port = client.read_u16().await?;
+
+                    let mut server = TcpStream::connect((host.as_str(), port)).await?;
+                    client.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await?;
+                    copy_bidirectional(&mut client, &mut server).await?;
+                    Ok::<_, std::io::Error>(())
+                });
+            }
+        });
+        address
+    }
+
+    #[tokio_shared_rt::test]
+    async fn test_isolated_streams_through_socks() {
+        let _lock = lock_global_state().await;
+        let usernames = Arc::new(Mutex::new(Vec::new()));
+        let address = start_socks_stand_in(usernames.clone()).await;
+        set_tor(Some(TorOptions {
+            socks_address: address.to_string(),
+            http_tunnel_address: None,
+            isolate_accounts: true,
+        }));
+        let reset = TorReset;
+
+        for isolation in ["account-a", "account-b"] {
+            let mut client =
+                get_isolated_client(LIGHTSERVER_URI.to_owned(), Some(isolation.to_string()))
+                    .await
+                    .unwrap();
+            client.get_lightd_info(service::Empty {}).await.unwrap();
+        }
+        drop(reset);
+
+        let usernames = usernames.lock().unwrap();
+        assert!(usernames.contains(&Some("account-a".to_string())));
+        assert!(usernames.contains(&Some("account-b".to_string())));
+    }
+}